@@ -0,0 +1,30 @@
+use crate::repo::{ArchiveRepo, FileArchive};
+use crate::stream::{XAck, XAdd, XRead};
+
+use std::rc::Rc;
+
+pub struct Components {
+    pub xadd: Box<dyn XAdd>,
+    pub xack: Box<dyn XAck>,
+    pub xread: Box<dyn XRead>,
+    pub archive_repo: Box<dyn ArchiveRepo>,
+}
+
+const REDIS_URL: &str = "redis://redis/";
+/// Mounted from the host, so that it outlives the container
+const ARCHIVE_DIR: &str = "/BUGOUT/archive";
+
+pub fn redis_client() -> Rc<redis::Client> {
+    Rc::new(redis::Client::open(REDIS_URL).expect("redis client"))
+}
+
+impl Components {
+    pub fn new(client: Rc<redis::Client>) -> Self {
+        Components {
+            archive_repo: Box::new(FileArchive::new(ARCHIVE_DIR).expect("archive dir")),
+            xadd: Box::new(client.clone()),
+            xack: Box::new(client.clone()),
+            xread: Box::new(client),
+        }
+    }
+}
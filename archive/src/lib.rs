@@ -0,0 +1,13 @@
+/// it should listen to game states, game over,
+/// score finalized and undo audited events
+///
+/// it should keep finished games on disk, since
+/// redis forgets them after a day
+///
+/// on ProvideArchive:
+///     it should emit ArchiveProvided
+mod components;
+mod repo;
+pub mod stream;
+
+pub use components::*;
@@ -0,0 +1,87 @@
+use super::RepoErr;
+use core_model::GameId;
+use move_model::{FinalScore, GameState};
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use undo_model::api::UndoAudited;
+
+/// What we know about a game once it is over.  The score
+/// may arrive before the final game state does.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedGame {
+    pub game_state: Option<GameState>,
+    pub score: Option<FinalScore>,
+    /// Every undo asked for during the game, in order
+    pub undos: Vec<UndoAudited>,
+}
+
+pub trait ArchiveRepo {
+    fn get(&self, game_id: &GameId) -> Result<Option<ArchivedGame>, RepoErr>;
+    fn put(&self, game_id: &GameId, game: &ArchivedGame) -> Result<(), RepoErr>;
+}
+
+/// Keeps one file per game.  Files never expire.
+pub struct FileArchive {
+    dir: PathBuf,
+}
+
+impl FileArchive {
+    pub fn new(dir: &str) -> Result<Self, RepoErr> {
+        fs::create_dir_all(dir)?;
+        Ok(FileArchive { dir: dir.into() })
+    }
+
+    fn path(&self, game_id: &GameId) -> PathBuf {
+        self.dir.join(format!("{}.bin", game_id.0))
+    }
+}
+
+impl ArchiveRepo for FileArchive {
+    fn get(&self, game_id: &GameId) -> Result<Option<ArchivedGame>, RepoErr> {
+        match fs::read(self.path(game_id)) {
+            Ok(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RepoErr::Io(e)),
+        }
+    }
+
+    fn put(&self, game_id: &GameId, game: &ArchivedGame) -> Result<(), RepoErr> {
+        let bytes = bincode::serialize(game)?;
+        // a crash part way through a write must not
+        // leave a truncated file behind
+        let tmp = self.path(game_id).with_extension("tmp");
+        fs::write(&tmp, bytes)?;
+        fs::rename(tmp, self.path(game_id))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_model::{BoardSize, Rules};
+
+    #[test]
+    fn file_archive_round_trip() {
+        let dir = std::env::temp_dir().join(format!("archive-{}", GameId::new().0));
+        let archive = FileArchive::new(dir.to_str().unwrap()).unwrap();
+        let game_id = GameId::new();
+        assert_eq!(archive.get(&game_id).unwrap(), None);
+
+        let game = ArchivedGame {
+            game_state: Some(GameState::new(
+                game_id.clone(),
+                BoardSize::square(9),
+                Rules::default(),
+                0,
+            )),
+            score: None,
+            undos: vec![],
+        };
+        archive.put(&game_id, &game).unwrap();
+        assert_eq!(archive.get(&game_id).unwrap(), Some(game));
+        fs::remove_dir_all(dir).unwrap()
+    }
+}
@@ -0,0 +1,19 @@
+mod archive;
+
+pub use archive::*;
+
+#[derive(Debug)]
+pub enum RepoErr {
+    Io(std::io::Error),
+    SerDes(Box<bincode::ErrorKind>),
+}
+impl From<std::io::Error> for RepoErr {
+    fn from(e: std::io::Error) -> Self {
+        RepoErr::Io(e)
+    }
+}
+impl From<Box<bincode::ErrorKind>> for RepoErr {
+    fn from(e: Box<bincode::ErrorKind>) -> Self {
+        RepoErr::SerDes(e)
+    }
+}
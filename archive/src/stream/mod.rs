@@ -0,0 +1,32 @@
+mod init;
+mod process;
+mod topics;
+mod xack;
+mod xadd;
+mod xread;
+
+pub use init::*;
+pub use process::*;
+pub use xack::*;
+pub use xadd::*;
+pub use xread::*;
+
+use move_model::{GameOver, GameState, ScoreFinalized};
+use sync_model::api::{ArchiveProvided, ProvideArchive};
+use undo_model::api::UndoAudited;
+
+pub const GROUP_NAME: &str = "archive";
+
+#[derive(Debug, Clone)]
+pub enum StreamOutput {
+    AP(ArchiveProvided),
+}
+
+#[derive(Clone, Debug)]
+pub enum StreamInput {
+    LOG(GameState),
+    GO(GameOver),
+    SF(ScoreFinalized),
+    UA(UndoAudited),
+    PA(ProvideArchive),
+}
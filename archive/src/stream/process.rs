@@ -0,0 +1,216 @@
+use super::*;
+use crate::repo::ArchivedGame;
+use crate::Components;
+use core_model::GameId;
+use log::{error, info};
+use move_model::FinalScore;
+
+pub fn process(reg: &Components) {
+    let mut unacked = Unacknowledged::default();
+    loop {
+        match reg.xread.xread_sorted() {
+            Ok(xrr) => {
+                for (xid, data) in xrr {
+                    consume(&data, reg);
+                    unacked.push(xid, data);
+                }
+            }
+            Err(e) => error!("Stream err {:?}", e),
+        }
+
+        unacked.ack_all(reg)
+    }
+}
+
+fn consume(event: &StreamInput, reg: &Components) {
+    match event {
+        StreamInput::LOG(game_state) if game_state.game_over => {
+            update(&game_state.game_id, reg, |archived| {
+                archived.game_state = Some(game_state.clone())
+            })
+        }
+        StreamInput::LOG(_) => (),
+        StreamInput::GO(game_over) => update(&game_over.game_id, reg, |archived| {
+            archived.score = game_over.score
+        }),
+        StreamInput::SF(score_finalized) => update(&score_finalized.game_id, reg, |archived| {
+            archived.score = Some(score_finalized.score)
+        }),
+        StreamInput::UA(audited) => update(&audited.game_id, reg, |archived| {
+            archived.undos.push(audited.clone())
+        }),
+        StreamInput::PA(provide) => consume_provide_archive(provide, reg),
+    }
+}
+
+/// Game states only reach the archive once the game is
+/// over, though undos are kept as they happen.  Each event
+/// fills in part of the record, in whichever order the
+/// events arrive.
+fn update(game_id: &GameId, reg: &Components, change: impl FnOnce(&mut ArchivedGame)) {
+    match reg.archive_repo.get(game_id) {
+        Ok(found) => {
+            let mut archived = found.unwrap_or_default();
+            change(&mut archived);
+            if let Err(e) = reg.archive_repo.put(game_id, &archived) {
+                error!("could not archive {:?}: {:?}", game_id, e)
+            } else {
+                info!("🗄️ {:?}", game_id)
+            }
+        }
+        Err(e) => error!("could not read archive for {:?}: {:?}", game_id, e),
+    }
+}
+
+fn consume_provide_archive(provide: &ProvideArchive, reg: &Components) {
+    let (game_state, score): (_, Option<FinalScore>) = match reg.archive_repo.get(&provide.game_id)
+    {
+        Ok(Some(archived)) => (archived.game_state, archived.score),
+        Ok(None) => (None, None),
+        Err(e) => {
+            error!("could not read archive for {:?}: {:?}", provide.game_id, e);
+            (None, None)
+        }
+    };
+    if let Err(e) = reg.xadd.xadd(&StreamOutput::AP(ArchiveProvided {
+        game_id: provide.game_id.clone(),
+        reply_to: provide.req_id.clone(),
+        game_state,
+        score,
+        page: provide.page,
+    })) {
+        error!("could not xadd archive provided {:?}", e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::{ArchiveRepo, RepoErr};
+    use core_model::{EventId, ReqId};
+    use move_model::*;
+    use redis_streams::XReadEntryId;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use undo_model::api::UndoOutcome;
+
+    struct FakeArchive(RefCell<HashMap<GameId, ArchivedGame>>);
+    impl ArchiveRepo for Rc<FakeArchive> {
+        fn get(&self, game_id: &GameId) -> Result<Option<ArchivedGame>, RepoErr> {
+            Ok(self.0.borrow().get(game_id).cloned())
+        }
+        fn put(&self, game_id: &GameId, game: &ArchivedGame) -> Result<(), RepoErr> {
+            self.0.borrow_mut().insert(game_id.clone(), game.clone());
+            Ok(())
+        }
+    }
+
+    struct FakeXAdd(Rc<RefCell<Vec<StreamOutput>>>);
+    impl XAdd for FakeXAdd {
+        fn xadd(&self, output: &StreamOutput) -> Result<(), StreamAddErr> {
+            self.0.borrow_mut().push(output.clone());
+            Ok(())
+        }
+    }
+
+    struct NoStream;
+    impl XRead for NoStream {
+        fn xread_sorted(&self) -> Result<Vec<(XReadEntryId, StreamInput)>, XReadErr> {
+            Ok(vec![])
+        }
+    }
+    impl XAck for NoStream {
+        fn ack_changelog(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_game_over(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_score_finalized(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_undo_audited(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_provide_archive(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+    }
+
+    fn components(added: Rc<RefCell<Vec<StreamOutput>>>) -> Components {
+        Components {
+            xadd: Box::new(FakeXAdd(added)),
+            xack: Box::new(NoStream),
+            xread: Box::new(NoStream),
+            archive_repo: Box::new(Rc::new(FakeArchive(RefCell::new(HashMap::new())))),
+        }
+    }
+
+    #[test]
+    fn finished_games_are_provided() {
+        let added = Rc::new(RefCell::new(vec![]));
+        let reg = components(added.clone());
+        let mut game_state =
+            GameState::new(GameId::new(), BoardSize::square(9), Rules::default(), 0);
+        consume(&StreamInput::LOG(game_state.clone()), &reg);
+        let score = score(
+            &game_state.board,
+            &game_state.captures,
+            &Default::default(),
+            6.5,
+        );
+        consume(
+            &StreamInput::GO(GameOver {
+                game_id: game_state.game_id.clone(),
+                event_id: EventId::new(),
+                result: GameResult {
+                    winner: score.area.winner,
+                    reason: EndReason::Score,
+                },
+                score: Some(score),
+            }),
+            &reg,
+        );
+        game_state.game_over = true;
+        consume(&StreamInput::LOG(game_state.clone()), &reg);
+
+        let req_id = ReqId::new();
+        consume(
+            &StreamInput::PA(ProvideArchive {
+                game_id: game_state.game_id.clone(),
+                req_id: req_id.clone(),
+                page: Default::default(),
+            }),
+            &reg,
+        );
+        let added = added.borrow();
+        match &added[..] {
+            [StreamOutput::AP(provided)] => {
+                assert_eq!(provided.reply_to, req_id);
+                assert_eq!(provided.game_state, Some(game_state.clone()));
+                assert_eq!(provided.score, Some(score))
+            }
+            other => panic!("unexpected output {:?}", other),
+        }
+    }
+
+    #[test]
+    fn undos_are_archived() {
+        let reg = components(Rc::new(RefCell::new(vec![])));
+        let game_id = GameId::new();
+        let audited = UndoAudited {
+            game_id: game_id.clone(),
+            player: Player::BLACK,
+            turn: Some(5),
+            to_turn: Some(3),
+            outcome: UndoOutcome::Declined,
+            epoch_millis: 1,
+        };
+        consume(&StreamInput::UA(audited.clone()), &reg);
+
+        let archived = reg.archive_repo.get(&game_id).unwrap().unwrap();
+        assert_eq!(archived.undos, vec![audited]);
+        assert_eq!(archived.game_state, None)
+    }
+}
@@ -0,0 +1,6 @@
+pub const GAME_STATES_CHANGELOG: &str = "bugout-game-states";
+pub const GAME_OVER: &str = "bugout-game-over-ev";
+pub const SCORE_FINALIZED: &str = "bugout-score-finalized-ev";
+pub const UNDO_AUDITED: &str = "bugout-undo-audited-ev";
+pub const PROVIDE_ARCHIVE: &str = "bugout-provide-archive-cmd";
+pub const ARCHIVE_PROVIDED: &str = "bugout-archive-provided-ev";
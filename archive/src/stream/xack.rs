@@ -0,0 +1,122 @@
+use super::topics;
+use super::StreamInput;
+use crate::Components;
+use log::error;
+use redis::{Client, Commands};
+use redis_streams::XReadEntryId;
+
+pub trait XAck {
+    fn ack_changelog(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_game_over(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_score_finalized(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_undo_audited(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_provide_archive(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+}
+
+pub struct StreamAckErr;
+
+pub struct Unacknowledged {
+    changelog: Vec<XReadEntryId>,
+    game_over: Vec<XReadEntryId>,
+    score_finalized: Vec<XReadEntryId>,
+    undo_audited: Vec<XReadEntryId>,
+    provide_archive: Vec<XReadEntryId>,
+}
+
+impl XAck for std::rc::Rc<Client> {
+    fn ack_changelog(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::GAME_STATES_CHANGELOG, xids)
+    }
+    fn ack_game_over(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::GAME_OVER, xids)
+    }
+    fn ack_score_finalized(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::SCORE_FINALIZED, xids)
+    }
+    fn ack_undo_audited(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::UNDO_AUDITED, xids)
+    }
+    fn ack_provide_archive(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::PROVIDE_ARCHIVE, xids)
+    }
+}
+
+fn ack(client: &Client, key: &str, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+    match client.get_connection() {
+        Ok(mut conn) => {
+            let idstrs: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+            let _: usize = conn.xack(key, super::GROUP_NAME, &idstrs)?;
+            Ok(())
+        }
+        Err(_) => Err(StreamAckErr),
+    }
+}
+
+impl From<redis::RedisError> for StreamAckErr {
+    fn from(_: redis::RedisError) -> Self {
+        Self
+    }
+}
+
+impl Unacknowledged {
+    pub fn ack_all(&mut self, reg: &Components) {
+        if !self.changelog.is_empty() {
+            if let Err(_e) = reg.xack.ack_changelog(&self.changelog) {
+                error!("ack for changelog failed")
+            } else {
+                self.changelog.clear();
+            }
+        }
+        if !self.game_over.is_empty() {
+            if let Err(_e) = reg.xack.ack_game_over(&self.game_over) {
+                error!("ack for game over failed")
+            } else {
+                self.game_over.clear();
+            }
+        }
+        if !self.score_finalized.is_empty() {
+            if let Err(_e) = reg.xack.ack_score_finalized(&self.score_finalized) {
+                error!("ack for score finalized failed")
+            } else {
+                self.score_finalized.clear();
+            }
+        }
+        if !self.undo_audited.is_empty() {
+            if let Err(_e) = reg.xack.ack_undo_audited(&self.undo_audited) {
+                error!("ack for undo audited failed")
+            } else {
+                self.undo_audited.clear();
+            }
+        }
+        if !self.provide_archive.is_empty() {
+            if let Err(_e) = reg.xack.ack_provide_archive(&self.provide_archive) {
+                error!("ack for provide archive failed")
+            } else {
+                self.provide_archive.clear();
+            }
+        }
+    }
+
+    pub fn push(&mut self, xid: XReadEntryId, event: StreamInput) {
+        match event {
+            StreamInput::LOG(_) => self.changelog.push(xid),
+            StreamInput::GO(_) => self.game_over.push(xid),
+            StreamInput::SF(_) => self.score_finalized.push(xid),
+            StreamInput::UA(_) => self.undo_audited.push(xid),
+            StreamInput::PA(_) => self.provide_archive.push(xid),
+        }
+    }
+}
+
+const INIT_ACK_CAPACITY: usize = 50;
+impl Default for Unacknowledged {
+    fn default() -> Self {
+        Self {
+            changelog: Vec::with_capacity(INIT_ACK_CAPACITY),
+            game_over: Vec::with_capacity(INIT_ACK_CAPACITY),
+            score_finalized: Vec::with_capacity(INIT_ACK_CAPACITY),
+            undo_audited: Vec::with_capacity(INIT_ACK_CAPACITY),
+            provide_archive: Vec::with_capacity(INIT_ACK_CAPACITY),
+        }
+    }
+}
@@ -1,19 +1,46 @@
 use super::{AlphaNumCoord, Bot};
-use core_model::GameId;
-use move_model::{GameState, Player};
+use core_model::{GameId, ReqId};
+use move_model::{Coord, GameState, Player, Rules};
 use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComputeMove {
     pub game_id: GameId,
+    /// Names this request alone, so that its answer
+    /// is never mistaken for another one's
+    pub req_id: ReqId,
     pub game_state: GameState,
     pub max_visits: Option<u16>,
 }
+
+/// Asks tinybrain to stop thinking about a move which
+/// is no longer wanted, because the game has moved on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CancelCompute {
+    pub game_id: GameId,
+    pub req_id: ReqId,
+    pub turn: u16,
+    pub player: Player,
+}
+
+/// Everything which botlink asks of tinybrain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BotCommand {
+    Compute(ComputeMove),
+    Cancel(CancelCompute),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MoveComputed {
     pub game_id: GameId,
+    /// The request which this answers
+    pub req_id: ReqId,
     pub player: Player,
+    /// The turn which the move was computed for
+    pub turn: u16,
     pub alphanum_coord: Option<AlphaNumCoord>,
+    /// The bot sees no way to win
+    pub resign: bool,
 }
 
 /// This command is sent from gateway, and
@@ -26,7 +53,16 @@ pub struct AttachBot {
     pub game_id: GameId,
     pub player: Player,
     pub board_size: Option<u8>,
+    /// Only given for rectangular boards, whose
+    /// width is then the board size
+    #[serde(default)]
+    pub board_height: Option<u8>,
     pub bot: Bot,
+    #[serde(default)]
+    pub rules: Rules,
+    /// Black's handicap stones, if any
+    #[serde(default)]
+    pub handicap: Vec<Coord>,
 }
 
 /// This reply is sent once a bot is listening
@@ -49,7 +85,10 @@ mod tests {
             game_id: GameId(Uuid::nil()),
             player: Player::BLACK,
             board_size: Some(9),
+            board_height: Some(7),
             bot: Bot::KataGoInstant,
+            rules: Rules::default(),
+            handicap: vec![],
         };
         let json = serde_json::to_string(&expected).expect("to_string");
         let actual: AttachBot = serde_json::from_str(&json).expect("from_str");
@@ -73,7 +112,10 @@ mod tests {
             game_id: GameId(Uuid::nil()),
             player: Player::BLACK,
             board_size: Some(19),
+            board_height: None,
             bot: Bot::KataGoFullStrength,
+            rules: Rules::default(),
+            handicap: vec![],
         };
         let json = serde_json::to_string(&input).expect("to_string");
         assert!(json.contains("gameId"));
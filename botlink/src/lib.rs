@@ -7,7 +7,9 @@ extern crate uuid;
 
 pub mod env;
 pub mod max_visits;
+pub mod outstanding;
 pub mod registry;
 pub mod repo;
 pub mod stream;
 pub mod websocket;
+pub mod workers;
@@ -1,5 +1,5 @@
 extern crate botlink;
-use botlink::{stream, websocket};
+use botlink::{stream, websocket, workers};
 use log::info;
 use std::thread;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -21,8 +21,12 @@ async fn main() {
     let mco = components.move_computed_out.clone();
     let xmm = components.xadder.clone();
     let bsr = components.board_size_repo.clone();
+    let outstanding = components.outstanding.clone();
+    let bco = components.bot_command_out.clone();
+    let dispatch_workers = components.workers.clone();
 
-    thread::spawn(move || stream::xadd_loop(mco, xmm, bsr));
+    thread::spawn(move || workers::dispatch_loop(bco, dispatch_workers));
+    thread::spawn(move || stream::xadd_loop(mco, xmm, bsr, outstanding));
     thread::spawn(move || stream::xread_loop(&mut stream::StreamOpts::from(components)));
     websocket::listen(ws_opts).await;
 }
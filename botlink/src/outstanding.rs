@@ -0,0 +1,115 @@
+use bot_model::api::CancelCompute;
+use core_model::{GameId, ReqId};
+use move_model::Player;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// The move which tinybrain is thinking about in each
+/// game.  Shared between the thread which asks for moves
+/// and the one which writes them, so that answers to
+/// questions nobody is asking any more can be dropped.
+/// Each request is kept in the form needed to cancel it.
+#[derive(Clone, Default)]
+pub struct Outstanding(Arc<Mutex<HashMap<GameId, CancelCompute>>>);
+
+impl Outstanding {
+    /// Remembers a request under a new id, unless the same
+    /// turn is already being computed.  Returns None for a
+    /// duplicate.
+    pub fn start(&self, game_id: &GameId, turn: u16, player: Player) -> Option<ReqId> {
+        let mut requests = self.0.lock().expect("outstanding lock");
+        match requests.get(game_id) {
+            Some(r) if r.turn == turn && r.player == player => None,
+            _ => {
+                let req_id = ReqId::new();
+                requests.insert(
+                    game_id.clone(),
+                    CancelCompute {
+                        game_id: game_id.clone(),
+                        req_id: req_id.clone(),
+                        turn,
+                        player,
+                    },
+                );
+                Some(req_id)
+            }
+        }
+    }
+
+    /// Forgets the request for this game unless it is for
+    /// the given turn, and returns it so that the
+    /// computation can be cancelled
+    pub fn cancel_unless(&self, game_id: &GameId, turn: u16) -> Option<CancelCompute> {
+        let mut requests = self.0.lock().expect("outstanding lock");
+        match requests.get(game_id) {
+            Some(r) if r.turn != turn => requests.remove(game_id),
+            _ => None,
+        }
+    }
+
+    /// Forgets the request for this game, whatever its turn
+    pub fn cancel(&self, game_id: &GameId) -> Option<CancelCompute> {
+        self.0.lock().expect("outstanding lock").remove(game_id)
+    }
+
+    /// True if the request is still waiting on an answer
+    pub fn wants(&self, game_id: &GameId, req_id: &ReqId) -> bool {
+        let requests = self.0.lock().expect("outstanding lock");
+        requests.get(game_id).map(|r| &r.req_id == req_id) == Some(true)
+    }
+
+    /// True if the answer is to the request we are waiting
+    /// on, which is then forgotten
+    pub fn finish(&self, game_id: &GameId, req_id: &ReqId) -> bool {
+        let mut requests = self.0.lock().expect("outstanding lock");
+        match requests.get(game_id) {
+            Some(r) if &r.req_id == req_id => requests.remove(game_id).is_some(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_stale_answers() {
+        let outstanding = Outstanding::default();
+        let game_id = GameId::new();
+        let first = outstanding.start(&game_id, 4, Player::WHITE).unwrap();
+        assert_eq!(outstanding.start(&game_id, 4, Player::WHITE), None);
+
+        // an undo took the game back to turn 2
+        let cancelled = outstanding.cancel_unless(&game_id, 2).unwrap();
+        assert_eq!(
+            (cancelled.req_id.clone(), cancelled.turn),
+            (first.clone(), 4)
+        );
+        assert!(!outstanding.finish(&game_id, &first));
+
+        let second = outstanding.start(&game_id, 2, Player::WHITE).unwrap();
+        assert_eq!(outstanding.cancel_unless(&game_id, 2), None);
+        assert!(outstanding.finish(&game_id, &second));
+        assert_eq!(outstanding.cancel(&game_id), None)
+    }
+
+    #[test]
+    fn drops_answers_to_an_earlier_request_for_the_same_turn() {
+        let outstanding = Outstanding::default();
+        let game_id = GameId::new();
+        let first = outstanding.start(&game_id, 4, Player::WHITE).unwrap();
+
+        // the game went back to turn 2 and forward to 4 again
+        // before the first answer arrived
+        outstanding.cancel_unless(&game_id, 2);
+        let second = outstanding.start(&game_id, 4, Player::WHITE).unwrap();
+        assert_ne!(first, second);
+
+        assert!(!outstanding.wants(&game_id, &first));
+        assert!(outstanding.wants(&game_id, &second));
+        assert!(!outstanding.finish(&game_id, &first));
+        assert!(outstanding.finish(&game_id, &second));
+        assert!(!outstanding.wants(&game_id, &second))
+    }
+}
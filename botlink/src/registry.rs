@@ -1,8 +1,10 @@
+use crate::outstanding::Outstanding;
 use crate::repo::*;
 use crate::stream::xack::XAck;
 use crate::stream::xadd::*;
 use crate::stream::xread::XReader;
-use bot_model::api::{ComputeMove, MoveComputed};
+use crate::workers::Workers;
+use bot_model::api::{BotCommand, MoveComputed};
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use redis::Client;
 use std::sync::Arc;
@@ -13,10 +15,12 @@ pub struct Components {
     pub xreader: Box<dyn XReader>,
     pub xadder: Arc<dyn XAdder>,
     pub xack: Arc<dyn XAck>,
-    pub compute_move_in: Sender<ComputeMove>,
-    pub compute_move_out: Receiver<ComputeMove>,
+    pub bot_command_in: Sender<BotCommand>,
+    pub bot_command_out: Receiver<BotCommand>,
     pub move_computed_in: Sender<MoveComputed>,
     pub move_computed_out: Receiver<MoveComputed>,
+    pub outstanding: Outstanding,
+    pub workers: Workers,
 }
 
 const REDIS_URL: &str = "redis://redis/";
@@ -26,22 +30,26 @@ pub fn create_redis_client() -> Arc<redis::Client> {
 
 impl Components {
     pub fn new(client: Arc<Client>) -> Self {
-        let (compute_move_in, compute_move_out): (Sender<ComputeMove>, Receiver<ComputeMove>) =
+        let (bot_command_in, bot_command_out): (Sender<BotCommand>, Receiver<BotCommand>) =
             unbounded();
 
         let (move_computed_in, move_computed_out): (Sender<MoveComputed>, Receiver<MoveComputed>) =
             unbounded();
 
+        let outstanding = Outstanding::default();
+
         Components {
             attachment_repo: Box::new(client.clone()),
             board_size_repo: Arc::new(client.clone()),
             xreader: Box::new(client.clone()),
             xadder: Arc::new(client.clone()),
             xack: Arc::new(client),
-            compute_move_in,
-            compute_move_out,
+            bot_command_in,
+            bot_command_out,
             move_computed_in,
             move_computed_out,
+            workers: Workers::new(outstanding.clone()),
+            outstanding,
         }
     }
 }
@@ -1,28 +1,30 @@
 use super::expire;
 use super::RepoErr;
 use core_model::GameId;
+use move_model::BoardSize;
 use redis::{Client, Commands};
 use std::sync::Arc;
 
 pub trait BoardSizeRepo: Send + Sync {
-    fn get(&self, game_id: &GameId) -> Result<u16, RepoErr>;
+    fn get(&self, game_id: &GameId) -> Result<BoardSize, RepoErr>;
 
-    fn put(&self, game_id: &GameId, board_size: u16) -> Result<(), RepoErr>;
+    fn put(&self, game_id: &GameId, board_size: BoardSize) -> Result<(), RepoErr>;
 }
 
 impl BoardSizeRepo for Arc<Client> {
-    fn get(&self, game_id: &GameId) -> Result<u16, RepoErr> {
+    fn get(&self, game_id: &GameId) -> Result<BoardSize, RepoErr> {
         if let Ok(mut conn) = self.get_connection() {
-            let result = conn.get(board_size_key(&game_id))?;
+            let bytes: Vec<u8> = conn.get(board_size_key(game_id))?;
             expire(&board_size_key(game_id), &mut conn)?;
-            Ok(result)
+            bincode::deserialize(&bytes).map_err(RepoErr::SerDes)
         } else {
             Err(RepoErr::Conn)
         }
     }
-    fn put(&self, game_id: &GameId, board_size: u16) -> Result<(), RepoErr> {
+    fn put(&self, game_id: &GameId, board_size: BoardSize) -> Result<(), RepoErr> {
         if let Ok(mut conn) = self.get_connection() {
-            conn.set(board_size_key(&game_id), board_size)?;
+            let bytes = bincode::serialize(&board_size).map_err(RepoErr::SerDes)?;
+            conn.set(board_size_key(game_id), bytes)?;
             expire(&board_size_key(game_id), &mut conn)?;
             Ok(())
         } else {
@@ -3,7 +3,13 @@ use log::warn;
 use redis::{Client, Commands};
 pub fn create_consumer_group(client: &Client) {
     let mut conn = client.get_connection().expect("group create conn");
-    let to_create = vec![topics::GAME_STATES_CHANGELOG, topics::ATTACH_BOT_CMD];
+    let to_create = vec![
+        topics::GAME_STATES_CHANGELOG,
+        topics::ATTACH_BOT_CMD,
+        topics::GAME_OVER_EV,
+        topics::DEAD_STONES_MARKED_EV,
+        topics::QUIT_GAME_CMD,
+    ];
     for topic in to_create {
         let created: Result<(), _> = conn.xgroup_create_mkstream(topic, GROUP_NAME, "$");
         if let Err(e) = created {
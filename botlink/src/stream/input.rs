@@ -2,4 +2,7 @@
 pub enum StreamInput {
     AB(bot_model::api::AttachBot),
     GS(move_model::GameState),
+    GO(move_model::GameOver),
+    DSM(move_model::DeadStonesMarked),
+    QG(move_model::QuitGame),
 }
@@ -15,9 +15,9 @@ pub use write_moves::xadd_loop;
 
 use crate::max_visits::*;
 use crate::repo::Attachment;
-use bot_model::api::{AttachBot, ComputeMove};
+use bot_model::api::{AttachBot, BotCommand, CancelCompute, ComputeMove};
 use log::{error, info};
-use move_model::GameState;
+use move_model::{AcceptScore, BoardSize, Coord, EndReason, GameState, Player};
 
 const GROUP_NAME: &str = "botlink";
 
@@ -47,23 +47,78 @@ fn process(event: &StreamInput, opts: &mut StreamOpts) {
         StreamInput::GS(game_state) => {
             process_game_state(&game_state, opts);
         }
+        StreamInput::GO(game_over) => {
+            if let Some(cancel) = opts.outstanding.cancel(&game_over.game_id) {
+                cancel_compute(cancel, opts)
+            }
+            if game_over.result.reason == EndReason::Score {
+                accept_score(&game_over.game_id, &[], &[], opts)
+            }
+        }
+        StreamInput::DSM(marked) => {
+            accept_score(&marked.game_id, &marked.dead, &marked.accepted, opts)
+        }
+        StreamInput::QG(quit) => {
+            // nobody is left to play the bot's move
+            if let Some(cancel) = opts.outstanding.cancel(&quit.game_id) {
+                cancel_compute(cancel, opts)
+            }
+        }
+    }
+}
+
+/// Bots can't tell which stones are dead, so they only
+/// accept the score with nothing marked.  Otherwise the
+/// human could mark the bot's live groups dead and win.
+fn accept_score(
+    game_id: &core_model::GameId,
+    dead: &[Coord],
+    accepted: &[Player],
+    opts: &StreamOpts,
+) {
+    if !dead.is_empty() {
+        info!(
+            "Bot won't accept {} dead stones in {:?}",
+            dead.len(),
+            game_id
+        );
+        return;
+    }
+    for player in &[Player::BLACK, Player::WHITE] {
+        if accepted.contains(player) {
+            continue;
+        }
+        match opts.attachment_repo.get(game_id, *player) {
+            Ok(Some(_)) => {
+                if let Err(e) = opts.xadd.xadd_accept_score(&AcceptScore {
+                    game_id: game_id.clone(),
+                    player: *player,
+                }) {
+                    error!("Error xadd accept score {:?}", e)
+                }
+            }
+            Ok(None) => (),
+            Err(e) => error!("Attachment repo {:?}", e),
+        }
     }
 }
 
 fn process_attach_bot(ab: &AttachBot, opts: &mut StreamOpts) {
     use bot_model::api::BotAttached;
-    let mut game_state = move_model::GameState {
-        game_id: core_model::GameId(ab.game_id.0),
-        captures: move_model::Captures::default(),
-        turn: 1,
-        moves: vec![],
-        board: move_model::Board::default(),
-        player_up: move_model::Player::BLACK,
+    let width = ab
+        .board_size
+        .map(|bs| bs.into())
+        .unwrap_or_else(|| BoardSize::default().width);
+    let board_size = BoardSize {
+        width,
+        height: ab.board_height.map(|h| h.into()).unwrap_or(width),
     };
-
-    if let Some(bs) = ab.board_size {
-        game_state.board.size = bs.into()
-    }
+    let game_state = move_model::GameState::starting(
+        core_model::GameId(ab.game_id.0),
+        board_size,
+        ab.rules,
+        ab.handicap.clone(),
+    );
 
     if let Err(e) = opts.attachment_repo.put(&Attachment {
         game_id: ab.game_id.clone(),
@@ -93,28 +148,58 @@ fn process_attach_bot(ab: &AttachBot, opts: &mut StreamOpts) {
 fn process_game_state(game_state: &GameState, opts: &mut StreamOpts) {
     let player_up = game_state.player_up;
     let game_id = &game_state.game_id;
+    // an undo takes the game back past whatever the
+    // bot was thinking about
+    let stale = if game_state.game_over {
+        opts.outstanding.cancel(game_id)
+    } else {
+        opts.outstanding.cancel_unless(game_id, game_state.turn)
+    };
+    if let Some(cancel) = stale {
+        cancel_compute(cancel, opts)
+    }
+    if game_state.game_over {
+        info!("Game over, no more moves for {:?}", game_id);
+        return;
+    }
     match opts.attachment_repo.get(&game_id, player_up) {
-        Ok(Some(attachment)) => {
-            info!(
-                "🐌 game turn: {:?}, playerup: {:?}, moves: {}",
-                game_state.turn,
-                game_state.player_up,
-                game_state.moves.len()
-            );
-
-            if let Err(e) = opts.compute_move_in.send(ComputeMove {
-                game_id: game_id.clone(),
-                game_state: game_state.clone(),
-                max_visits: max_visits(attachment.bot),
-            }) {
-                error!("WS SEND ERROR {:?}", e)
+        Ok(Some(attachment)) => match opts.outstanding.start(game_id, game_state.turn, player_up) {
+            None => info!(
+                "Already computing turn {} of {:?}",
+                game_state.turn, game_id
+            ),
+            Some(req_id) => {
+                info!(
+                    "🐌 game turn: {:?}, playerup: {:?}, moves: {}",
+                    game_state.turn,
+                    game_state.player_up,
+                    game_state.moves.len()
+                );
+
+                if let Err(e) = opts.bot_command_in.send(BotCommand::Compute(ComputeMove {
+                    game_id: game_id.clone(),
+                    req_id,
+                    game_state: game_state.clone(),
+                    max_visits: max_visits(attachment.bot),
+                })) {
+                    error!("WS SEND ERROR {:?}", e)
+                }
             }
-        }
+        },
         Ok(None) => info!("Ignoring {:?} {:?}", game_id, player_up),
         Err(e) => error!("Attachment repo {:?}", e),
     }
 }
 
+/// Tells tinybrain to stop thinking about a move which
+/// nobody wants any more
+fn cancel_compute(cancel: CancelCompute, opts: &StreamOpts) {
+    info!("🛑 cancel turn {} of {:?}", cancel.turn, cancel.game_id);
+    if let Err(e) = opts.bot_command_in.send(BotCommand::Cancel(cancel)) {
+        error!("WS SEND ERROR {:?}", e)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +212,7 @@ mod tests {
     use crossbeam_channel::{select, unbounded, Receiver};
     use move_model::*;
     use redis_streams::XReadEntryId;
+    use std::collections::HashSet;
     use std::sync::atomic::{AtomicU16, Ordering};
     use std::sync::{Arc, Mutex};
     use std::thread;
@@ -152,20 +238,26 @@ mod tests {
         }
     }
 
-    static FAKE_BOARD_SIZE: AtomicU16 = AtomicU16::new(0);
+    static FAKE_BOARD_WIDTH: AtomicU16 = AtomicU16::new(0);
+    static FAKE_BOARD_HEIGHT: AtomicU16 = AtomicU16::new(0);
     struct FakeBoardSizeRepo;
     impl BoardSizeRepo for FakeBoardSizeRepo {
-        fn get(&self, _game_id: &GameId) -> Result<u16, RepoErr> {
-            Ok(FAKE_BOARD_SIZE.load(Ordering::SeqCst))
+        fn get(&self, _game_id: &GameId) -> Result<BoardSize, RepoErr> {
+            Ok(BoardSize {
+                width: FAKE_BOARD_WIDTH.load(Ordering::SeqCst),
+                height: FAKE_BOARD_HEIGHT.load(Ordering::SeqCst),
+            })
         }
-        fn put(&self, _game_id: &GameId, board_size: u16) -> Result<(), RepoErr> {
-            FAKE_BOARD_SIZE.store(board_size, Ordering::SeqCst);
+        fn put(&self, _game_id: &GameId, board_size: BoardSize) -> Result<(), RepoErr> {
+            FAKE_BOARD_WIDTH.store(board_size.width, Ordering::SeqCst);
+            FAKE_BOARD_HEIGHT.store(board_size.height, Ordering::SeqCst);
             Ok(())
         }
     }
 
     struct FakeXAdder {
         added_in: Sender<move_model::GameState>,
+        accepted: Arc<Mutex<Vec<AcceptScore>>>,
     }
     impl xadd::XAdder for FakeXAdder {
         fn xadd_game_state(
@@ -183,6 +275,13 @@ mod tests {
         fn xadd_make_move_command(&self, _command: &MakeMove) -> Result<(), StreamAddError> {
             Ok(info!("Doing nothing for xadd make move"))
         }
+        fn xadd_accept_score(&self, command: &AcceptScore) -> Result<(), StreamAddError> {
+            self.accepted.lock().expect("lock").push(command.clone());
+            Ok(())
+        }
+        fn xadd_resign(&self, _command: &Resign) -> Result<(), StreamAddError> {
+            Ok(info!("Doing nothing for xadd resign"))
+        }
     }
 
     struct FakeXReader {
@@ -212,9 +311,55 @@ mod tests {
         }
     }
 
+    struct FakeXAck {
+        acked: Mutex<Vec<XReadEntryId>>,
+    }
+    impl crate::stream::xack::XAck for FakeXAck {
+        fn ack_attach_bot(&self, xids: &[XReadEntryId]) -> Result<(), super::xack::StreamAckError> {
+            if let Ok(mut a) = self.acked.lock() {
+                a.extend(xids)
+            }
+            Ok(())
+        }
+
+        fn ack_game_states_changelog(
+            &self,
+            xids: &[XReadEntryId],
+        ) -> Result<(), super::xack::StreamAckError> {
+            if let Ok(mut a) = self.acked.lock() {
+                a.extend(xids)
+            }
+            Ok(())
+        }
+
+        fn ack_game_over(&self, xids: &[XReadEntryId]) -> Result<(), super::xack::StreamAckError> {
+            if let Ok(mut a) = self.acked.lock() {
+                a.extend(xids)
+            }
+            Ok(())
+        }
+
+        fn ack_dead_stones_marked(
+            &self,
+            xids: &[XReadEntryId],
+        ) -> Result<(), super::xack::StreamAckError> {
+            if let Ok(mut a) = self.acked.lock() {
+                a.extend(xids)
+            }
+            Ok(())
+        }
+
+        fn ack_quit_game(&self, xids: &[XReadEntryId]) -> Result<(), super::xack::StreamAckError> {
+            if let Ok(mut a) = self.acked.lock() {
+                a.extend(xids)
+            }
+            Ok(())
+        }
+    }
+
     #[test]
     fn process_test() {
-        let (compute_move_in, _): (Sender<ComputeMove>, _) = unbounded();
+        let (bot_command_in, _): (Sender<BotCommand>, _) = unbounded();
         let (added_in, added_out): (
             Sender<move_model::GameState>,
             Receiver<move_model::GameState>,
@@ -244,39 +389,21 @@ mod tests {
                     game_id: GAME_ID.clone(),
                     player,
                     board_size,
+                    board_height: None,
                     bot: Bot::KataGoOneStar,
+                    rules: move_model::Rules::default(),
+                    handicap: vec![],
                 }),
             )]),
         });
-        let xadder = Arc::new(FakeXAdder { added_in });
-        struct FakeXAck {
-            acked: Mutex<Vec<XReadEntryId>>,
-        };
-        impl crate::stream::xack::XAck for FakeXAck {
-            fn ack_attach_bot(
-                &self,
-                xids: &[XReadEntryId],
-            ) -> Result<(), super::xack::StreamAckError> {
-                if let Ok(mut a) = self.acked.lock() {
-                    a.extend(xids)
-                }
-                Ok(())
-            }
-
-            fn ack_game_states_changelog(
-                &self,
-                xids: &[XReadEntryId],
-            ) -> Result<(), super::xack::StreamAckError> {
-                if let Ok(mut a) = self.acked.lock() {
-                    a.extend(xids)
-                }
-                Ok(())
-            }
-        }
-
+        let xadder = Arc::new(FakeXAdder {
+            added_in,
+            accepted: Arc::new(Mutex::new(vec![])),
+        });
         thread::spawn(move || {
             let mut opts = StreamOpts {
-                compute_move_in,
+                bot_command_in,
+                outstanding: crate::outstanding::Outstanding::default(),
                 attachment_repo,
                 board_size_repo,
                 xread: xreader,
@@ -304,4 +431,115 @@ mod tests {
         thread::sleep(Duration::from_millis(1));
         assert!(abr.get(&GAME_ID, player).expect("ab repo").is_some());
     }
+
+    #[test]
+    fn quit_cancels_compute() {
+        let (bot_command_in, bot_command_out) = unbounded();
+        let (added_in, _) = unbounded();
+        let game_id = GameId::new();
+        let mut opts = StreamOpts {
+            bot_command_in,
+            outstanding: crate::outstanding::Outstanding::default(),
+            attachment_repo: Box::new(FakeAttachmentRepo {
+                members: Arc::new(Mutex::new(vec![Attachment {
+                    game_id: game_id.clone(),
+                    player: Player::BLACK,
+                    bot: Bot::KataGoOneStar,
+                }])),
+            }),
+            board_size_repo: Arc::new(FakeBoardSizeRepo),
+            xread: Box::new(FakeXReader {
+                incoming_game_state: Arc::new(Mutex::new(vec![])),
+                init_data: Mutex::new(vec![]),
+            }),
+            xadd: Arc::new(FakeXAdder {
+                added_in,
+                accepted: Arc::new(Mutex::new(vec![])),
+            }),
+            xack: Arc::new(FakeXAck {
+                acked: Mutex::new(vec![]),
+            }),
+        };
+
+        let game_state = GameState::new(game_id.clone(), BoardSize::square(9), Rules::default(), 0);
+        process(&StreamInput::GS(game_state), &mut opts);
+        let req_id = match bot_command_out.try_recv() {
+            Ok(BotCommand::Compute(compute_move)) => compute_move.req_id,
+            other => panic!("expected compute, got {:?}", other),
+        };
+
+        process(
+            &StreamInput::QG(QuitGame {
+                game_id: game_id.clone(),
+            }),
+            &mut opts,
+        );
+        match bot_command_out.try_recv() {
+            Ok(BotCommand::Cancel(cancel)) => assert_eq!(cancel.req_id, req_id),
+            other => panic!("expected cancel, got {:?}", other),
+        }
+        // the answer, if it still comes, is dropped
+        assert!(!opts.outstanding.finish(&game_id, &req_id))
+    }
+
+    #[test]
+    fn accepts_only_when_nothing_is_marked_dead() {
+        let (bot_command_in, _) = unbounded();
+        let (added_in, _) = unbounded();
+        let accepted = Arc::new(Mutex::new(vec![]));
+        let game_id = GameId::new();
+        let mut opts = StreamOpts {
+            bot_command_in,
+            outstanding: crate::outstanding::Outstanding::default(),
+            attachment_repo: Box::new(FakeAttachmentRepo {
+                members: Arc::new(Mutex::new(vec![Attachment {
+                    game_id: game_id.clone(),
+                    player: Player::WHITE,
+                    bot: Bot::KataGoOneStar,
+                }])),
+            }),
+            board_size_repo: Arc::new(FakeBoardSizeRepo),
+            xread: Box::new(FakeXReader {
+                incoming_game_state: Arc::new(Mutex::new(vec![])),
+                init_data: Mutex::new(vec![]),
+            }),
+            xadd: Arc::new(FakeXAdder {
+                added_in,
+                accepted: accepted.clone(),
+            }),
+            xack: Arc::new(FakeXAck {
+                acked: Mutex::new(vec![]),
+            }),
+        };
+
+        // black marks the bot's group dead, and accepts
+        let mut board = Board::default();
+        let white_group = vec![Coord::of(3, 3), Coord::of(3, 4)];
+        for c in &white_group {
+            board.place(*c, Player::WHITE)
+        }
+        let dead: HashSet<Coord> = white_group.iter().cloned().collect();
+        let marked = |dead: &HashSet<Coord>| {
+            StreamInput::DSM(DeadStonesMarked {
+                game_id: game_id.clone(),
+                event_id: EventId::new(),
+                dead: dead.iter().cloned().collect(),
+                accepted: vec![Player::BLACK],
+                score: score(&board, &Captures::default(), dead, DEFAULT_KOMI),
+            })
+        };
+        process(&marked(&dead), &mut opts);
+        assert!(accepted.lock().expect("lock").is_empty());
+
+        // and then thinks better of it
+        process(&marked(&HashSet::new()), &mut opts);
+        let accepted = accepted.lock().expect("lock").clone();
+        assert_eq!(
+            accepted,
+            vec![AcceptScore {
+                game_id: game_id.clone(),
+                player: Player::WHITE
+            }]
+        )
+    }
 }
@@ -1,7 +1,8 @@
 use super::*;
+use crate::outstanding::Outstanding;
 use crate::registry::Components;
 use crate::repo::{AttachmentRepo, BoardSizeRepo};
-use bot_model::api::ComputeMove;
+use bot_model::api::BotCommand;
 use crossbeam_channel::Sender;
 use std::sync::Arc;
 
@@ -11,7 +12,8 @@ pub struct StreamOpts {
     pub xread: Box<dyn xread::XReader>,
     pub xadd: Arc<dyn xadd::XAdder>,
     pub xack: Arc<dyn xack::XAck>,
-    pub compute_move_in: Sender<ComputeMove>,
+    pub bot_command_in: Sender<BotCommand>,
+    pub outstanding: Outstanding,
 }
 
 impl StreamOpts {
@@ -22,7 +24,8 @@ impl StreamOpts {
             xread: components.xreader,
             xadd: components.xadder,
             xack: components.xack,
-            compute_move_in: components.compute_move_in,
+            bot_command_in: components.bot_command_in,
+            outstanding: components.outstanding,
         }
     }
 }
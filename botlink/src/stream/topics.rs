@@ -2,3 +2,8 @@ pub const ATTACH_BOT_CMD: &str = "bugout-attach-bot-cmd";
 pub const GAME_STATES_CHANGELOG: &str = "bugout-game-states";
 pub const MAKE_MOVE_CMD: &str = "bugout-make-move-cmd";
 pub const BOT_ATTACHED_EV: &str = "bugout-bot-attached-ev";
+pub const GAME_OVER_EV: &str = "bugout-game-over-ev";
+pub const DEAD_STONES_MARKED_EV: &str = "bugout-dead-stones-marked-ev";
+pub const QUIT_GAME_CMD: &str = "bugout-quit-game-cmd";
+pub const ACCEPT_SCORE_CMD: &str = "bugout-accept-score-cmd";
+pub const RESIGN_CMD: &str = "bugout-resign-cmd";
@@ -5,6 +5,9 @@ use redis_streams::XReadEntryId;
 pub struct Unacknowledged {
     attach_bot: Vec<XReadEntryId>,
     game_states: Vec<XReadEntryId>,
+    game_over: Vec<XReadEntryId>,
+    dead_stones_marked: Vec<XReadEntryId>,
+    quit_game: Vec<XReadEntryId>,
 }
 
 impl Unacknowledged {
@@ -24,11 +27,38 @@ impl Unacknowledged {
                 self.game_states.clear();
             }
         }
+
+        if !self.game_over.is_empty() {
+            if let Err(_e) = opts.xack.ack_game_over(&self.game_over) {
+                error!("ack for go failed")
+            } else {
+                self.game_over.clear();
+            }
+        }
+
+        if !self.dead_stones_marked.is_empty() {
+            if let Err(_e) = opts.xack.ack_dead_stones_marked(&self.dead_stones_marked) {
+                error!("ack for dsm failed")
+            } else {
+                self.dead_stones_marked.clear();
+            }
+        }
+
+        if !self.quit_game.is_empty() {
+            if let Err(_e) = opts.xack.ack_quit_game(&self.quit_game) {
+                error!("ack for qg failed")
+            } else {
+                self.quit_game.clear();
+            }
+        }
     }
     pub fn push(&mut self, xid: XReadEntryId, event: &StreamInput) {
         match event {
             StreamInput::GS(_) => self.game_states.push(xid),
             StreamInput::AB(_) => self.attach_bot.push(xid),
+            StreamInput::GO(_) => self.game_over.push(xid),
+            StreamInput::DSM(_) => self.dead_stones_marked.push(xid),
+            StreamInput::QG(_) => self.quit_game.push(xid),
         }
     }
 }
@@ -42,6 +72,9 @@ impl Default for Unacknowledged {
         Self {
             attach_bot: nv(),
             game_states: nv(),
+            game_over: nv(),
+            dead_stones_marked: nv(),
+            quit_game: nv(),
         }
     }
 }
@@ -1,10 +1,11 @@
 use super::xadd::XAdder;
+use crate::outstanding::Outstanding;
 use crate::repo::BoardSizeRepo;
 use bot_model::{api::MoveComputed, AlphaNumCoord};
 use core_model::ReqId;
 use crossbeam_channel::{select, Receiver};
-use log::error;
-use move_model::{Coord, MakeMove};
+use log::{error, info};
+use move_model::{BoardSize, Coord, MakeMove, Resign};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -12,11 +13,20 @@ pub fn xadd_loop(
     move_computed_out: Receiver<MoveComputed>,
     xadder: Arc<dyn XAdder>,
     board_size_repo: Arc<dyn BoardSizeRepo>,
+    outstanding: Outstanding,
 ) {
     loop {
         select! { recv(move_computed_out) -> msg =>
             match msg {
-                Ok(MoveComputed { game_id, player, alphanum_coord }) => {
+                Ok(MoveComputed { game_id, req_id, turn, .. }) if !outstanding.finish(&game_id, &req_id) => {
+                    info!("Dropping stale move for turn {} of {:?}", turn, game_id)
+                }
+                Ok(MoveComputed { game_id, player, resign: true, .. }) => {
+                    if let Err(e) = xadder.xadd_resign(&Resign { game_id, player }) {
+                        error!("could not xadd resign command : {:?}",e)
+                    }
+                }
+                Ok(MoveComputed { game_id, player, alphanum_coord, .. }) => {
                     if let Ok(board_size) = board_size_repo.get(&game_id) {
                         let coord = alphanum_coord.map(|a|convert(a, board_size));
 
@@ -36,7 +46,7 @@ pub fn xadd_loop(
     }
 }
 
-fn convert(a: AlphaNumCoord, board_size: u16) -> Coord {
+fn convert(a: AlphaNumCoord, board_size: BoardSize) -> Coord {
     let r: Vec<char> = (b'A'..=b'Z')
         .filter(|l| l != &b'I')
         .map(char::from)
@@ -45,7 +55,7 @@ fn convert(a: AlphaNumCoord, board_size: u16) -> Coord {
 
     Coord {
         x,
-        y: board_size - a.1,
+        y: board_size.height - a.1,
     }
 }
 
@@ -55,7 +65,7 @@ mod tests {
     #[test]
     fn test_convert() {
         let a = AlphaNumCoord('A', 1);
-        let board_size = 9;
+        let board_size = BoardSize::square(9);
         let actual = convert(a, board_size);
         let expected = Coord { x: 0, y: 8 };
         assert_eq!(actual, expected)
@@ -64,9 +74,21 @@ mod tests {
     #[test]
     fn test_skip_i() {
         let j = AlphaNumCoord('J', 19);
-        let board_size = 19;
+        let board_size = BoardSize::square(19);
         let actual = convert(j, board_size);
         let expected = Coord { x: 8, y: 0 };
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn test_rectangular_rows() {
+        let a = AlphaNumCoord('G', 1);
+        let board_size = BoardSize {
+            width: 7,
+            height: 5,
+        };
+        let actual = convert(a, board_size);
+        let expected = Coord { x: 6, y: 4 };
+        assert_eq!(actual, expected)
+    }
 }
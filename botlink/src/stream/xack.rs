@@ -6,6 +6,9 @@ use std::sync::Arc;
 pub trait XAck: Send + Sync {
     fn ack_attach_bot(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError>;
     fn ack_game_states_changelog(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError>;
+    fn ack_game_over(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError>;
+    fn ack_dead_stones_marked(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError>;
+    fn ack_quit_game(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError>;
 }
 pub struct StreamAckError;
 
@@ -17,6 +20,18 @@ impl XAck for Arc<Client> {
     fn ack_game_states_changelog(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError> {
         ack(self, topics::GAME_STATES_CHANGELOG, xids)
     }
+
+    fn ack_game_over(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError> {
+        ack(self, topics::GAME_OVER_EV, xids)
+    }
+
+    fn ack_dead_stones_marked(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError> {
+        ack(self, topics::DEAD_STONES_MARKED_EV, xids)
+    }
+
+    fn ack_quit_game(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckError> {
+        ack(self, topics::QUIT_GAME_CMD, xids)
+    }
 }
 
 fn ack(client: &Client, key: &str, ids: &[XReadEntryId]) -> Result<(), StreamAckError> {
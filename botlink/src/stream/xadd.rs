@@ -1,7 +1,7 @@
 use crate::stream::topics;
 use bot_model::api::BotAttached;
 use move_model;
-use move_model::{Coord, MakeMove};
+use move_model::{AcceptScore, Coord, MakeMove, Resign};
 use redis::Client;
 use redis::RedisError;
 
@@ -12,6 +12,8 @@ pub trait XAdder: Send + Sync {
     fn xadd_game_state(&self, game_state: &move_model::GameState) -> Result<(), StreamAddError>;
     fn xadd_make_move_command(&self, command: &MakeMove) -> Result<(), StreamAddError>;
     fn xadd_bot_attached(&self, bot_attached: BotAttached) -> Result<(), StreamAddError>;
+    fn xadd_accept_score(&self, command: &AcceptScore) -> Result<(), StreamAddError>;
+    fn xadd_resign(&self, command: &Resign) -> Result<(), StreamAddError>;
 }
 
 #[derive(Debug)]
@@ -94,6 +96,48 @@ impl XAdder for Arc<Client> {
             Err(e) => Err(StreamAddError::Redis(e)),
         }
     }
+
+    fn xadd_accept_score(&self, command: &AcceptScore) -> Result<(), StreamAddError> {
+        match self.get_connection() {
+            Ok(mut conn) => {
+                redis::cmd("XADD")
+                    .arg(topics::ACCEPT_SCORE_CMD)
+                    .arg("MAXLEN")
+                    .arg("~")
+                    .arg("1000")
+                    .arg("*")
+                    .arg("data")
+                    .arg(bincode::serialize(command)?)
+                    .query::<String>(&mut conn)?;
+                Ok(())
+            }
+            Err(e) => Err(StreamAddError::Redis(e)),
+        }
+    }
+
+    fn xadd_resign(&self, command: &Resign) -> Result<(), StreamAddError> {
+        match self.get_connection() {
+            Ok(mut conn) => {
+                redis::cmd("XADD")
+                    .arg(topics::RESIGN_CMD)
+                    .arg("MAXLEN")
+                    .arg("~")
+                    .arg("1000")
+                    .arg("*")
+                    .arg("data")
+                    .arg(bincode::serialize(command)?)
+                    .query::<String>(&mut conn)?;
+
+                info!(
+                    "🏳️ {} {}",
+                    &command.game_id.0.to_string()[0..8],
+                    command.player.to_string()
+                );
+                Ok(())
+            }
+            Err(e) => Err(StreamAddError::Redis(e)),
+        }
+    }
 }
 
 impl From<RedisError> for StreamAddError {
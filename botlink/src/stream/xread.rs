@@ -39,8 +39,14 @@ impl XReader for Arc<Client> {
                     .block(BLOCK_MS)
                     .group(super::GROUP_NAME, CONSUMER_NAME);
                 let ser = conn.xread_options(
-                    &[topics::ATTACH_BOT_CMD, topics::GAME_STATES_CHANGELOG],
-                    &[">", ">"],
+                    &[
+                        topics::ATTACH_BOT_CMD,
+                        topics::GAME_STATES_CHANGELOG,
+                        topics::GAME_OVER_EV,
+                        topics::DEAD_STONES_MARKED_EV,
+                        topics::QUIT_GAME_CMD,
+                    ],
+                    &[">", ">", ">", ">", ">"],
                     opts,
                 )?;
 
@@ -74,13 +80,23 @@ fn deser(xrr: StreamReadReply) -> Result<HashMap<XReadEntryId, StreamInput>, Str
                 let maybe_data: Option<Vec<u8>> = e.get("data");
                 if let Some(data) = maybe_data {
                     let sd: Option<StreamInput> = if key == topics::GAME_STATES_CHANGELOG {
-                        bincode::deserialize(&data)
+                        move_model::GameState::from(&data)
                             .map(|gs| StreamInput::GS(gs))
                             .ok()
                     } else if key == topics::ATTACH_BOT_CMD {
                         bincode::deserialize(&data)
                             .map(|ab| StreamInput::AB(ab))
                             .ok()
+                    } else if key == topics::GAME_OVER_EV {
+                        bincode::deserialize(&data)
+                            .map(|go| StreamInput::GO(go))
+                            .ok()
+                    } else if key == topics::DEAD_STONES_MARKED_EV {
+                        bincode::deserialize(&data)
+                            .map(|dsm| StreamInput::DSM(dsm))
+                            .ok()
+                    } else if key == topics::QUIT_GAME_CMD {
+                        bincode::deserialize(&data).map(StreamInput::QG).ok()
                     } else {
                         error!("Unknown key {}", key);
                         return Err(StreamReadError::Deser);
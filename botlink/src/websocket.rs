@@ -1,5 +1,6 @@
 use crate::env;
-use bot_model::api::{ComputeMove, MoveComputed};
+use crate::workers::Workers;
+use bot_model::api::{BotCommand, MoveComputed};
 
 use bincode::{deserialize, serialize};
 use crossbeam_channel::{Receiver, Sender};
@@ -9,7 +10,7 @@ use log::{error, info, warn};
 use std::time::Duration;
 use tokio;
 use tokio::net::{TcpListener, TcpStream};
-use tokio_tungstenite::{accept_hdr_async, tungstenite::Error};
+use tokio_tungstenite::{accept_hdr_async, tungstenite::Error, WebSocketStream};
 use tungstenite::handshake::server::{Request, Response};
 use tungstenite::http;
 use tungstenite::{Message, Result};
@@ -67,6 +68,19 @@ async fn handle_connection(stream: TcpStream, opts: &WSOpts) -> Result<()> {
         .await
         .expect("failed to accept");
 
+    let (worker_id, bot_command_out) = opts.workers.connect();
+    let result = relay(ws_stream, &bot_command_out, opts).await;
+    opts.workers.disconnect(worker_id);
+    result
+}
+
+/// Passes this worker the commands meant for it, and
+/// the moves it computes back to botlink
+async fn relay(
+    ws_stream: WebSocketStream<TcpStream>,
+    bot_command_out: &Receiver<BotCommand>,
+    opts: &WSOpts,
+) -> Result<()> {
     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
     let mut interval = tokio::time::interval(Duration::from_millis(WRITE_TICK_MS));
     let mut msg_fut = ws_receiver.next();
@@ -78,13 +92,14 @@ async fn handle_connection(stream: TcpStream, opts: &WSOpts) -> Result<()> {
                     Some(msg) => {
                         let msg = msg?;
                         match msg {
-                            Message::Binary(data) => {
-                                let move_computed: MoveComputed =
-                                    deserialize(&data).expect("bincode deser");
-                                if let Err(e) = opts.move_computed_in.send(move_computed) {
-                                    error!("mc send err {:?}", e)
+                            Message::Binary(data) => match deserialize::<MoveComputed>(&data) {
+                                Ok(move_computed) => {
+                                    if let Err(e) = opts.move_computed_in.send(move_computed) {
+                                        error!("mc send err {:?}", e)
+                                    }
                                 }
-                            }
+                                Err(e) => error!("Ignoring bad frame from worker: {:?}", e),
+                            },
                             Message::Text(_) => warn!("Unexpected text data"),
                             Message::Close(_) => break,
                             Message::Ping(_) => (),
@@ -97,9 +112,9 @@ async fn handle_connection(stream: TcpStream, opts: &WSOpts) -> Result<()> {
                 };
             }
             Either::Right((_, msg_fut_continue)) => {
-                while let Ok(cm) = opts.compute_move_out.try_recv() {
+                while let Ok(command) = bot_command_out.try_recv() {
                     ws_sender
-                        .send(Message::Binary(serialize(&cm).expect("bincode ser")))
+                        .send(Message::Binary(serialize(&command).expect("bincode ser")))
                         .await?;
                 }
 
@@ -114,13 +129,13 @@ async fn handle_connection(stream: TcpStream, opts: &WSOpts) -> Result<()> {
 
 #[derive(Clone)]
 pub struct WSOpts {
-    pub compute_move_out: Receiver<ComputeMove>,
+    pub workers: Workers,
     pub move_computed_in: Sender<MoveComputed>,
 }
 impl WSOpts {
     pub fn from(c: &crate::registry::Components) -> Self {
         WSOpts {
-            compute_move_out: c.compute_move_out.clone(),
+            workers: c.workers.clone(),
             move_computed_in: c.move_computed_in.clone(),
         }
     }
@@ -0,0 +1,286 @@
+use crate::outstanding::Outstanding;
+use bot_model::api::{BotCommand, CancelCompute, ComputeMove};
+use core_model::GameId;
+use crossbeam_channel::{select, unbounded, Receiver, Sender};
+use log::{error, info, warn};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+pub type WorkerId = u64;
+
+/// The tinybrain instances connected over websocket, and
+/// the computations each of them owes us.  Every request
+/// goes to a single worker.  When a worker drops, whatever
+/// it was thinking about is handed to another one, or kept
+/// until one connects.
+#[derive(Clone)]
+pub struct Workers(Arc<Mutex<Registry>>);
+
+struct Registry {
+    next_id: WorkerId,
+    workers: HashMap<WorkerId, Sender<BotCommand>>,
+    /// Which worker each request was sent to.  Whether it
+    /// still wants an answer is up to `outstanding`.
+    assigned: HashMap<GameId, (WorkerId, ComputeMove)>,
+    queued: VecDeque<ComputeMove>,
+    outstanding: Outstanding,
+}
+
+/// Hands each bot command to the worker which should see it
+pub fn dispatch_loop(bot_command_out: Receiver<BotCommand>, workers: Workers) {
+    loop {
+        select! { recv(bot_command_out) -> msg =>
+            match msg {
+                Ok(command) => workers.dispatch(command),
+                Err(e) => error!("dispatch recv: {}", e)
+            }
+        }
+    }
+}
+
+impl Workers {
+    pub fn new(outstanding: Outstanding) -> Self {
+        Workers(Arc::new(Mutex::new(Registry {
+            next_id: 0,
+            workers: HashMap::new(),
+            assigned: HashMap::new(),
+            queued: VecDeque::new(),
+            outstanding,
+        })))
+    }
+
+    /// Registers a worker, and hands it any requests which
+    /// were waiting for someone to compute them
+    pub fn connect(&self) -> (WorkerId, Receiver<BotCommand>) {
+        let mut registry = self.0.lock().expect("workers lock");
+        let (command_in, command_out) = unbounded();
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.workers.insert(id, command_in);
+        info!("Worker {} connected ({} total)", id, registry.workers.len());
+        let queued: Vec<ComputeMove> = registry.queued.drain(..).collect();
+        registry.assign(queued);
+        (id, command_out)
+    }
+
+    /// Forgets a worker and requeues the computations it
+    /// never answered
+    pub fn disconnect(&self, id: WorkerId) {
+        let mut registry = self.0.lock().expect("workers lock");
+        let orphaned = registry.drop_worker(id);
+        registry.assign(orphaned)
+    }
+
+    pub fn dispatch(&self, command: BotCommand) {
+        let mut registry = self.0.lock().expect("workers lock");
+        match command {
+            BotCommand::Compute(compute_move) => registry.assign(vec![compute_move]),
+            BotCommand::Cancel(cancel) => registry.cancel(cancel),
+        }
+    }
+}
+
+impl Registry {
+    /// Sends each request to the worker with the least to
+    /// do.  A worker which can't be reached is dropped, and
+    /// its requests are sent elsewhere along with the rest.
+    fn assign(&mut self, mut compute_moves: Vec<ComputeMove>) {
+        self.forget_answered();
+        compute_moves.retain(|cm| self.outstanding.wants(&cm.game_id, &cm.req_id));
+        compute_moves.reverse();
+        while let Some(compute_move) = compute_moves.pop() {
+            let assigned = &self.assigned;
+            let least_busy = self
+                .workers
+                .iter()
+                .min_by_key(|(id, _)| (assigned.values().filter(|(w, _)| w == *id).count(), **id));
+            match least_busy {
+                None => {
+                    self.queued
+                        .retain(|queued| queued.game_id != compute_move.game_id);
+                    self.queued.push_back(compute_move)
+                }
+                Some((id, command_in)) => {
+                    let id = *id;
+                    if let Err(e) = command_in.send(BotCommand::Compute(compute_move.clone())) {
+                        error!("could not send to worker {}: {:?}", id, e);
+                        compute_moves.push(compute_move);
+                        compute_moves.extend(self.drop_worker(id))
+                    } else {
+                        self.assigned
+                            .insert(compute_move.game_id.clone(), (id, compute_move));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes a worker, and returns the requests it
+    /// still owed us
+    fn drop_worker(&mut self, id: WorkerId) -> Vec<ComputeMove> {
+        self.workers.remove(&id);
+        info!("Worker {} dropped ({} left)", id, self.workers.len());
+        let orphaned: Vec<GameId> = self
+            .assigned
+            .iter()
+            .filter(|(_, (w, _))| *w == id)
+            .map(|(game_id, _)| game_id.clone())
+            .collect();
+        orphaned
+            .iter()
+            .filter_map(|game_id| self.assigned.remove(game_id))
+            .map(|(_, compute_move)| {
+                warn!("Requeueing {:?} from worker {}", compute_move.game_id, id);
+                compute_move
+            })
+            .collect()
+    }
+
+    /// Requests which were answered, or which nobody
+    /// wants any more, no longer count against a worker
+    fn forget_answered(&mut self) {
+        let outstanding = &self.outstanding;
+        self.assigned
+            .retain(|game_id, (_, cm)| outstanding.wants(game_id, &cm.req_id));
+    }
+
+    /// Only the worker computing the move needs to hear
+    /// that it has been cancelled.  If the request was
+    /// already forgotten, every worker hears it, and those
+    /// which never saw the request ignore it.
+    fn cancel(&mut self, cancel: CancelCompute) {
+        self.queued.retain(|queued| queued.req_id != cancel.req_id);
+        let owners: Vec<WorkerId> = match self.assigned.get(&cancel.game_id) {
+            Some((id, compute_move)) if compute_move.req_id == cancel.req_id => {
+                let id = *id;
+                self.assigned.remove(&cancel.game_id);
+                vec![id]
+            }
+            _ => self.workers.keys().copied().collect(),
+        };
+        for owner in owners {
+            if let Some(command_in) = self.workers.get(&owner) {
+                if let Err(e) = command_in.send(BotCommand::Cancel(cancel.clone())) {
+                    error!("could not send to worker {}: {:?}", owner, e)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_model::{BoardSize, GameState, Player, Rules};
+
+    fn compute(outstanding: &Outstanding, game_id: &GameId) -> ComputeMove {
+        ComputeMove {
+            game_id: game_id.clone(),
+            req_id: outstanding.start(game_id, 1, Player::BLACK).unwrap(),
+            game_state: GameState::new(game_id.clone(), BoardSize::square(9), Rules::default(), 0),
+            max_visits: None,
+        }
+    }
+
+    fn game_of(command: BotCommand) -> GameId {
+        match command {
+            BotCommand::Compute(cm) => cm.game_id,
+            BotCommand::Cancel(c) => c.game_id,
+        }
+    }
+
+    #[test]
+    fn balances_and_requeues() {
+        let outstanding = Outstanding::default();
+        let workers = Workers::new(outstanding.clone());
+        let (g1, g2, g3) = (GameId::new(), GameId::new(), GameId::new());
+
+        // nobody is connected yet
+        let c1 = compute(&outstanding, &g1);
+        workers.dispatch(BotCommand::Compute(c1.clone()));
+        let (w1, out1) = workers.connect();
+        assert_eq!(game_of(out1.try_recv().unwrap()), g1);
+
+        let (_, out2) = workers.connect();
+        workers.dispatch(BotCommand::Compute(compute(&outstanding, &g2)));
+        assert_eq!(game_of(out2.try_recv().unwrap()), g2);
+        assert!(out1.try_recv().is_err());
+
+        // once its answer is in, the first worker is free again
+        assert!(outstanding.finish(&g1, &c1.req_id));
+        workers.dispatch(BotCommand::Compute(compute(&outstanding, &g3)));
+        assert_eq!(game_of(out1.try_recv().unwrap()), g3);
+
+        workers.disconnect(w1);
+        assert_eq!(game_of(out2.try_recv().unwrap()), g3);
+
+        let cancel = outstanding.cancel(&g2).unwrap();
+        workers.dispatch(BotCommand::Cancel(cancel));
+        match out2.try_recv().unwrap() {
+            BotCommand::Cancel(c) => assert_eq!(c.game_id, g2),
+            _ => panic!("expected cancel"),
+        }
+        assert!(out2.try_recv().is_err())
+    }
+
+    #[test]
+    fn cancels_forgotten_requests_everywhere() {
+        let outstanding = Outstanding::default();
+        let workers = Workers::new(outstanding.clone());
+        let g1 = GameId::new();
+        let (_, out1) = workers.connect();
+        let (_, out2) = workers.connect();
+        workers.dispatch(BotCommand::Compute(compute(&outstanding, &g1)));
+        assert_eq!(game_of(out1.try_recv().unwrap()), g1);
+
+        // another game's request arrives between the
+        // cancellation and its dispatch
+        let cancel = outstanding.cancel(&g1).unwrap();
+        workers.dispatch(BotCommand::Compute(compute(&outstanding, &GameId::new())));
+        assert!(out1.try_recv().is_ok() || out2.try_recv().is_ok());
+        workers.dispatch(BotCommand::Cancel(cancel));
+        assert!(matches!(out1.try_recv(), Ok(BotCommand::Cancel(_))));
+        assert!(matches!(out2.try_recv(), Ok(BotCommand::Cancel(_))))
+    }
+
+    #[test]
+    fn reassigns_when_a_worker_cannot_be_reached() {
+        let outstanding = Outstanding::default();
+        let workers = Workers::new(outstanding.clone());
+        let (g1, g2) = (GameId::new(), GameId::new());
+
+        let (_, out1) = workers.connect();
+        workers.dispatch(BotCommand::Compute(compute(&outstanding, &g1)));
+        assert_eq!(game_of(out1.try_recv().unwrap()), g1);
+
+        // the first worker goes away before it is disconnected
+        let (_, out2) = workers.connect();
+        drop(out1);
+        workers.dispatch(BotCommand::Compute(compute(&outstanding, &g2)));
+        workers.dispatch(BotCommand::Compute(compute(&outstanding, &GameId::new())));
+
+        let mut seen = vec![];
+        while let Ok(command) = out2.try_recv() {
+            seen.push(game_of(command))
+        }
+        assert_eq!(seen.len(), 3);
+        assert!(seen.contains(&g1) && seen.contains(&g2))
+    }
+
+    #[test]
+    fn requeues_nothing_that_was_answered() {
+        let outstanding = Outstanding::default();
+        let workers = Workers::new(outstanding.clone());
+        let g1 = GameId::new();
+
+        let (w1, out1) = workers.connect();
+        let c1 = compute(&outstanding, &g1);
+        workers.dispatch(BotCommand::Compute(c1.clone()));
+        assert_eq!(game_of(out1.try_recv().unwrap()), g1);
+        assert!(outstanding.finish(&g1, &c1.req_id));
+
+        workers.disconnect(w1);
+        let (_, out2) = workers.connect();
+        assert!(out2.try_recv().is_err())
+    }
+}
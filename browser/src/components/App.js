@@ -19,10 +19,12 @@ import IdleStatusModal from "./bugout/IdleStatusModal";
 import InvalidLinkModal from "./bugout/InvalidLinkModal";
 import MultiplayerColorPrefModal from "./bugout/MultiplayerColorPrefModal";
 import OpponentPassedModal from "./bugout/OpponentPassedModal";
+import MoveRejectedModal from "./bugout/MoveRejectedModal";
 import OpponentQuitModal from "./bugout/OpponentQuitModal";
 import PlayBotColorSelectionModal from "./bugout/PlayBotColorSelectionModal";
 import ReconnectModal from "./bugout/ReconnectModal";
 import WaitForBotModal from "./bugout/WaitForBotModal";
+import UndoRequestedModal from "./bugout/UndoRequestedModal";
 import WaitForUndoModal from "./bugout/WaitForUndoModal";
 import WaitForOpponentModal from "./bugout/WaitForOpponentModal";
 import WaitForYourColorModal from "./bugout/WaitForYourColorModal";
@@ -274,10 +276,10 @@ class App extends Component {
     });
   }
 
-  setMode(mode) {
+  setMode(mode, { guessDeadStones = true } = {}) {
     let stateChange = { mode };
 
-    if (["scoring", "estimator"].includes(mode)) {
+    if (guessDeadStones && ["scoring", "estimator"].includes(mode)) {
       // Guess dead stones
 
       let { gameIndex, gameTrees, treePosition } = this.state;
@@ -602,6 +604,14 @@ class App extends Component {
     } else if (["scoring", "estimator"].includes(this.state.mode)) {
       if (button !== 0 || board.get(vertex) === 0) return;
 
+      if (this.state.mode === "scoring" && this.state.multiplayer) {
+        // the backend decides which stones are dead,
+        // and tells both players
+        this.events.emit("bugout-toggle-dead-stone", { vertex });
+        this.events.emit("vertexClick");
+        return;
+      }
+
       let { mode, deadStones } = this.state;
       let dead = deadStones.some((v) => helper.vertexEquals(v, vertex));
       let stones =
@@ -779,24 +789,67 @@ class App extends Component {
     this.events.emit("undo", { player: reversePlayer });
   }
 
-  onMoveUndone() {
+  undoToTurn(turn) {
+    let { currentPlayer } = this.inferredState;
+    let player = currentPlayer > 0 ? Player.BLACK : Player.WHITE;
+    this.events.emit("undo-to-turn", { player, turn });
+  }
+
+  onMoveUndone({ turn } = {}) {
     let { gameTrees, gameIndex, treePosition } = this.state;
     let tree = gameTrees[gameIndex];
 
-    // Try going back two moves
-    let thisMove = tree.get(treePosition);
-    let oneMoveAgo = tree.get(thisMove.parentId);
+    // The moves from here back to the root, newest first
+    let line = [];
+    for (
+      let node = tree.get(treePosition);
+      node.parentId != null;
+      node = tree.get(node.parentId)
+    ) {
+      line.push(node);
+    }
 
-    // Update data
-    let nextTreePosition = oneMoveAgo.parentId;
+    // Without a turn, go back two moves.  Otherwise keep
+    // the moves played before that turn.
+    let steps = turn == null ? 2 : line.length - (turn - 1);
+    steps = Math.min(steps, line.length);
+    if (steps < 1) return;
+
+    // Removing the oldest of them takes the rest along
+    let oldest = line[steps - 1];
+    let nextTreePosition = oldest.parentId;
     let newTree = tree.mutate((draft) => {
-      draft.removeNode(treePosition); // this move
-      draft.removeNode(thisMove.parentId); // one move ago
+      draft.removeNode(oldest.id);
     });
 
     this.setCurrentTreePosition(newTree, nextTreePosition);
   }
 
+  onSyncSnapshot({ moves }) {
+    let { gameTrees, gameIndex } = this.state;
+    let tree = gameTrees[gameIndex];
+
+    // Our board drifted from the backend's, so replay
+    // its moves in place of our own
+    let nextTreePosition = tree.root.id;
+    let newTree = tree.mutate((draft) => {
+      for (let child of tree.root.children) {
+        draft.removeNode(child.id);
+      }
+      for (let { player, coord } of moves) {
+        let color = player === Player.BLACK ? "B" : "W";
+        let vertex = coord ? [coord.x, coord.y] : [-1, -1];
+        nextTreePosition = draft.appendNode(nextTreePosition, {
+          [color]: [sgf.stringifyVertex(vertex)],
+        });
+      }
+    });
+
+    this.setCurrentTreePosition(newTree, nextTreePosition, {
+      clearCache: true,
+    });
+  }
+
   // Navigation
 
   setCurrentTreePosition(tree, id, { clearCache = false } = {}) {
@@ -1405,9 +1458,11 @@ class App extends Component {
       h(IdleStatusModal, { data: state.multiplayer }),
       h(InvalidLinkModal),
       h(OpponentPassedModal),
+      h(MoveRejectedModal),
       h(OpponentQuitModal),
       h(WaitForBotModal),
       h(WaitForUndoModal),
+      h(UndoRequestedModal),
       // ↑ BUGOUT ↑
 
       h(MainView, state),
@@ -15,6 +15,7 @@ class Bar extends Component {
     sabaki.events.on("bugout-opponent-quit", onGameOver.bind(this));
     sabaki.events.on("resign", onGameOver.bind(this));
     sabaki.events.on("bugout-consecutive-pass", onGameOver.bind(this));
+    sabaki.events.on("bugout-game-over", onGameOver.bind(this));
 
     this.componentWillReceiveProps(props);
     this.onCloseButtonClick = () => sabaki.setMode("play");
@@ -59,6 +59,14 @@ class PlayBar extends Component {
       sabaki.undoMove();
     };
 
+    this.handleUndoToTurnClick = () => {
+      let answer = prompt(t("Take back every move from turn:"));
+      let turn = parseInt(answer, 10);
+      if (turn > 0) {
+        sabaki.undoToTurn(turn);
+      }
+    };
+
     this.handleMenuClick = () => {
       let { left, top } = this.menuButtonElement.getBoundingClientRect();
       helper.popupMenu(
@@ -90,6 +98,11 @@ class PlayBar extends Component {
             click: () => toggleSetting("view.show_move_colorization"),
           },
           { type: "separator" },
+          {
+            label: t("&Undo to Turn…"),
+            click: this.handleUndoToTurnClick,
+          },
+          { type: "separator" },
           {
             label: t("Es&timate"),
             click: () => sabaki.setMode("estimator"),
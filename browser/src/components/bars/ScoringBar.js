@@ -8,6 +8,8 @@ class ScoringBar extends Component {
 
     this.handleDetailsClick = () => sabaki.openDrawer("score");
     this.handleNewGameClick = () => location.reload();
+    this.handleAcceptClick = () => sabaki.events.emit("bugout-accept-score");
+    this.handleResumeClick = () => sabaki.events.emit("bugout-resume-play");
   }
 
   render({ type, method, areaMap, scoreBoard, komi, handicap }) {
@@ -36,6 +38,10 @@ class ScoringBar extends Component {
       ),
       " ",
       type === "scoring" ? t("Select dead stones.") : t("Toggle group status."),
+      type === "scoring" &&
+        h("button", { onClick: this.handleAcceptClick }, t("Accept")),
+      type === "scoring" &&
+        h("button", { onClick: this.handleResumeClick }, t("Resume")),
       h(
         "button",
         {
@@ -0,0 +1,60 @@
+const { h, Component } = require("preact");
+
+// 🦹🏻‍ Bundle Bloat Protector
+import Dialog from "preact-material-components/Dialog";
+
+const REASON_TEXT = {
+  WrongPlayer: "It isn't your turn.",
+  OutOfBounds: "That point is off the board.",
+  Occupied: "There's already a stone there.",
+  Ko: "That move would retake a ko.  Play elsewhere first.",
+  Suicide: "That move would leave your stones without liberties.",
+  GameOver: "The game is over.",
+  UnknownGame: "The game could not be found.",
+};
+
+class MoveRejectedModal extends Component {
+  constructor() {
+    super();
+    this.state = { showDialog: false, reason: undefined };
+
+    // From GTP.js
+    sabaki.events.on("bugout-move-rejected", ({ reason }) => {
+      this.setState({ showDialog: true, reason });
+    });
+  }
+
+  render({ id = "move-rejected-modal" }) {
+    let { showDialog, reason } = this.state;
+
+    let empty = h("div", { id });
+
+    return showDialog
+      ? h(
+          Dialog,
+          {
+            id,
+            isOpen: true,
+          },
+          h(Dialog.Header, null, "Illegal Move"),
+          h(Dialog.Body, null, REASON_TEXT[reason] || "Move not allowed."),
+          h(
+            Dialog.Footer,
+            null,
+            h(
+              Dialog.FooterButton,
+              {
+                accept: true,
+                onClick: () => {
+                  this.setState({ showDialog: false });
+                },
+              },
+              "OK"
+            )
+          )
+        )
+      : empty;
+  }
+}
+
+export default MoveRejectedModal;
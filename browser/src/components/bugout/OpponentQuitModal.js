@@ -6,16 +6,19 @@ import Dialog from "preact-material-components/Dialog";
 class OpponentQuitModal extends Component {
   constructor() {
     super();
-    this.state = { showDialog: false, scoringMode: false };
+    this.state = { showDialog: false, scoringMode: false, resigned: false };
 
     // From GTP.js
     sabaki.events.on("bugout-opponent-quit", () => {
-      this.setState({ showDialog: true });
+      this.setState({ showDialog: true, resigned: false });
+    });
+    sabaki.events.on("bugout-opponent-resigned", () => {
+      this.setState({ showDialog: true, resigned: true });
     });
   }
 
   render({ id = "opponent-quit-modal" }) {
-    let { showDialog } = this.state;
+    let { showDialog, resigned } = this.state;
 
     let empty = h("div", { id });
 
@@ -27,7 +30,11 @@ class OpponentQuitModal extends Component {
             isOpen: true,
           },
           h(Dialog.Header, null, "Game Over"),
-          h(Dialog.Body, null, "The opponent quit."),
+          h(
+            Dialog.Body,
+            null,
+            resigned ? "The opponent resigned." : "The opponent quit."
+          ),
           h(
             Dialog.Footer,
             null,
@@ -0,0 +1,69 @@
+const { h, Component } = require("preact");
+
+// 🦹🏻‍ Bundle Bloat Protector
+import Dialog from "preact-material-components/Dialog";
+
+class UndoRequestedModal extends Component {
+  constructor() {
+    super();
+    this.state = { showDialog: false, turn: null };
+
+    // From GTP.js
+    sabaki.events.on("bugout-undo-requested", ({ turn }) => {
+      this.setState({ showDialog: true, turn });
+    });
+    // Accepted, declined, or timed out
+    sabaki.events.on("bugout-undo-settled", () => {
+      this.setState({ showDialog: false });
+    });
+  }
+
+  answer(event) {
+    this.setState({ showDialog: false });
+    sabaki.events.emit(event);
+  }
+
+  render({ id = "undo-requested-modal" }) {
+    let { showDialog, turn } = this.state;
+
+    let empty = h("div", { id });
+
+    return showDialog
+      ? h(
+          Dialog,
+          {
+            id,
+            isOpen: true,
+          },
+          h(Dialog.Header, null, "Undo Move"),
+          h(
+            Dialog.Body,
+            null,
+            `Your opponent would like to take back the moves since turn ${turn}.`
+          ),
+          h(
+            Dialog.Footer,
+            null,
+            h(
+              Dialog.FooterButton,
+              {
+                cancel: true,
+                onClick: () => this.answer("bugout-decline-undo"),
+              },
+              "Decline"
+            ),
+            h(
+              Dialog.FooterButton,
+              {
+                accept: true,
+                onClick: () => this.answer("bugout-accept-undo"),
+              },
+              "Allow"
+            )
+          )
+        )
+      : empty;
+  }
+}
+
+export default UndoRequestedModal;
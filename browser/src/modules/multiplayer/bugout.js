@@ -98,11 +98,17 @@ const registerBoardSizeEvents = (app) => {
 };
 
 const registerUndoEvent = (app) => {
-  app.events.on("bugout-move-undone", () => {
-    app.onMoveUndone();
+  app.events.on("bugout-move-undone", (moveUndone) => {
+    app.onMoveUndone(moveUndone);
   });
 };
 
+const registerSyncEvents = (app) => {
+  app.events.on("bugout-sync-snapshot", (syncReply) =>
+    app.onSyncSnapshot(syncReply)
+  );
+};
+
 const registerReconnectEvents = (app) => {
   app.events.on("websocket-closed", () =>
     app.setState({
@@ -250,6 +256,7 @@ const load = () => {
             registerReconnectEvents(app);
             registerBoardSizeEvents(app);
             registerUndoEvent(app);
+            registerSyncEvents(app);
           }
         }, placeholderColor);
       }
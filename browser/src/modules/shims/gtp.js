@@ -2,6 +2,7 @@
 
 const EventEmitter = require("events");
 const Board = require("../board");
+const gametree = require("../gametree");
 const RobustWebSocket = require("robust-websocket");
 const uuidv4 = require("uuid/v4");
 
@@ -128,6 +129,10 @@ const opponentMoved = (msg, opponent) =>
 
 const opponentQuit = (msg) => msg.type === "OpponentQuit";
 
+const gameOver = (msg) => msg.type === "GameOver";
+
+const toVertices = (coords) => coords.map((c) => [c.x, c.y]);
+
 class WebSocketController extends EventEmitter {
   constructor(webSocketAddress, spawnOptions) {
     super();
@@ -160,13 +165,60 @@ class WebSocketController extends EventEmitter {
 
     this.gameId = null;
     sabaki.events.on("resign", () => {
-      this.gameId = null;
+      if (this.gameId && !this.gameOver) {
+        // the judge announces the result with GameOver,
+        // so keep listening for this game
+        this.gatewayConn.resign();
+      } else {
+        this.gameId = null;
+      }
     });
 
     sabaki.events.on("undo", ({ player }) => {
       this.gatewayConn.undoMove(player);
     });
 
+    sabaki.events.on("undo-to-turn", ({ player, turn }) => {
+      this.gatewayConn.undoToTurn(player, turn);
+    });
+
+    // A human opponent asked us to agree to their undo
+    sabaki.events.on("bugout-accept-undo", () => {
+      if (this.yourColor) {
+        this.gatewayConn.answerUndo("AcceptUndo");
+      }
+    });
+    sabaki.events.on("bugout-decline-undo", () => {
+      if (this.yourColor) {
+        this.gatewayConn.answerUndo("DeclineUndo");
+      }
+    });
+
+    sabaki.events.on("your-color", ({ yourColor }) => {
+      this.yourColor = yourColor;
+    });
+
+    // Once the game is over, both players agree on
+    // the dead stones before the score is final
+    sabaki.events.on("bugout-toggle-dead-stone", ({ vertex }) => {
+      if (this.gameOver && this.yourColor) {
+        this.gatewayConn.toggleDeadStone({
+          x: vertex[0],
+          y: vertex[1],
+        });
+      }
+    });
+    sabaki.events.on("bugout-accept-score", () => {
+      if (this.gameOver && this.yourColor) {
+        this.gatewayConn.acceptScore();
+      }
+    });
+    sabaki.events.on("bugout-resume-play", () => {
+      if (this.gameOver && this.yourColor) {
+        this.gatewayConn.resumePlay();
+      }
+    });
+
     this.clientId = ClientId.fromStorage();
 
     this.beeping = true;
@@ -247,6 +299,56 @@ class WebSocketController extends EventEmitter {
 
     sabaki.events.on("bugout-turn", ({ turn }) => (this.turn = turn));
 
+    // The backend announces the end of the game no matter
+    // which reply we happen to be waiting on
+    this.webSocket.addEventListener("message", (event) => {
+      try {
+        let msg = JSON.parse(event.data);
+        if (msg.gameId !== this.gameId) {
+          return;
+        }
+        if (gameOver(msg)) {
+          this.gameOver = true;
+          sabaki.events.emit("bugout-game-over", msg);
+          if (msg.result.reason === "Resignation") {
+            // nothing left to count
+            if (msg.result.winner === this.yourColor) {
+              sabaki.events.emit("bugout-opponent-resigned");
+            }
+          } else if (sabaki.state.mode !== "scoring") {
+            // the players mark dead stones together,
+            // so don't guess them locally
+            sabaki.setMode("scoring", { guessDeadStones: false });
+          }
+        } else if (msg.type === "DeadStonesMarked") {
+          sabaki.setState({ deadStones: toVertices(msg.dead) });
+          sabaki.events.emit("bugout-dead-stones-marked", msg);
+        } else if (msg.type === "ScoreFinalized") {
+          sabaki.setState({ deadStones: toVertices(msg.dead) });
+          sabaki.events.emit("bugout-score-finalized", msg);
+        } else if (msg.type === "PlayResumed") {
+          this.gameOver = false;
+          sabaki.setState({ deadStones: [] });
+          sabaki.setMode("play");
+          sabaki.events.emit("bugout-play-resumed", msg);
+        } else if (this.yourColor && msg.player !== this.yourColor) {
+          // the opponent's undo, which we were asked to allow
+          if (msg.type === "UndoRequested") {
+            sabaki.events.emit("bugout-undo-requested", msg);
+          } else if (msg.type === "MoveUndone") {
+            sabaki.events.emit("bugout-undo-settled");
+            sabaki.events.emit("bugout-move-undone", msg);
+          } else if (msg.type === "UndoRejected") {
+            sabaki.events.emit("bugout-undo-settled");
+          }
+        }
+      } catch (err) {
+        console.log(
+          `Error processing websocket message (G): ${JSON.stringify(err)}`
+        );
+      }
+    });
+
     this.webSocket.addEventListener("close", () => {
       this.removeMessageListener();
       console.log("WebSocket closed.");
@@ -363,6 +465,8 @@ class WebSocketController extends EventEmitter {
                 type: "ProvideHistory",
                 gameId: this.gameId,
                 reqId: uuidv4(),
+                // only the opponent's latest move is needed
+                page: { sinceTurn: Math.max(this.turn - 1, 0) },
               };
 
               this.webSocket.send(JSON.stringify(provideHistoryCommand));
@@ -464,6 +568,12 @@ class WebSocketController extends EventEmitter {
           this.handleOpponentQuit(resolve);
           this.genMoveInProgress = false;
           sabaki.events.emit("gen-move-completed", { done: true });
+        } else if (gameOver(msg)) {
+          // no more moves are coming
+          this.removeMessageListener();
+          resolve({ id: null, error: false });
+          this.genMoveInProgress = false;
+          sabaki.events.emit("gen-move-completed", { done: true });
         }
 
         // discard any other messages until we receive confirmation
@@ -542,6 +652,15 @@ class WebSocketController extends EventEmitter {
             let msg = JSON.parse(event.data);
             if (msg.type === "MoveMade" && msg.replyTo === makeMove.reqId) {
               resolve({ id: null, error: false });
+            } else if (
+              msg.type === "MoveRejected" &&
+              msg.replyTo === makeMove.reqId
+            ) {
+              sabaki.events.emit("bugout-move-rejected", {
+                reason: msg.reason,
+                coord: msg.coord,
+              });
+              resolve({ id: null, error: true });
             }
 
             // discard any other messages until we receive confirmation
@@ -726,8 +845,6 @@ class GatewayConn {
     sabaki.events.on("choose-color-pref", ({ colorPref }) =>
       this.chooseColorPref(colorPref)
     );
-
-    sabaki.events.on("resign", () => this.quitGame());
   }
 
   async reconnect(gameId, resolveMoveMade, board) {
@@ -805,6 +922,8 @@ class GatewayConn {
             // App.js wants to know about this as well
             sabaki.events.emit("bugout-bot-attached", msg);
 
+            resolve(msg);
+          } else if (msg.type === "GameOptionsRejected") {
             resolve(msg);
           }
           // discard any other messages
@@ -884,6 +1003,8 @@ class GatewayConn {
             // turn off dialog
             this.handleWaitForOpponent({ gap: false, hasEvent: false });
             sabaki.events.emit("bugout-game-ready", msg);
+          } else if (msg.type === "GameOptionsRejected") {
+            resolve(msg);
           }
           // discard any other messages
         } catch (err) {
@@ -947,12 +1068,16 @@ class GatewayConn {
   }
 
   async undoMove(player) {
-    return new Promise((resolve, reject) => {
-      let requestPayload = {
-        type: "UndoMove",
-        player,
-      };
+    return this.requestUndo({ type: "UndoMove", player });
+  }
+
+  /** Takes back every move from turn onwards */
+  async undoToTurn(player, turn) {
+    return this.requestUndo({ type: "UndoToTurn", player, turn });
+  }
 
+  async requestUndo(requestPayload) {
+    return new Promise((resolve, reject) => {
       this.updateUndoListener((event) => {
         try {
           let msg = JSON.parse(event.data);
@@ -960,7 +1085,7 @@ class GatewayConn {
           if (msg.type === "MoveUndone") {
             this.removeUndoListener();
             resolve(msg);
-            sabaki.events.emit("bugout-move-undone");
+            sabaki.events.emit("bugout-move-undone", msg);
             sabaki.events.emit("bugout-wait-for-undo", {
               showWait: false,
               showReject: false,
@@ -1024,10 +1149,38 @@ class GatewayConn {
   async quitGame() {
     this.webSocket.send('{"type": "QuitGame"}');
   }
+
+  /** The gateway marks, accepts and resumes as the
+   * color we were given. */
+  toggleDeadStone(coord) {
+    this.webSocket.send(JSON.stringify({ type: "ToggleDeadStone", coord }));
+  }
+
+  acceptScore() {
+    this.webSocket.send('{"type": "AcceptScore"}');
+  }
+
+  resumePlay() {
+    this.webSocket.send('{"type": "ResumePlay"}');
+  }
+
+  /** The gateway resigns as the color we were given */
+  resign() {
+    this.webSocket.send('{"type": "Resign"}');
+  }
+
+  /** type is either AcceptUndo or DeclineUndo.  The
+   * gateway answers as the color we were given. */
+  answerUndo(type) {
+    this.webSocket.send(JSON.stringify({ type }));
+  }
 }
 
 const SYNC_TIMEOUT_MS = 5000;
 const SYNC_DELAY_MS = 7500;
+/** Moves sent with each sync request, so that the
+ * backend can catch up on several moves at once */
+const SYNC_RECENT_MOVES = 10;
 
 class BugoutSync {
   constructor(webSocket) {
@@ -1077,7 +1230,10 @@ class BugoutSync {
   processReply(syncReply) {
     let { playerUp, lastMove, turn } = deriveLocalState();
 
-    if (syncReply.turn === turn && syncReply.playerUp === playerUp) {
+    if (syncReply.snapshot) {
+      console.log("!  SYNC: BOARD MISMATCH");
+      sabaki.events.emit("bugout-sync-snapshot", syncReply);
+    } else if (syncReply.turn === turn && syncReply.playerUp === playerUp) {
       sabaki.events.emit("sync-no-op");
     } else if (
       syncReply.turn - 1 === turn &&
@@ -1106,7 +1262,7 @@ class BugoutSync {
   }
 
   makePayload(reqId) {
-    let { playerUp, lastMove, turn } = deriveLocalState();
+    let { playerUp, lastMove, turn, moves, board } = deriveLocalState();
 
     return {
       type: "ReqSync",
@@ -1114,6 +1270,10 @@ class BugoutSync {
       reqId,
       turn,
       lastMove,
+      recentMoves: moves.slice(-SYNC_RECENT_MOVES),
+      // moves we already have need not be sent back
+      page: { sinceTurn: lastMove ? lastMove.turn : 0 },
+      boardChecksum: boardChecksum(board),
     };
   }
 
@@ -1132,20 +1292,55 @@ class BugoutSync {
 }
 
 const deriveLocalState = () => {
-  let { gameTrees, gameIndex } = sabaki.state;
+  let { gameTrees, gameIndex, treePosition } = sabaki.state;
   let { currentPlayer } = sabaki.inferredState;
 
   let playerUp = interpretPlayerNum(currentPlayer);
   let tree = gameTrees[gameIndex];
-  let lastMove = findLastMove(tree);
+  let moves = findMoves(tree);
+  let lastMove = moves.length === 0 ? null : moves[moves.length - 1];
   let turn = lastMove == undefined ? 1 : lastMove.turn + 1;
+  let board = gametree.getBoard(tree, treePosition);
 
-  return { playerUp, lastMove, turn };
+  return { playerUp, lastMove, turn, moves, board };
+};
+
+const MASK_64 = (BigInt(1) << BigInt(64)) - BigInt(1);
+
+const splitmix64 = (seed) => {
+  let z = (seed + BigInt("0x9E3779B97F4A7C15")) & MASK_64;
+  z = ((z ^ (z >> BigInt(30))) * BigInt("0xBF58476D1CE4E5B9")) & MASK_64;
+  z = ((z ^ (z >> BigInt(27))) * BigInt("0x94D049BB133111EB")) & MASK_64;
+  return z ^ (z >> BigInt(31));
+};
+
+/** Hashes the stones and captures the same way as
+ * move_model's Board::checksum, so that the backend can
+ * tell when our board has drifted from its own.  Sent as
+ * hex, since it does not fit in a javascript number. */
+const boardChecksum = (board) => {
+  let hash = BigInt(0);
+  board.arrangement.forEach((row, y) =>
+    row.forEach((sign, x) => {
+      if (sign !== 0) {
+        let color = BigInt(sign > 0 ? 0 : 1);
+        hash ^= splitmix64(
+          (BigInt(x) << BigInt(32)) | (BigInt(y) << BigInt(1)) | color
+        );
+      }
+    })
+  );
+  // captures[0] holds the stones which black has taken
+  let [black, white] = board.captures;
+  hash ^= splitmix64(
+    (BigInt(1) << BigInt(63)) | (BigInt(black) << BigInt(16)) | BigInt(white)
+  );
+  return hash.toString(16);
 };
 
 const interpretPlayerNum = (n) => (n === 1 ? "BLACK" : "WHITE");
 
-const findLastMove = (tree) => {
+const findMoves = (tree) => {
   var bottom = false;
 
   if (
@@ -1154,13 +1349,13 @@ const findLastMove = (tree) => {
     tree.root.children === undefined ||
     tree.root.children.length === 0
   ) {
-    return null;
+    return [];
   }
 
   // skip the top level game node
   var subtree = tree.root.children[0];
   var turn = 0;
-  var lastMove = null;
+  var moves = [];
   while (!bottom) {
     turn = turn + 1;
 
@@ -1172,12 +1367,12 @@ const findLastMove = (tree) => {
       if (blackTreeCoords) {
         let coord = convertTreeCoord(blackTreeCoords);
         let player = "BLACK";
-        lastMove = { turn, player, coord };
+        moves.push({ turn, player, coord });
         proceed = true;
       } else if (whiteTreeCoords) {
         let coord = convertTreeCoord(whiteTreeCoords);
         let player = "WHITE";
-        lastMove = { turn, player, coord };
+        moves.push({ turn, player, coord });
         proceed = true;
       }
 
@@ -1194,7 +1389,7 @@ const findLastMove = (tree) => {
       bottom = true;
     }
   }
-  return lastMove;
+  return moves;
 };
 
 const convertTreeCoord = (treeCoords) => {
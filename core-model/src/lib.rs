@@ -9,6 +9,11 @@ impl GameId {
         Self(Uuid::new_v4())
     }
 }
+impl Default for GameId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
 pub struct ClientId(pub Uuid);
@@ -18,6 +23,11 @@ impl ClientId {
         Self(Uuid::new_v4())
     }
 }
+impl Default for ClientId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
 pub struct SessionId(pub Uuid);
 
@@ -26,6 +36,11 @@ impl SessionId {
         Self(Uuid::new_v4())
     }
 }
+impl Default for SessionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
 pub struct ReqId(pub Uuid);
 
@@ -34,6 +49,11 @@ impl ReqId {
         Self(Uuid::new_v4())
     }
 }
+impl Default for ReqId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
 pub struct EventId(pub Uuid);
 
@@ -42,3 +62,8 @@ impl EventId {
         Self(Uuid::new_v4())
     }
 }
+impl Default for EventId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,5 @@
 use crate::backend::commands::*;
-use crate::model::{ColorPref, Coord, Move, Player, Visibility};
+use crate::model::{ColorPref, Coord, Move, Page, Player, Visibility};
 
 use color_model as color;
 use lobby_model as lobby;
@@ -58,3 +58,12 @@ impl From<Move> for sync::Move {
         }
     }
 }
+
+impl From<Page> for sync::Page {
+    fn from(p: Page) -> Self {
+        sync::Page {
+            since_turn: p.since_turn,
+            size: p.size,
+        }
+    }
+}
@@ -29,6 +29,9 @@ impl IntoShared<sync::api::ReqSync> for ReqSyncBackendCommand {
             game_id: self.game_id.into_shared(),
             player_up: self.player_up.into(),
             last_move: self.last_move.map(|m| m.into()),
+            recent_moves: self.recent_moves.iter().map(|m| (*m).into()).collect(),
+            page: self.page.into(),
+            board_checksum: self.board_checksum,
             turn: self.turn,
         }
     }
@@ -69,6 +72,9 @@ impl IntoShared<lobby::api::CreateGame> for CreateGameBackendCommand {
             visibility: self.visibility.into(),
             session_id: self.session_id.into_shared(),
             board_size: self.board_size,
+            rules: self.rules,
+            handicap: self.handicap.clone(),
+            rated: self.rated,
         }
     }
 }
@@ -78,6 +84,15 @@ impl IntoShared<sync::api::ProvideHistory> for ProvideHistoryCommand {
         sync::api::ProvideHistory {
             game_id: self.game_id.into_shared(),
             req_id: self.req_id.into_shared(),
+            page: self.page.into(),
+        }
+    }
+}
+
+impl IntoShared<move_model::QuitGame> for QuitGameCommand {
+    fn into_shared(&self) -> move_model::QuitGame {
+        move_model::QuitGame {
+            game_id: self.game_id.into_shared(),
         }
     }
 }
@@ -46,7 +46,10 @@ pub struct CreateGameBackendCommand {
     #[serde(rename = "sessionId")]
     pub session_id: SessionId,
     #[serde(rename = "boardSize")]
-    pub board_size: u16,
+    pub board_size: move_model::BoardSize,
+    pub rules: move_model::Rules,
+    pub handicap: Vec<move_model::Coord>,
+    pub rated: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -89,6 +92,9 @@ pub struct ReqSyncBackendCommand {
     pub player_up: Player,
     pub turn: u32,
     pub last_move: Option<Move>,
+    pub recent_moves: Vec<Move>,
+    pub page: Page,
+    pub board_checksum: Option<u64>,
     pub game_id: GameId,
 }
 
@@ -107,4 +113,11 @@ pub enum BackendCommands {
     AttachBot(bot_model::api::AttachBot),
     ReqSync(ReqSyncBackendCommand),
     UndoMove(undo_model::api::UndoMove),
+    UndoToTurn(undo_model::api::UndoToTurn),
+    AcceptUndo(undo_model::api::AcceptUndo),
+    DeclineUndo(undo_model::api::DeclineUndo),
+    ToggleDeadStone(move_model::ToggleDeadStone),
+    AcceptScore(move_model::AcceptScore),
+    ResumePlay(move_model::ResumePlay),
+    Resign(move_model::Resign),
 }
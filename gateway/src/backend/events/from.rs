@@ -1,5 +1,9 @@
 use crate::backend::events as be;
-use crate::model::{Coord, HistoryProvidedEvent, Move, MoveMadeEvent, Player, Visibility};
+use crate::model::{
+    Captures, Coord, DeadStonesMarkedEvent, EndReason, FinalScore, GameOverEvent, GameResult,
+    HistoryProvidedEvent, Move, MoveMadeEvent, MoveRejectReason, MoveRejectedEvent, Page,
+    PlayResumedEvent, Player, Score, ScoreFinalizedEvent, Snapshot, Stone, Visibility,
+};
 use color_model as color;
 use lobby_model as lobby;
 use move_model as moves;
@@ -17,6 +21,103 @@ impl From<moves::MoveMade> for MoveMadeEvent {
         }
     }
 }
+impl From<moves::MoveRejected> for MoveRejectedEvent {
+    fn from(m: moves::MoveRejected) -> Self {
+        MoveRejectedEvent {
+            game_id: m.game_id.0,
+            reply_to: m.reply_to.0,
+            event_id: m.event_id.0,
+            player: Player::from(m.player),
+            coord: m.coord.map(|c| Coord::from(c)),
+            reason: MoveRejectReason::from(m.reason),
+        }
+    }
+}
+impl From<moves::GameOver> for GameOverEvent {
+    fn from(g: moves::GameOver) -> Self {
+        GameOverEvent {
+            game_id: g.game_id.0,
+            event_id: g.event_id.0,
+            result: GameResult::from(g.result),
+            score: g.score.map(|s| FinalScore::from(s)),
+        }
+    }
+}
+impl From<moves::DeadStonesMarked> for DeadStonesMarkedEvent {
+    fn from(d: moves::DeadStonesMarked) -> Self {
+        DeadStonesMarkedEvent {
+            game_id: d.game_id.0,
+            event_id: d.event_id.0,
+            dead: d.dead.iter().map(|c| Coord::from(c.clone())).collect(),
+            accepted: d.accepted.iter().map(|p| Player::from(*p)).collect(),
+            score: FinalScore::from(d.score),
+        }
+    }
+}
+impl From<moves::ScoreFinalized> for ScoreFinalizedEvent {
+    fn from(s: moves::ScoreFinalized) -> Self {
+        ScoreFinalizedEvent {
+            game_id: s.game_id.0,
+            event_id: s.event_id.0,
+            dead: s.dead.iter().map(|c| Coord::from(c.clone())).collect(),
+            result: GameResult::from(s.result),
+            score: FinalScore::from(s.score),
+        }
+    }
+}
+impl From<moves::PlayResumed> for PlayResumedEvent {
+    fn from(p: moves::PlayResumed) -> Self {
+        PlayResumedEvent {
+            game_id: p.game_id.0,
+            event_id: p.event_id.0,
+            player: Player::from(p.player),
+        }
+    }
+}
+impl From<moves::FinalScore> for FinalScore {
+    fn from(f: moves::FinalScore) -> Self {
+        FinalScore {
+            area: Score::from(f.area),
+            territory: Score::from(f.territory),
+            komi: f.komi,
+        }
+    }
+}
+impl From<moves::GameResult> for GameResult {
+    fn from(r: moves::GameResult) -> Self {
+        GameResult {
+            winner: r.winner.map(|p| Player::from(p)),
+            reason: match r.reason {
+                moves::EndReason::Score => EndReason::Score,
+                moves::EndReason::Resignation => EndReason::Resignation,
+                moves::EndReason::FirstCapture => EndReason::FirstCapture,
+            },
+        }
+    }
+}
+impl From<moves::Score> for Score {
+    fn from(s: moves::Score) -> Self {
+        Score {
+            black: s.black,
+            white: s.white,
+            winner: s.winner.map(|p| Player::from(p)),
+            margin: s.margin,
+        }
+    }
+}
+impl From<moves::RejectReason> for MoveRejectReason {
+    fn from(r: moves::RejectReason) -> Self {
+        match r {
+            moves::RejectReason::WrongPlayer => MoveRejectReason::WrongPlayer,
+            moves::RejectReason::OutOfBounds => MoveRejectReason::OutOfBounds,
+            moves::RejectReason::Occupied => MoveRejectReason::Occupied,
+            moves::RejectReason::Ko => MoveRejectReason::Ko,
+            moves::RejectReason::Suicide => MoveRejectReason::Suicide,
+            moves::RejectReason::GameOver => MoveRejectReason::GameOver,
+            moves::RejectReason::UnknownGame => MoveRejectReason::UnknownGame,
+        }
+    }
+}
 impl From<sync::api::SyncReply> for be::SyncReplyBackendEvent {
     fn from(s: sync::api::SyncReply) -> Self {
         be::SyncReplyBackendEvent {
@@ -26,6 +127,28 @@ impl From<sync::api::SyncReply> for be::SyncReplyBackendEvent {
             turn: s.turn,
             player_up: Player::from(s.player_up),
             moves: s.moves.iter().map(|m| Move::from(m.clone())).collect(),
+            next_page: s.next_page.map(Page::from),
+            snapshot: s.snapshot.map(Snapshot::from),
+        }
+    }
+}
+impl From<sync::Snapshot> for Snapshot {
+    fn from(s: sync::Snapshot) -> Self {
+        Snapshot {
+            width: s.board.size.width,
+            height: s.board.size.height,
+            stones: s
+                .board
+                .pieces()
+                .map(|(c, p)| Stone {
+                    player: Player::from(p),
+                    coord: Coord::from(c),
+                })
+                .collect(),
+            captures: Captures {
+                black: s.captures.black,
+                white: s.captures.white,
+            },
         }
     }
 }
@@ -35,7 +158,10 @@ impl From<sync::api::HistoryProvided> for HistoryProvidedEvent {
             game_id: h.game_id.0,
             reply_to: h.reply_to.0,
             moves: h.moves.iter().map(|m| Move::from(m.clone())).collect(),
+            next_page: h.next_page.map(Page::from),
             event_id: h.event_id.0,
+            score: h.score.map(|s| FinalScore::from(s)),
+            result: h.result.map(|r| GameResult::from(r)),
         }
     }
 }
@@ -53,7 +179,8 @@ impl From<lobby::api::GameReady> for be::GameReadyBackendEvent {
     fn from(g: lobby::api::GameReady) -> Self {
         Self {
             game_id: g.game_id.0,
-            board_size: g.board_size as u8,
+            board_size: g.board_size.width as u8,
+            board_height: g.board_size.height as u8,
             event_id: g.event_id.0,
             sessions: crate::model::GameSessions {
                 first: g.sessions.0.0,
@@ -100,6 +227,14 @@ impl From<sync::Move> for Move {
         }
     }
 }
+impl From<sync::Page> for Page {
+    fn from(p: sync::Page) -> Self {
+        Self {
+            since_turn: p.since_turn,
+            size: p.size,
+        }
+    }
+}
 impl From<moves::Player> for Player {
     fn from(p: moves::Player) -> Self {
         match p {
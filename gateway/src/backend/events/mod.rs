@@ -10,6 +10,10 @@ use serde_derive::{Deserialize, Serialize};
 pub enum BackendEvents {
     MoveMade(MoveMadeEvent),
     MoveRejected(MoveRejectedEvent),
+    GameOver(GameOverEvent),
+    DeadStonesMarked(DeadStonesMarkedEvent),
+    ScoreFinalized(ScoreFinalizedEvent),
+    PlayResumed(PlayResumedEvent),
     HistoryProvided(HistoryProvidedEvent),
     GameReady(GameReadyBackendEvent),
     PrivateGameRejected(PrivateGameRejectedBackendEvent),
@@ -19,6 +23,7 @@ pub enum BackendEvents {
     SyncReply(SyncReplyBackendEvent),
     MoveUndone(undo_model::api::MoveUndone),
     UndoRejected(undo_model::api::UndoMove),
+    UndoRequested(undo_model::api::UndoRequested),
 }
 
 impl BackendEvents {
@@ -26,6 +31,10 @@ impl BackendEvents {
         match self {
             BackendEvents::MoveMade(m) => ClientEvents::MoveMade(m),
             BackendEvents::MoveRejected(m) => ClientEvents::MoveRejected(m),
+            BackendEvents::GameOver(g) => ClientEvents::GameOver(g),
+            BackendEvents::DeadStonesMarked(d) => ClientEvents::DeadStonesMarked(d),
+            BackendEvents::ScoreFinalized(s) => ClientEvents::ScoreFinalized(s),
+            BackendEvents::PlayResumed(p) => ClientEvents::PlayResumed(p),
             BackendEvents::HistoryProvided(h) => ClientEvents::HistoryProvided(h),
             // Dummy impl, don't trust it
             BackendEvents::ColorsChosen(c) => ClientEvents::YourColor(YourColorEvent {
@@ -36,11 +45,13 @@ impl BackendEvents {
                 game_id,
                 event_id,
                 board_size,
+                board_height,
                 sessions: _,
             }) => ClientEvents::GameReady(GameReadyClientEvent {
                 game_id,
                 event_id,
                 board_size,
+                board_height,
             }),
             BackendEvents::PrivateGameRejected(p) => {
                 ClientEvents::PrivateGameRejected(PrivateGameRejectedClientEvent {
@@ -75,14 +86,19 @@ impl BackendEvents {
                 reply_to,
                 turn,
                 moves,
+                next_page,
+                snapshot,
             }) => ClientEvents::SyncReply(SyncReplyClientEvent {
                 player_up,
                 turn,
                 reply_to,
                 moves,
+                next_page,
+                snapshot,
             }),
             BackendEvents::MoveUndone(m) => ClientEvents::MoveUndone(m.into()),
             BackendEvents::UndoRejected(u) => ClientEvents::UndoRejected(u),
+            BackendEvents::UndoRequested(u) => ClientEvents::UndoRequested(u),
         }
     }
 
@@ -90,6 +106,10 @@ impl BackendEvents {
         match self {
             BackendEvents::MoveMade(e) => e.game_id,
             BackendEvents::MoveRejected(e) => e.game_id,
+            BackendEvents::GameOver(e) => e.game_id,
+            BackendEvents::DeadStonesMarked(e) => e.game_id,
+            BackendEvents::ScoreFinalized(e) => e.game_id,
+            BackendEvents::PlayResumed(e) => e.game_id,
             BackendEvents::HistoryProvided(e) => e.game_id,
             BackendEvents::GameReady(e) => e.game_id,
             BackendEvents::PrivateGameRejected(e) => e.game_id,
@@ -99,6 +119,7 @@ impl BackendEvents {
             BackendEvents::SyncReply(e) => e.game_id,
             BackendEvents::MoveUndone(e) => e.game_id.0,
             BackendEvents::UndoRejected(e) => e.game_id.0,
+            BackendEvents::UndoRequested(e) => e.game_id.0,
         }
     }
 }
@@ -112,6 +133,8 @@ pub struct GameReadyBackendEvent {
     pub event_id: EventId,
     #[serde(rename = "boardSize")]
     pub board_size: u8,
+    #[serde(rename = "boardHeight")]
+    pub board_height: u8,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -145,5 +168,7 @@ pub struct SyncReplyBackendEvent {
     pub player_up: Player,
     pub turn: u32,
     pub moves: Vec<Move>,
+    pub next_page: Option<Page>,
+    pub snapshot: Option<Snapshot>,
     pub game_id: GameId,
 }
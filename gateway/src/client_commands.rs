@@ -21,6 +21,29 @@ pub struct JoinPrivateGameClientCommand {
 pub struct CreatePrivateGameClientCommand {
     #[serde(rename = "boardSize")]
     pub board_size: Option<u8>,
+    /// Only sent for rectangular boards, in which
+    /// case boardSize gives the width
+    #[serde(rename = "boardHeight", default)]
+    pub board_height: Option<u8>,
+    #[serde(default)]
+    pub ruleset: Option<move_model::Ruleset>,
+    #[serde(default)]
+    pub komi: Option<f32>,
+    /// A number of stones on the star points, or
+    /// a list of points for free placement
+    #[serde(default)]
+    pub handicap: Option<move_model::Handicap>,
+    /// Bots only play standard games, so
+    /// only private games offer a variant
+    #[serde(default)]
+    pub variant: move_model::Variant,
+    /// How often each player may take moves back
+    #[serde(default)]
+    pub undo: move_model::UndoPolicy,
+    /// Nobody takes moves back in a rated game,
+    /// whatever the undo policy says
+    #[serde(default)]
+    pub rated: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -34,7 +57,17 @@ pub struct AttachBotClientCommand {
     pub player: Player,
     #[serde(rename = "boardSize")]
     pub board_size: Option<u8>,
+    #[serde(rename = "boardHeight", default)]
+    pub board_height: Option<u8>,
     pub bot: bot_model::Bot,
+    #[serde(default)]
+    pub ruleset: Option<move_model::Ruleset>,
+    #[serde(default)]
+    pub komi: Option<f32>,
+    #[serde(default)]
+    pub handicap: Option<move_model::Handicap>,
+    #[serde(default)]
+    pub undo: move_model::UndoPolicy,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -42,6 +75,38 @@ pub struct UndoMoveClientCommand {
     pub player: Player,
 }
 
+/// Takes back several moves at once.  Turn is the
+/// one which will be played next.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UndoToTurnClientCommand {
+    pub player: Player,
+    pub turn: u16,
+}
+
+/// The opponent's answer to an UndoRequested event.
+/// The gateway answers as the color which the session
+/// was given, so the client does not name a player.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnswerUndoClientCommand {}
+
+/// Marks the group at coord dead, or alive again,
+/// once the current game is over.  Like the commands
+/// below, it is sent as the session's color.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ToggleDeadStoneClientCommand {
+    pub coord: Coord,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AcceptScoreClientCommand {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResumePlayClientCommand {}
+
+/// Concede the current game, as the session's color
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResignClientCommand {}
+
 /// This command requests synchronization with the backend
 /// view of the game.  
 ///
@@ -56,6 +121,16 @@ pub struct UndoMoveClientCommand {
 /// a move which was presumed sent by the client hasn't actually
 /// been received by gateway.  When that happens, backend should
 /// trigger a MakeMove event.
+///
+/// recent_moves are the client's latest moves, oldest first,
+/// so that the backend can catch up on more than one of them.
+/// page limits the moves sent back, so that a client which
+/// is nearly caught up need not hear the whole game again.
+///
+/// board_checksum is the client's board and captures, hashed
+/// the same way as move_model's Board::checksum.  It is sent
+/// as a hex string, since it does not fit in a javascript
+/// number.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ReqSyncClientCommand {
@@ -63,6 +138,12 @@ pub struct ReqSyncClientCommand {
     pub player_up: Player,
     pub turn: u32,
     pub last_move: Option<Move>,
+    #[serde(default)]
+    pub recent_moves: Vec<Move>,
+    #[serde(default)]
+    pub page: Page,
+    #[serde(default)]
+    pub board_checksum: Option<String>,
 }
 
 /// Events originating from the browser and
@@ -84,6 +165,13 @@ pub enum ClientCommands {
     AttachBot(AttachBotClientCommand),
     ReqSync(ReqSyncClientCommand),
     UndoMove(UndoMoveClientCommand),
+    UndoToTurn(UndoToTurnClientCommand),
+    AcceptUndo(AnswerUndoClientCommand),
+    DeclineUndo(AnswerUndoClientCommand),
+    ToggleDeadStone(ToggleDeadStoneClientCommand),
+    AcceptScore(AcceptScoreClientCommand),
+    ResumePlay(ResumePlayClientCommand),
+    Resign(ResignClientCommand),
 }
 
 #[cfg(test)]
@@ -145,7 +233,16 @@ mod tests {
 
         assert_eq!(
             d,
-            ClientCommands::CreatePrivateGame(CreatePrivateGameClientCommand { board_size: None })
+            ClientCommands::CreatePrivateGame(CreatePrivateGameClientCommand {
+                board_size: None,
+                board_height: None,
+                ruleset: None,
+                komi: None,
+                handicap: None,
+                variant: move_model::Variant::Standard,
+                undo: move_model::UndoPolicy::Unlimited,
+                rated: false,
+            })
         )
     }
 
@@ -158,11 +255,220 @@ mod tests {
         assert_eq!(
             d,
             ClientCommands::CreatePrivateGame(CreatePrivateGameClientCommand {
-                board_size: Some(9)
+                board_size: Some(9),
+                board_height: None,
+                ruleset: None,
+                komi: None,
+                handicap: None,
+                variant: move_model::Variant::Standard,
+                undo: move_model::UndoPolicy::Unlimited,
+                rated: false,
             })
         )
     }
 
+    #[test]
+    fn deserialize_create_private_game_rectangular() {
+        let json = "{\"type\":\"CreatePrivateGame\",\"boardSize\":9,\"boardHeight\":7}";
+
+        let d: ClientCommands = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            d,
+            ClientCommands::CreatePrivateGame(CreatePrivateGameClientCommand {
+                board_size: Some(9),
+                board_height: Some(7),
+                ruleset: None,
+                komi: None,
+                handicap: None,
+                variant: move_model::Variant::Standard,
+                undo: move_model::UndoPolicy::Unlimited,
+                rated: false,
+            })
+        )
+    }
+
+    #[test]
+    fn deserialize_create_private_game_ruleset_komi() {
+        let json =
+            "{\"type\":\"CreatePrivateGame\",\"boardSize\":9,\"ruleset\":\"Japanese\",\"komi\":0.5}";
+
+        let d: ClientCommands = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            d,
+            ClientCommands::CreatePrivateGame(CreatePrivateGameClientCommand {
+                board_size: Some(9),
+                board_height: None,
+                ruleset: Some(move_model::Ruleset::Japanese),
+                komi: Some(0.5),
+                handicap: None,
+                variant: move_model::Variant::Standard,
+                undo: move_model::UndoPolicy::Unlimited,
+                rated: false,
+            })
+        )
+    }
+
+    #[test]
+    fn deserialize_create_private_game_undo_policy() {
+        let json = "{\"type\":\"CreatePrivateGame\",\"boardSize\":9,\"undo\":{\"PerGame\":3},\"rated\":true}";
+
+        let d: ClientCommands = serde_json::from_str(json).unwrap();
+
+        match d {
+            ClientCommands::CreatePrivateGame(cp) => {
+                assert_eq!(cp.undo, move_model::UndoPolicy::PerGame(3));
+                assert!(cp.rated)
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deserialize_create_private_game_handicap() {
+        let fixed = "{\"type\":\"CreatePrivateGame\",\"boardSize\":19,\"handicap\":4}";
+        let free = "{\"type\":\"CreatePrivateGame\",\"boardSize\":9,\"handicap\":[{\"x\":2,\"y\":3},{\"x\":5,\"y\":5}]}";
+
+        match serde_json::from_str(fixed).unwrap() {
+            ClientCommands::CreatePrivateGame(cp) => {
+                assert_eq!(cp.handicap, Some(move_model::Handicap::Fixed(4)))
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        match serde_json::from_str(free).unwrap() {
+            ClientCommands::CreatePrivateGame(cp) => assert_eq!(
+                cp.handicap,
+                Some(move_model::Handicap::Free(vec![
+                    move_model::Coord::of(2, 3),
+                    move_model::Coord::of(5, 5)
+                ]))
+            ),
+            other => panic!("unexpected command {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deserialize_create_private_game_variant() {
+        let json = "{\"type\":\"CreatePrivateGame\",\"boardSize\":9,\"variant\":\"AtariGo\"}";
+
+        let d: ClientCommands = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            d,
+            ClientCommands::CreatePrivateGame(CreatePrivateGameClientCommand {
+                board_size: Some(9),
+                board_height: None,
+                ruleset: None,
+                komi: None,
+                handicap: None,
+                variant: move_model::Variant::AtariGo,
+                undo: move_model::UndoPolicy::Unlimited,
+                rated: false,
+            })
+        )
+    }
+
+    #[test]
+    fn deserialize_req_sync_recent_moves() {
+        let req_id = Uuid::new_v4();
+        let json = format!(
+            "{{\"type\":\"ReqSync\",\"reqId\":\"{}\",\"playerUp\":\"BLACK\",\"turn\":3,\"lastMove\":{{\"player\":\"WHITE\",\"coord\":null,\"turn\":2}},\"recentMoves\":[{{\"player\":\"BLACK\",\"coord\":{{\"x\":1,\"y\":2}},\"turn\":1}},{{\"player\":\"WHITE\",\"coord\":null,\"turn\":2}}],\"page\":{{\"sinceTurn\":2,\"size\":10}},\"boardChecksum\":\"54b3fe25d730eb05\"}}",
+            req_id
+        );
+
+        let d: ClientCommands = serde_json::from_str(&json).unwrap();
+
+        let last_move = Move {
+            player: Player::WHITE,
+            coord: None,
+            turn: 2,
+        };
+        assert_eq!(
+            d,
+            ClientCommands::ReqSync(ReqSyncClientCommand {
+                req_id,
+                player_up: Player::BLACK,
+                turn: 3,
+                last_move: Some(last_move),
+                recent_moves: vec![
+                    Move {
+                        player: Player::BLACK,
+                        coord: Some(Coord { x: 1, y: 2 }),
+                        turn: 1,
+                    },
+                    last_move
+                ],
+                page: Page {
+                    since_turn: 2,
+                    size: Some(10)
+                },
+                board_checksum: Some("54b3fe25d730eb05".to_string()),
+            })
+        )
+    }
+
+    #[test]
+    fn refuse_req_sync_empty_page() {
+        let json = format!(
+            "{{\"type\":\"ReqSync\",\"reqId\":\"{}\",\"playerUp\":\"BLACK\",\"turn\":1,\"lastMove\":null,\"page\":{{\"sinceTurn\":0,\"size\":0}}}}",
+            Uuid::new_v4()
+        );
+
+        assert!(serde_json::from_str::<ClientCommands>(&json).is_err())
+    }
+
+    #[test]
+    fn deserialize_undo_to_turn_client_command() {
+        let json = "{\"type\":\"UndoToTurn\",\"player\":\"BLACK\",\"turn\":5}";
+
+        let d: ClientCommands = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            d,
+            ClientCommands::UndoToTurn(UndoToTurnClientCommand {
+                player: Player::BLACK,
+                turn: 5
+            })
+        )
+    }
+
+    #[test]
+    fn deserialize_answer_undo_client_commands() {
+        let accept: ClientCommands =
+            serde_json::from_str("{\"type\":\"AcceptUndo\",\"player\":\"WHITE\"}").unwrap();
+        let decline: ClientCommands =
+            serde_json::from_str("{\"type\":\"DeclineUndo\",\"player\":\"BLACK\"}").unwrap();
+
+        assert_eq!(
+            accept,
+            ClientCommands::AcceptUndo(AnswerUndoClientCommand {})
+        );
+        assert_eq!(
+            decline,
+            ClientCommands::DeclineUndo(AnswerUndoClientCommand {})
+        )
+    }
+
+    #[test]
+    fn deserialize_accept_score_client_command() {
+        // older clients still name a player, which is ignored
+        let json = "{\"type\":\"AcceptScore\",\"player\":\"WHITE\"}";
+
+        let d: ClientCommands = serde_json::from_str(json).unwrap();
+
+        assert_eq!(d, ClientCommands::AcceptScore(AcceptScoreClientCommand {}))
+    }
+
+    #[test]
+    fn deserialize_resign_client_command() {
+        let json = "{\"type\":\"Resign\",\"player\":\"WHITE\"}";
+
+        let d: ClientCommands = serde_json::from_str(json).unwrap();
+
+        assert_eq!(d, ClientCommands::Resign(ResignClientCommand {}))
+    }
+
     #[test]
     fn deserialize_beep_client_command() {
         let json = "{\"type\":\"Beep\"}";
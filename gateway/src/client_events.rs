@@ -13,6 +13,10 @@ use crate::model::*;
 pub enum ClientEvents {
     MoveMade(MoveMadeEvent),
     MoveRejected(MoveRejectedEvent),
+    GameOver(GameOverEvent),
+    DeadStonesMarked(DeadStonesMarkedEvent),
+    ScoreFinalized(ScoreFinalizedEvent),
+    PlayResumed(PlayResumedEvent),
     Reconnected(ReconnectedEvent),
     HistoryProvided(HistoryProvidedEvent),
     GameReady(GameReadyClientEvent),
@@ -26,6 +30,12 @@ pub enum ClientEvents {
     SyncReply(SyncReplyClientEvent),
     MoveUndone(MoveUndoneClientEvent),
     UndoRejected(undo_model::api::UndoMove),
+    /// Sent to both players.  The one who did not ask
+    /// should answer with AcceptUndo or DeclineUndo.
+    UndoRequested(undo_model::api::UndoRequested),
+    /// Sent instead of creating a game when the client
+    /// asked for options which the server will not allow
+    GameOptionsRejected(GameOptionsRejectedClientEvent),
 }
 
 impl ClientEvents {
@@ -36,6 +46,10 @@ impl ClientEvents {
         match self {
             ClientEvents::MoveMade(e) => Some(e.game_id),
             ClientEvents::MoveRejected(e) => Some(e.game_id),
+            ClientEvents::GameOver(e) => Some(e.game_id),
+            ClientEvents::DeadStonesMarked(e) => Some(e.game_id),
+            ClientEvents::ScoreFinalized(e) => Some(e.game_id),
+            ClientEvents::PlayResumed(e) => Some(e.game_id),
             ClientEvents::Reconnected(e) => Some(e.game_id),
             ClientEvents::HistoryProvided(e) => Some(e.game_id),
             ClientEvents::GameReady(e) => Some(e.game_id),
@@ -44,11 +58,23 @@ impl ClientEvents {
             ClientEvents::BotAttached(b) => Some(b.game_id.0),
             ClientEvents::MoveUndone(m) => Some(m.game_id),
             ClientEvents::UndoRejected(u) => Some(u.game_id.0),
+            ClientEvents::UndoRequested(u) => Some(u.game_id.0),
             _ => None,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum GameOption {
+    Komi,
+    Handicap,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameOptionsRejectedClientEvent {
+    pub option: GameOption,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Link(String);
 impl Link {
@@ -88,6 +114,8 @@ pub struct GameReadyClientEvent {
     pub event_id: EventId,
     #[serde(rename = "boardSize")]
     pub board_size: u8,
+    #[serde(rename = "boardHeight")]
+    pub board_height: u8,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -112,6 +140,9 @@ pub struct SyncReplyClientEvent {
     pub player_up: Player,
     pub turn: u32,
     pub moves: Vec<Move>,
+    pub next_page: Option<Page>,
+    /// Present when the client's board has drifted from ours
+    pub snapshot: Option<Snapshot>,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -73,6 +73,8 @@ pub struct ProvideHistoryCommand {
     pub game_id: GameId,
     #[serde(rename = "reqId")]
     pub req_id: ReqId,
+    #[serde(default)]
+    pub page: Page,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -97,7 +99,100 @@ pub struct MoveRejectedEvent {
     #[serde(rename = "eventId")]
     pub event_id: EventId,
     pub player: Player,
-    pub coord: Coord,
+    pub coord: Option<Coord>,
+    pub reason: MoveRejectReason,
+}
+
+/// Both players passed in succession, or one player
+/// resigned, and the game will accept no more moves
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameOverEvent {
+    #[serde(rename = "gameId")]
+    pub game_id: GameId,
+    #[serde(rename = "eventId")]
+    pub event_id: EventId,
+    pub result: GameResult,
+    /// Absent when the game ended by resignation
+    pub score: Option<FinalScore>,
+}
+
+/// The dead stones as currently marked, the players
+/// who have accepted them, and the resulting score
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeadStonesMarkedEvent {
+    #[serde(rename = "gameId")]
+    pub game_id: GameId,
+    #[serde(rename = "eventId")]
+    pub event_id: EventId,
+    pub dead: Vec<Coord>,
+    pub accepted: Vec<Player>,
+    pub score: FinalScore,
+}
+
+/// Both players accepted the score
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScoreFinalizedEvent {
+    #[serde(rename = "gameId")]
+    pub game_id: GameId,
+    #[serde(rename = "eventId")]
+    pub event_id: EventId,
+    pub dead: Vec<Coord>,
+    pub result: GameResult,
+    pub score: FinalScore,
+}
+
+/// A player disputed the marking, and the game
+/// will accept moves again
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlayResumedEvent {
+    #[serde(rename = "gameId")]
+    pub game_id: GameId,
+    #[serde(rename = "eventId")]
+    pub event_id: EventId,
+    pub player: Player,
+}
+
+/// The count under one scoring method, including komi
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub black: f32,
+    pub white: f32,
+    pub winner: Option<Player>,
+    pub margin: f32,
+}
+
+/// Area (Chinese) and territory (Japanese) counts
+/// for a finished game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FinalScore {
+    pub area: Score,
+    pub territory: Score,
+    pub komi: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GameResult {
+    pub winner: Option<Player>,
+    pub reason: EndReason,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum EndReason {
+    Score,
+    Resignation,
+    FirstCapture,
+}
+
+/// Tells the client why its move was not accepted
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum MoveRejectReason {
+    WrongPlayer,
+    OutOfBounds,
+    Occupied,
+    Ko,
+    Suicide,
+    GameOver,
+    UnknownGame,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -131,6 +226,10 @@ pub struct HistoryProvidedEvent {
     #[serde(rename = "eventId")]
     pub event_id: EventId,
     pub moves: Vec<Move>,
+    #[serde(rename = "nextPage")]
+    pub next_page: Option<Page>,
+    pub score: Option<FinalScore>,
+    pub result: Option<GameResult>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -151,6 +250,23 @@ impl Default for Captures {
     }
 }
 
+/// A stone on the board
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Stone {
+    pub player: Player,
+    pub coord: Coord,
+}
+
+/// The backend's board, sent along with a sync reply
+/// when the client's board checksum disagrees with it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snapshot {
+    pub width: u16,
+    pub height: u16,
+    pub stones: Vec<Stone>,
+    pub captures: Captures,
+}
+
 /// The basic components of a move, used by several
 /// other data structures
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
@@ -160,6 +276,37 @@ pub struct Move {
     pub turn: i32,
 }
 
+/// Which moves to send: those after `since_turn`, and
+/// no more than `size` of them.  Replies give the page
+/// to ask for next when moves are left over.  A page of
+/// no moves is refused.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase", try_from = "UncheckedPage")]
+pub struct Page {
+    pub since_turn: u32,
+    pub size: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UncheckedPage {
+    since_turn: u32,
+    size: Option<u32>,
+}
+
+impl std::convert::TryFrom<UncheckedPage> for Page {
+    type Error = String;
+    fn try_from(page: UncheckedPage) -> Result<Self, Self::Error> {
+        match page.size {
+            Some(0) => Err("a page must hold at least one move".to_string()),
+            size => Ok(Page {
+                since_turn: page.since_turn,
+                size,
+            }),
+        }
+    }
+}
+
 impl std::fmt::Display for Player {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -9,6 +9,11 @@ use undo_model as undo;
 pub enum StreamData {
     BotAttached(bot::api::BotAttached),
     MoveMade(moves::MoveMade),
+    MoveRejected(moves::MoveRejected),
+    GameOver(moves::GameOver),
+    DeadStonesMarked(moves::DeadStonesMarked),
+    ScoreFinalized(moves::ScoreFinalized),
+    PlayResumed(moves::PlayResumed),
     HistoryProvided(sync::api::HistoryProvided),
     SyncReply(sync::api::SyncReply),
     WaitForOpponent(lobby::api::WaitForOpponent),
@@ -17,6 +22,7 @@ pub enum StreamData {
     ColorsChosen(color::api::ColorsChosen),
     MoveUndone(undo::api::MoveUndone),
     UndoRejected(undo::api::UndoMove),
+    UndoRequested(undo::api::UndoRequested),
 }
 
 impl From<sync_model::api::HistoryProvided> for StreamData {
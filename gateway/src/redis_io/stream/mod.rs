@@ -11,7 +11,10 @@ pub use write::write_loop;
 
 use crate::backend::events as be;
 use crate::backend::events::BackendEvents;
-use crate::model::{ColorsChosenEvent, HistoryProvidedEvent, MoveMadeEvent};
+use crate::model::{
+    ColorsChosenEvent, DeadStonesMarkedEvent, GameOverEvent, HistoryProvidedEvent, MoveMadeEvent,
+    MoveRejectedEvent, PlayResumedEvent, ScoreFinalizedEvent,
+};
 use crossbeam_channel::Sender;
 use log::{error, info};
 use xack::XAck;
@@ -55,6 +58,15 @@ impl From<StreamData> for BackendEvents {
     fn from(stream_data: StreamData) -> Self {
         match stream_data {
             StreamData::MoveMade(m) => BackendEvents::MoveMade(MoveMadeEvent::from(m)),
+            StreamData::MoveRejected(m) => BackendEvents::MoveRejected(MoveRejectedEvent::from(m)),
+            StreamData::GameOver(g) => BackendEvents::GameOver(GameOverEvent::from(g)),
+            StreamData::DeadStonesMarked(d) => {
+                BackendEvents::DeadStonesMarked(DeadStonesMarkedEvent::from(d))
+            }
+            StreamData::ScoreFinalized(s) => {
+                BackendEvents::ScoreFinalized(ScoreFinalizedEvent::from(s))
+            }
+            StreamData::PlayResumed(p) => BackendEvents::PlayResumed(PlayResumedEvent::from(p)),
             StreamData::BotAttached(b) => BackendEvents::BotAttached(b),
             StreamData::HistoryProvided(h) => {
                 BackendEvents::HistoryProvided(HistoryProvidedEvent::from(h))
@@ -76,6 +88,7 @@ impl From<StreamData> for BackendEvents {
             }
             StreamData::MoveUndone(m) => be::BackendEvents::MoveUndone(m),
             StreamData::UndoRejected(u) => be::BackendEvents::UndoRejected(u),
+            StreamData::UndoRequested(u) => be::BackendEvents::UndoRequested(u),
         }
     }
 }
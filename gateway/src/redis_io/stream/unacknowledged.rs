@@ -5,6 +5,11 @@ use redis_streams::XReadEntryId;
 
 pub struct Unacknowledged {
     move_made: Vec<XReadEntryId>,
+    move_rejected: Vec<XReadEntryId>,
+    game_over: Vec<XReadEntryId>,
+    dead_stones_marked: Vec<XReadEntryId>,
+    score_finalized: Vec<XReadEntryId>,
+    play_resumed: Vec<XReadEntryId>,
     history_provided: Vec<XReadEntryId>,
     sync_reply: Vec<XReadEntryId>,
     wait_for_opponent: Vec<XReadEntryId>,
@@ -14,6 +19,7 @@ pub struct Unacknowledged {
     bot_attached: Vec<XReadEntryId>,
     move_undone: Vec<XReadEntryId>,
     undo_rejected: Vec<XReadEntryId>,
+    undo_requested: Vec<XReadEntryId>,
 }
 
 const INIT_ACK_CAPACITY: usize = 25;
@@ -26,6 +32,41 @@ impl Unacknowledged {
                 self.move_made.clear();
             }
         }
+        if !self.move_rejected.is_empty() {
+            if let Err(_e) = stream.ack_move_rejected(&self.move_rejected) {
+                error!("ack for move rejected failed")
+            } else {
+                self.move_rejected.clear();
+            }
+        }
+        if !self.game_over.is_empty() {
+            if let Err(_e) = stream.ack_game_over(&self.game_over) {
+                error!("ack for game over failed")
+            } else {
+                self.game_over.clear();
+            }
+        }
+        if !self.dead_stones_marked.is_empty() {
+            if let Err(_e) = stream.ack_dead_stones_marked(&self.dead_stones_marked) {
+                error!("ack for dead stones marked failed")
+            } else {
+                self.dead_stones_marked.clear();
+            }
+        }
+        if !self.score_finalized.is_empty() {
+            if let Err(_e) = stream.ack_score_finalized(&self.score_finalized) {
+                error!("ack for score finalized failed")
+            } else {
+                self.score_finalized.clear();
+            }
+        }
+        if !self.play_resumed.is_empty() {
+            if let Err(_e) = stream.ack_play_resumed(&self.play_resumed) {
+                error!("ack for play resumed failed")
+            } else {
+                self.play_resumed.clear();
+            }
+        }
         if !self.history_provided.is_empty() {
             if let Err(_e) = stream.ack_history_provided(&self.history_provided) {
                 error!("ack hp failed")
@@ -92,10 +133,23 @@ impl Unacknowledged {
                 self.undo_rejected.clear();
             }
         }
+
+        if !self.undo_requested.is_empty() {
+            if stream.ack_undo_requested(&self.undo_requested).is_err() {
+                error!("ack undo requested failed")
+            } else {
+                self.undo_requested.clear();
+            }
+        }
     }
     pub fn push(&mut self, xid: XReadEntryId, event: StreamData) {
         match event {
             StreamData::MoveMade(_) => self.move_made.push(xid),
+            StreamData::MoveRejected(_) => self.move_rejected.push(xid),
+            StreamData::GameOver(_) => self.game_over.push(xid),
+            StreamData::DeadStonesMarked(_) => self.dead_stones_marked.push(xid),
+            StreamData::ScoreFinalized(_) => self.score_finalized.push(xid),
+            StreamData::PlayResumed(_) => self.play_resumed.push(xid),
             StreamData::HistoryProvided(_) => self.history_provided.push(xid),
             StreamData::SyncReply(_) => self.sync_reply.push(xid),
             StreamData::WaitForOpponent(_) => self.wait_for_opponent.push(xid),
@@ -105,6 +159,7 @@ impl Unacknowledged {
             StreamData::BotAttached(_) => self.bot_attached.push(xid),
             StreamData::MoveUndone(_) => self.move_undone.push(xid),
             StreamData::UndoRejected(_) => self.undo_rejected.push(xid),
+            StreamData::UndoRequested(_) => self.undo_requested.push(xid),
         }
     }
 }
@@ -116,6 +171,11 @@ impl Default for Unacknowledged {
         }
         Self {
             move_made: nv(),
+            move_rejected: nv(),
+            game_over: nv(),
+            dead_stones_marked: nv(),
+            score_finalized: nv(),
+            play_resumed: nv(),
             history_provided: nv(),
             sync_reply: nv(),
             wait_for_opponent: nv(),
@@ -125,6 +185,7 @@ impl Default for Unacknowledged {
             bot_attached: nv(),
             move_undone: nv(),
             undo_rejected: nv(),
+            undo_requested: nv(),
         }
     }
 }
@@ -24,8 +24,15 @@ pub fn write_loop(commands_out: Receiver<BC>, cmds: &dyn XAddCommands) {
                         BC::CreateGame(cg) => cmds.xadd_create_game(cg),
                         BC::ChooseColorPref(cp) => cmds.xadd_choose_color_pref(cp),
                         BC::SessionDisconnected(sd) => cmds.xadd_session_disconnected(sd),
+                        BC::QuitGame(qg) => cmds.xadd_quit_game(qg),
                         BC::UndoMove(ud) => cmds.xadd_undo_move(ud),
-                        _ => error!("cannot match backend command to xadd"),
+                        BC::UndoToTurn(ut) => cmds.xadd_undo_to_turn(ut),
+                        BC::AcceptUndo(au) => cmds.xadd_accept_undo(au),
+                        BC::DeclineUndo(du) => cmds.xadd_decline_undo(du),
+                        BC::ToggleDeadStone(td) => cmds.xadd_toggle_dead_stone(td),
+                        BC::AcceptScore(acc) => cmds.xadd_accept_score(acc),
+                        BC::ResumePlay(rp) => cmds.xadd_resume_play(rp),
+                        BC::Resign(rg) => cmds.xadd_resign(rg),
                     }
                 }
             }
@@ -5,6 +5,11 @@ use std::sync::Arc;
 
 pub trait XAck {
     fn ack_move_made(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_move_rejected(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_game_over(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_dead_stones_marked(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_score_finalized(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_play_resumed(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_history_provided(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_sync_reply(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_wait_for_opponent(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
@@ -14,6 +19,7 @@ pub trait XAck {
     fn ack_colors_chosen(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_move_undone(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_undo_rejected(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_undo_requested(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
 }
 
 #[derive(Debug)]
@@ -24,6 +30,26 @@ impl XAck for Arc<Client> {
         ack(self, topics::MOVE_MADE_TOPIC, ids)
     }
 
+    fn ack_move_rejected(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::MOVE_REJECTED_TOPIC, ids)
+    }
+
+    fn ack_game_over(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::GAME_OVER_TOPIC, ids)
+    }
+
+    fn ack_dead_stones_marked(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::DEAD_STONES_MARKED_TOPIC, ids)
+    }
+
+    fn ack_score_finalized(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::SCORE_FINALIZED_TOPIC, ids)
+    }
+
+    fn ack_play_resumed(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::PLAY_RESUMED_TOPIC, ids)
+    }
+
     fn ack_history_provided(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
         ack(self, topics::HISTORY_PROVIDED_TOPIC, ids)
     }
@@ -59,6 +85,10 @@ impl XAck for Arc<Client> {
     fn ack_undo_rejected(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
         ack(self, topics::UNDO_REJECTED_TOPIC, ids)
     }
+
+    fn ack_undo_requested(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::UNDO_REQUESTED_TOPIC, ids)
+    }
 }
 
 fn ack(client: &Client, key: &str, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
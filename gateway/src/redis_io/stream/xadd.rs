@@ -1,11 +1,12 @@
 use crate::backend::commands::{
     ChooseColorPrefBackendCommand, CreateGameBackendCommand, FindPublicGameBackendCommand,
-    JoinPrivateGameBackendCommand, ReqSyncBackendCommand, SessionDisconnected,
+    JoinPrivateGameBackendCommand, QuitGameCommand, ReqSyncBackendCommand, SessionDisconnected,
 };
 use crate::model::{Coord, MakeMoveCommand, ProvideHistoryCommand};
 use crate::topics;
 use bot_model::api::AttachBot;
-use undo_model::api::UndoMove;
+use move_model::{AcceptScore, Resign, ResumePlay, ToggleDeadStone};
+use undo_model::api::{AcceptUndo, DeclineUndo, UndoMove, UndoToTurn};
 
 use crate::backend::commands::IntoShared;
 use log::error;
@@ -21,7 +22,15 @@ pub trait XAddCommands {
     fn xadd_create_game(&self, command: CreateGameBackendCommand);
     fn xadd_choose_color_pref(&self, command: ChooseColorPrefBackendCommand);
     fn xadd_session_disconnected(&self, command: SessionDisconnected);
+    fn xadd_quit_game(&self, command: QuitGameCommand);
     fn xadd_undo_move(&self, command: UndoMove);
+    fn xadd_undo_to_turn(&self, command: UndoToTurn);
+    fn xadd_accept_undo(&self, command: AcceptUndo);
+    fn xadd_decline_undo(&self, command: DeclineUndo);
+    fn xadd_toggle_dead_stone(&self, command: ToggleDeadStone);
+    fn xadd_accept_score(&self, command: AcceptScore);
+    fn xadd_resume_play(&self, command: ResumePlay);
+    fn xadd_resign(&self, command: Resign);
 }
 
 pub struct RedisXAddCommands {
@@ -127,9 +136,47 @@ impl XAddCommands for RedisXAddCommands {
         )
     }
 
+    fn xadd_quit_game(&self, command: QuitGameCommand) {
+        self.xadd_classic(
+            bincode::serialize(&command.into_shared()),
+            topics::QUIT_GAME_TOPIC,
+        )
+    }
+
     fn xadd_undo_move(&self, command: UndoMove) {
         self.xadd_classic(bincode::serialize(&command), topics::UNDO_MOVE_TOPIC)
     }
+
+    fn xadd_undo_to_turn(&self, command: UndoToTurn) {
+        self.xadd_classic(bincode::serialize(&command), topics::UNDO_TO_TURN_TOPIC)
+    }
+
+    fn xadd_accept_undo(&self, command: AcceptUndo) {
+        self.xadd_classic(bincode::serialize(&command), topics::ACCEPT_UNDO_TOPIC)
+    }
+
+    fn xadd_decline_undo(&self, command: DeclineUndo) {
+        self.xadd_classic(bincode::serialize(&command), topics::DECLINE_UNDO_TOPIC)
+    }
+
+    fn xadd_toggle_dead_stone(&self, command: ToggleDeadStone) {
+        self.xadd_classic(
+            bincode::serialize(&command),
+            topics::TOGGLE_DEAD_STONE_TOPIC,
+        )
+    }
+
+    fn xadd_accept_score(&self, command: AcceptScore) {
+        self.xadd_classic(bincode::serialize(&command), topics::ACCEPT_SCORE_TOPIC)
+    }
+
+    fn xadd_resume_play(&self, command: ResumePlay) {
+        self.xadd_classic(bincode::serialize(&command), topics::RESUME_PLAY_TOPIC)
+    }
+
+    fn xadd_resign(&self, command: Resign) {
+        self.xadd_classic(bincode::serialize(&command), topics::RESIGN_TOPIC)
+    }
 }
 
 impl RedisXAddCommands {
@@ -186,6 +233,7 @@ mod tests {
         Create(CreateGameBackendCommand),
         ChCol(ChooseColorPrefBackendCommand),
         SessDisconn(SessionDisconnected),
+        Quit(QuitGameCommand),
     }
     impl FakeXAddCmd {
         fn sssend(&self, tr: TestResult) {
@@ -228,9 +276,41 @@ mod tests {
             self.sssend(TestResult::SessDisconn(command))
         }
 
+        fn xadd_quit_game(&self, command: QuitGameCommand) {
+            self.sssend(TestResult::Quit(command))
+        }
+
         fn xadd_undo_move(&self, _command: UndoMove) {
             todo!()
         }
+
+        fn xadd_undo_to_turn(&self, _command: UndoToTurn) {
+            todo!()
+        }
+
+        fn xadd_accept_undo(&self, _command: AcceptUndo) {
+            todo!()
+        }
+
+        fn xadd_decline_undo(&self, _command: DeclineUndo) {
+            todo!()
+        }
+
+        fn xadd_toggle_dead_stone(&self, _command: ToggleDeadStone) {
+            todo!()
+        }
+
+        fn xadd_accept_score(&self, _command: AcceptScore) {
+            todo!()
+        }
+
+        fn xadd_resume_play(&self, _command: ResumePlay) {
+            todo!()
+        }
+
+        fn xadd_resign(&self, _command: Resign) {
+            todo!()
+        }
     }
 
     use bot_model::Bot;
@@ -247,8 +327,11 @@ mod tests {
             .send(BC::AttachBot(AttachBot {
                 game_id: core_model::GameId(Uuid::nil()),
                 board_size: Some(9),
+                board_height: None,
                 player: move_model::Player::WHITE,
                 bot: Bot::KataGoOneStar,
+                rules: move_model::Rules::default(),
+                handicap: vec![],
             }))
             .expect("send test");
 
@@ -261,6 +344,13 @@ mod tests {
             }))
             .expect("send move test");
 
+        cmds_in
+            .send(BC::QuitGame(QuitGameCommand {
+                client_id: Uuid::nil(),
+                game_id: Uuid::nil(),
+            }))
+            .expect("send quit test");
+
         select! { recv(test_out) -> msg => match msg.expect("test out 0 ") {
             TestResult::Bot(_) => assert!(true),
             _ => assert!(false)
@@ -269,5 +359,9 @@ mod tests {
             TestResult::Move(_) => assert!(true),
             _ => assert!(false)
         } }
+        select! { recv(test_out) -> msg => match msg.expect("test out 2 ") {
+            TestResult::Quit(quit) => assert_eq!(quit.game_id, Uuid::nil()),
+            _ => panic!("expected quit")
+        } }
     }
 }
@@ -46,9 +46,14 @@ pub struct RedisXReader {
     pub client: Arc<redis::Client>,
 }
 
-const INPUT_TOPICS: &[&str; 10] = &[
+const INPUT_TOPICS: &[&str; 16] = &[
     topics::BOT_ATTACHED_TOPIC,
     topics::MOVE_MADE_TOPIC,
+    topics::MOVE_REJECTED_TOPIC,
+    topics::GAME_OVER_TOPIC,
+    topics::DEAD_STONES_MARKED_TOPIC,
+    topics::SCORE_FINALIZED_TOPIC,
+    topics::PLAY_RESUMED_TOPIC,
     topics::HISTORY_PROVIDED_TOPIC,
     topics::SYNC_REPLY_TOPIC,
     topics::WAIT_FOR_OPPONENT_TOPIC,
@@ -57,6 +62,7 @@ const INPUT_TOPICS: &[&str; 10] = &[
     topics::COLORS_CHOSEN_TOPIC,
     topics::MOVE_UNDONE_TOPIC,
     topics::UNDO_REJECTED_TOPIC,
+    topics::UNDO_REQUESTED_TOPIC,
 ];
 
 lazy_static! {
@@ -110,6 +116,21 @@ fn deser(srr: StreamReadReply) -> Result<HashMap<XReadEntryId, StreamData>, Stre
                         topics::MOVE_MADE_TOPIC => bincode::deserialize(&data)
                             .map(|m| StreamData::MoveMade(m))
                             .ok(),
+                        topics::MOVE_REJECTED_TOPIC => bincode::deserialize(&data)
+                            .map(|m| StreamData::MoveRejected(m))
+                            .ok(),
+                        topics::GAME_OVER_TOPIC => bincode::deserialize(&data)
+                            .map(|g| StreamData::GameOver(g))
+                            .ok(),
+                        topics::DEAD_STONES_MARKED_TOPIC => bincode::deserialize(&data)
+                            .map(|d| StreamData::DeadStonesMarked(d))
+                            .ok(),
+                        topics::SCORE_FINALIZED_TOPIC => bincode::deserialize(&data)
+                            .map(|s| StreamData::ScoreFinalized(s))
+                            .ok(),
+                        topics::PLAY_RESUMED_TOPIC => bincode::deserialize(&data)
+                            .map(|p| StreamData::PlayResumed(p))
+                            .ok(),
                         topics::HISTORY_PROVIDED_TOPIC => bincode::deserialize(&data)
                             .map(|hp| StreamData::HistoryProvided(hp))
                             .ok(),
@@ -134,6 +155,9 @@ fn deser(srr: StreamReadReply) -> Result<HashMap<XReadEntryId, StreamData>, Stre
                         topics::UNDO_REJECTED_TOPIC => bincode::deserialize(&data)
                             .map(|u| StreamData::UndoRejected(u))
                             .ok(),
+                        topics::UNDO_REQUESTED_TOPIC => bincode::deserialize(&data)
+                            .map(StreamData::UndoRequested)
+                            .ok(),
                         _ => {
                             error!("Unknown key {}", key);
                             return Err(StreamDeserErr);
@@ -4,15 +4,26 @@ pub const JOIN_PRIVATE_GAME_TOPIC: &str = "bugout-join-private-game-cmd";
 pub const FIND_PUBLIC_GAME_TOPIC: &str = "bugout-find-public-game-cmd";
 pub const CREATE_GAME_TOPIC: &str = "bugout-create-game-cmd";
 pub const CHOOSE_COLOR_PREF_TOPIC: &str = "bugout-choose-color-pref-cmd";
-/// Will be used in participation service
-pub const _QUIT_GAME_TOPIC: &str = "bugout-quit-game-cmd";
+/// Read by botlink, which stops thinking about the game
+pub const QUIT_GAME_TOPIC: &str = "bugout-quit-game-cmd";
 pub const ATTACH_BOT_TOPIC: &str = "bugout-attach-bot-cmd";
 pub const REQ_SYNC_TOPIC: &str = "bugout-req-sync-cmd";
 pub const UNDO_MOVE_TOPIC: &str = "bugout-undo-move-cmd";
+pub const UNDO_TO_TURN_TOPIC: &str = "bugout-undo-to-turn-cmd";
+pub const ACCEPT_UNDO_TOPIC: &str = "bugout-accept-undo-cmd";
+pub const DECLINE_UNDO_TOPIC: &str = "bugout-decline-undo-cmd";
+pub const TOGGLE_DEAD_STONE_TOPIC: &str = "bugout-toggle-dead-stone-cmd";
+pub const ACCEPT_SCORE_TOPIC: &str = "bugout-accept-score-cmd";
+pub const RESUME_PLAY_TOPIC: &str = "bugout-resume-play-cmd";
+pub const RESIGN_TOPIC: &str = "bugout-resign-cmd";
 
 /// A move was made and judged fit for communication to
 /// all interested clients
 pub const MOVE_MADE_TOPIC: &str = "bugout-move-made-ev";
+pub const MOVE_REJECTED_TOPIC: &str = "bugout-move-rejected-ev";
+/// Both players passed, or one resigned, and no more
+/// moves will be accepted
+pub const GAME_OVER_TOPIC: &str = "bugout-game-over-ev";
 pub const HISTORY_PROVIDED_TOPIC: &str = "bugout-history-provided-ev";
 pub const PRIVATE_GAME_REJECTED_TOPIC: &str = "bugout-private-game-rejected-ev";
 pub const GAME_READY_TOPIC: &str = "bugout-game-ready-ev";
@@ -23,3 +34,10 @@ pub const BOT_ATTACHED_TOPIC: &str = "bugout-bot-attached-ev";
 pub const SYNC_REPLY_TOPIC: &str = "bugout-sync-reply-ev";
 pub const MOVE_UNDONE_TOPIC: &str = "bugout-move-undone-ev";
 pub const UNDO_REJECTED_TOPIC: &str = "bugout-undo-rejected-ev";
+/// A human opponent is asked to agree to an undo
+pub const UNDO_REQUESTED_TOPIC: &str = "bugout-undo-requested-ev";
+/// After the game is over, players mark dead stones
+/// and agree on the score, or resume play
+pub const DEAD_STONES_MARKED_TOPIC: &str = "bugout-dead-stones-marked-ev";
+pub const SCORE_FINALIZED_TOPIC: &str = "bugout-score-finalized-ev";
+pub const PLAY_RESUMED_TOPIC: &str = "bugout-play-resumed-ev";
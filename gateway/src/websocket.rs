@@ -39,6 +39,12 @@ pub struct WsSession {
     pub router_commands_in: crossbeam_channel::Sender<RouterCommand>,
     pub req_idle_status_in: crossbeam_channel::Sender<RequestIdleStatus>,
     pub current_game: Option<GameId>,
+    /// The last game which this session saw end.
+    /// Moves for it are no longer sent to the backend.
+    pub finished_game: Option<GameId>,
+    /// The color this session was given in its current
+    /// game, when its opponent is human
+    pub your_color: Option<Player>,
     pub expire_after: std::time::Instant,
     pub client_id: Option<ClientId>,
 }
@@ -61,6 +67,8 @@ impl WsSession {
             router_commands_in,
             req_idle_status_in,
             current_game: None,
+            finished_game: None,
+            your_color: None,
             expire_after: next_expiry(),
             client_id: None,
         }
@@ -99,6 +107,19 @@ impl WsSession {
         }
     }
 
+    /// Tells the client that no game was created
+    fn reject_game_options(&self, option: GameOption) {
+        info!("🙅 {} {:<8} {:?}", session_code(self), "BADOPTS", option);
+        if let Err(e) = self.ws_out.send(
+            serde_json::to_string(&ClientEvents::GameOptionsRejected(
+                GameOptionsRejectedClientEvent { option },
+            ))
+            .unwrap(),
+        ) {
+            error!("could not reject game options {:?}", e)
+        }
+    }
+
     fn produce_client_heartbeat(&mut self, heartbeat_type: HeartbeatType) {
         if let Some(client_id) = self.client_id {
             if let Err(e) =
@@ -169,6 +190,11 @@ impl Handler for WsSession {
                     }
                 );
 
+                if self.finished_game == Some(game_id) {
+                    info!("🏁 {} {:<8} Ignoring move", session_code(self), "GAMEOVER");
+                    return Ok(self.observe_game());
+                }
+
                 if let Some(c) = self.current_game {
                     if c == game_id {
                         return self
@@ -216,13 +242,18 @@ impl Handler for WsSession {
                     complain_no_client_id()
                 }
             }
-            Ok(ClientCommands::ProvideHistory(ProvideHistoryCommand { game_id, req_id })) => {
+            Ok(ClientCommands::ProvideHistory(ProvideHistoryCommand {
+                game_id,
+                req_id,
+                page,
+            })) => {
                 info!("📋 {} PROVHIST", session_code(self));
 
                 if let Err(e) = self
                     .send_to_backend(BackendCommands::ProvideHistory(ProvideHistoryCommand {
                         game_id,
                         req_id,
+                        page,
                     }))
                     .map_err(|e| ws::Error::from(Box::new(e)))
                 {
@@ -254,19 +285,43 @@ impl Handler for WsSession {
                 Ok(self.observe_game())
             }
             Ok(ClientCommands::CreatePrivateGame(cp)) => {
+                let rules = match move_model::Rules::of(cp.ruleset, cp.komi) {
+                    Ok(rules) => rules,
+                    Err(_) => {
+                        self.reject_game_options(GameOption::Komi);
+                        return Ok(());
+                    }
+                };
+                let width = cp.board_size.unwrap_or(crate::FULL_BOARD_SIZE);
+                let board_size = move_model::BoardSize {
+                    width: width.into(),
+                    height: cp.board_height.unwrap_or(width).into(),
+                };
+                let handicap = match cp.handicap.unwrap_or_default().stones(board_size) {
+                    Ok(stones) => stones,
+                    Err(_) => {
+                        self.reject_game_options(GameOption::Handicap);
+                        return Ok(());
+                    }
+                };
                 // Ignore this request if we already have a game
                 // in progress.
                 if let (None, Some(client_id)) = (self.current_game, self.client_id) {
                     info!("🔒 {} CRETPRIV", session_code(self));
 
-                    let board_size = cp.board_size.unwrap_or(crate::FULL_BOARD_SIZE);
-
                     if let Err(e) = self
                         .send_to_backend(BackendCommands::CreateGame(CreateGameBackendCommand {
                             client_id,
                             visibility: Visibility::Private,
                             session_id: self.session_id,
-                            board_size: board_size as u16,
+                            board_size,
+                            rules: move_model::Rules {
+                                variant: cp.variant,
+                                undo: cp.undo,
+                                ..rules
+                            },
+                            handicap,
+                            rated: cp.rated,
                         }))
                         .map_err(|e| ws::Error::from(Box::new(e)))
                     {
@@ -370,10 +425,39 @@ impl Handler for WsSession {
             Ok(ClientCommands::AttachBot(AttachBotClientCommand {
                 player: lp,
                 board_size,
+                board_height,
                 bot,
+                ruleset,
+                komi,
+                handicap,
+                undo,
             })) => {
                 info!("📌 {} ATACHBOT", session_code(self));
 
+                let rules = match move_model::Rules::of(ruleset, komi) {
+                    Ok(rules) => rules,
+                    Err(_) => {
+                        self.reject_game_options(GameOption::Komi);
+                        return Ok(());
+                    }
+                };
+                // botlink gives a full size board to bots
+                // which were not told the size
+                let width = board_size
+                    .map(u16::from)
+                    .unwrap_or_else(|| move_model::BoardSize::default().width);
+                let size = move_model::BoardSize {
+                    width,
+                    height: board_height.map(u16::from).unwrap_or(width),
+                };
+                let handicap = match handicap.unwrap_or_default().stones(size) {
+                    Ok(stones) => stones,
+                    Err(_) => {
+                        self.reject_game_options(GameOption::Handicap);
+                        return Ok(());
+                    }
+                };
+
                 let player = match lp {
                     Player::BLACK => move_model::Player::BLACK,
                     _ => move_model::Player::WHITE,
@@ -391,7 +475,10 @@ impl Handler for WsSession {
                         game_id: core_model::GameId(game_id),
                         player,
                         board_size,
+                        board_height,
                         bot,
+                        rules: move_model::Rules { undo, ..rules },
+                        handicap,
                     });
 
                     if let Err(e) = self.session_commands_in.send(payload) {
@@ -399,6 +486,9 @@ impl Handler for WsSession {
                     }
 
                     self.current_game = Some(game_id);
+                    // no colors are chosen for a bot game, so the
+                    // human plays whichever one the bot doesn't
+                    self.your_color = Some(lp.other());
                 })
             }
             Ok(ClientCommands::ReqSync(ReqSyncClientCommand {
@@ -406,6 +496,9 @@ impl Handler for WsSession {
                 turn,
                 player_up,
                 last_move,
+                recent_moves,
+                page,
+                board_checksum,
             })) => {
                 if let Some(game_id) = self.current_game {
                     info!("📥 {} {:<8}", session_code(self), "REQSYNC");
@@ -416,6 +509,10 @@ impl Handler for WsSession {
                             turn,
                             player_up,
                             last_move,
+                            recent_moves,
+                            page,
+                            board_checksum: board_checksum
+                                .and_then(|c| u64::from_str_radix(&c, 16).ok()),
                             game_id,
                         }))
                         .map_err(|e| ws::Error::from(Box::new(e)))
@@ -444,6 +541,138 @@ impl Handler for WsSession {
 
                 Ok(())
             }
+            Ok(ClientCommands::UndoToTurn(UndoToTurnClientCommand { player, turn })) => {
+                if let Some(game_id) = self.current_game {
+                    info!("🔙 {} {:<8} {}", session_code(self), "UNDOTURN", turn);
+                    if let Err(e) = self
+                        .send_to_backend(BackendCommands::UndoToTurn(undo_model::api::UndoToTurn {
+                            game_id: core_model::GameId(game_id),
+                            player: player.into(),
+                            turn,
+                        }))
+                        .map_err(|e| ws::Error::from(Box::new(e)))
+                    {
+                        error!("💥 Undo to turn {:?}", e)
+                    }
+                } else {
+                    error!("undo to turn: unknown game")
+                }
+
+                Ok(())
+            }
+            Ok(ClientCommands::AcceptUndo(AnswerUndoClientCommand {})) => {
+                if let (Some(game_id), Some(player)) = (self.current_game, self.your_color) {
+                    info!("👍 {} {:<8}", session_code(self), "ACCUNDO");
+                    if let Err(e) = self
+                        .send_to_backend(BackendCommands::AcceptUndo(undo_model::api::AcceptUndo {
+                            game_id: core_model::GameId(game_id),
+                            player: player.into(),
+                        }))
+                        .map_err(|e| ws::Error::from(Box::new(e)))
+                    {
+                        error!("💥 Accept undo {:?}", e)
+                    }
+                } else {
+                    error!("accept undo: unknown game or color")
+                }
+
+                Ok(())
+            }
+            Ok(ClientCommands::DeclineUndo(AnswerUndoClientCommand {})) => {
+                if let (Some(game_id), Some(player)) = (self.current_game, self.your_color) {
+                    info!("👎 {} {:<8}", session_code(self), "DECUNDO");
+                    if let Err(e) = self
+                        .send_to_backend(BackendCommands::DeclineUndo(
+                            undo_model::api::DeclineUndo {
+                                game_id: core_model::GameId(game_id),
+                                player: player.into(),
+                            },
+                        ))
+                        .map_err(|e| ws::Error::from(Box::new(e)))
+                    {
+                        error!("💥 Decline undo {:?}", e)
+                    }
+                } else {
+                    error!("decline undo: unknown game or color")
+                }
+
+                Ok(())
+            }
+            Ok(ClientCommands::ToggleDeadStone(ToggleDeadStoneClientCommand { coord })) => {
+                if let (Some(game_id), Some(player)) = (self.current_game, self.your_color) {
+                    info!("💀 {} {:<8}", session_code(self), "TOGLDEAD");
+                    if let Err(e) = self
+                        .send_to_backend(BackendCommands::ToggleDeadStone(
+                            move_model::ToggleDeadStone {
+                                game_id: core_model::GameId(game_id),
+                                player: player.into(),
+                                coord: coord.into(),
+                            },
+                        ))
+                        .map_err(|e| ws::Error::from(Box::new(e)))
+                    {
+                        error!("💥 Toggle dead stone {:?}", e)
+                    }
+                } else {
+                    error!("toggle dead stone: unknown game or color")
+                }
+
+                Ok(())
+            }
+            Ok(ClientCommands::AcceptScore(AcceptScoreClientCommand {})) => {
+                if let (Some(game_id), Some(player)) = (self.current_game, self.your_color) {
+                    info!("🤝 {} {:<8}", session_code(self), "ACCSCORE");
+                    if let Err(e) = self
+                        .send_to_backend(BackendCommands::AcceptScore(move_model::AcceptScore {
+                            game_id: core_model::GameId(game_id),
+                            player: player.into(),
+                        }))
+                        .map_err(|e| ws::Error::from(Box::new(e)))
+                    {
+                        error!("💥 Accept score {:?}", e)
+                    }
+                } else {
+                    error!("accept score: unknown game or color")
+                }
+
+                Ok(())
+            }
+            Ok(ClientCommands::Resign(ResignClientCommand {})) => {
+                if let (Some(game_id), Some(player)) = (self.current_game, self.your_color) {
+                    info!("🏳️ {} {:<8}", session_code(self), "RESIGN");
+                    if let Err(e) = self
+                        .send_to_backend(BackendCommands::Resign(move_model::Resign {
+                            game_id: core_model::GameId(game_id),
+                            player: player.into(),
+                        }))
+                        .map_err(|e| ws::Error::from(Box::new(e)))
+                    {
+                        error!("💥 Resign {:?}", e)
+                    }
+                } else {
+                    error!("resign: unknown game or color")
+                }
+
+                Ok(())
+            }
+            Ok(ClientCommands::ResumePlay(ResumePlayClientCommand {})) => {
+                if let (Some(game_id), Some(player)) = (self.current_game, self.your_color) {
+                    info!("▶️ {} {:<8}", session_code(self), "RESUME");
+                    if let Err(e) = self
+                        .send_to_backend(BackendCommands::ResumePlay(move_model::ResumePlay {
+                            game_id: core_model::GameId(game_id),
+                            player: player.into(),
+                        }))
+                        .map_err(|e| ws::Error::from(Box::new(e)))
+                    {
+                        error!("💥 Resume play {:?}", e)
+                    }
+                } else {
+                    error!("resume play: unknown game or color")
+                }
+
+                Ok(())
+            }
             Err(_err) => {
                 error!(
                     "💥 {} {:<8} message deserialization {}",
@@ -527,6 +756,7 @@ impl Handler for WsSession {
                                 game_id,
                                 event_id: _,
                                 board_size: _,
+                                board_height: _,
                             }) => {
                                 self.current_game = Some(game_id.clone());
                                 info!("🎳 {} {:<8}", session_code(self), "GAMEREDY");
@@ -544,16 +774,19 @@ impl Handler for WsSession {
                                 game_id: _,
                                 your_color,
                             }) if your_color == &Player::BLACK => {
+                                self.your_color = Some(Player::BLACK);
                                 info!("⚫️ {} {:<8} Black", session_code(self), "YOURCOLR")
                             }
                             ClientEvents::YourColor(YourColorEvent {
                                 game_id: _,
                                 your_color,
                             }) if your_color == &Player::WHITE => {
+                                self.your_color = Some(Player::WHITE);
                                 info!("⚪️ {} {:<8} White", session_code(self), "YOURCOLR")
                             }
                             ClientEvents::OpponentQuit => {
                                 self.current_game = None;
+                                self.your_color = None;
                             }
                             ClientEvents::MoveMade(m) => info!(
                                 "🆗 {} {:<8} {} {:?}",
@@ -562,6 +795,21 @@ impl Handler for WsSession {
                                 m.player,
                                 m.coord
                             ),
+                            ClientEvents::GameOver(GameOverEvent {
+                                game_id,
+                                event_id: _,
+                                result: _,
+                                score: _,
+                            }) => {
+                                self.finished_game = Some(*game_id);
+                                info!("🏁 {} {:<8}", session_code(self), "GAMEOVER")
+                            }
+                            ClientEvents::PlayResumed(PlayResumedEvent { game_id, .. }) => {
+                                if self.finished_game == Some(*game_id) {
+                                    self.finished_game = None
+                                }
+                                info!("▶️ {} {:<8}", session_code(self), "RESUMED")
+                            }
                             ClientEvents::SyncReply(_) => {
                                 info!("📤 {} {:<8}", session_code(self), "SYNCRPLY")
                             }
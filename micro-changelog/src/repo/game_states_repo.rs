@@ -6,32 +6,61 @@ use redis::Commands;
 
 const EXPIRY_SECS: usize = 86400;
 
-pub fn fetch(game_id: &GameId, components: &Components) -> Result<Option<GameState>, FetchErr> {
+/// Fetches the game state along with the version of its
+/// record, which must be given back when writing it
+pub fn fetch(
+    game_id: &GameId,
+    components: &Components,
+) -> Result<Option<(GameState, u64)>, FetchErr> {
     let mut conn = components.client.get_connection().expect("fetch conn");
-    let key = components.redis_key_provider.game_states(&game_id);
-    let bin_data: Option<Vec<u8>> = conn.get(&key)?;
+    let key = components.redis_key_provider.game_states(game_id);
+    let version_key = components.redis_key_provider.game_state_versions(game_id);
+    let (bin_data, version): (Option<Vec<u8>>, Option<u64>) = conn.get(&[&key, &version_key])?;
     Ok(match bin_data {
         Some(b) => {
             let r = GameState::from(&b)?;
             // Touch TTL whenever you get the record
             conn.expire(key, EXPIRY_SECS)?;
-            Some(r)
+            conn.expire(version_key, EXPIRY_SECS)?;
+            Some((r, version.unwrap_or(0)))
         }
         None => None,
     })
 }
 
+/// Writes the game state, provided that nobody has written
+/// it since the given version was fetched.  Records which
+/// were never written are at version zero.  Gives the new
+/// version, or `WriteErr::Conflict` for the caller to retry.
 pub fn write(
     game_id: &GameId,
     game_state: &GameState,
+    version: u64,
     components: &Components,
-) -> Result<String, WriteErr> {
+) -> Result<u64, WriteErr> {
     let mut conn = components.client.get_connection().expect("write conn");
 
     let key = components.redis_key_provider.game_states(game_id);
-    let done = conn.set(&key, game_state.serialize()?)?;
+    let version_key = components.redis_key_provider.game_state_versions(game_id);
+    let bytes = game_state.serialize()?;
+    redis::cmd("WATCH")
+        .arg(&version_key)
+        .query::<()>(&mut conn)?;
+    let current: Option<u64> = conn.get(&version_key)?;
+    if current.unwrap_or(0) != version {
+        redis::cmd("UNWATCH").query::<()>(&mut conn)?;
+        return Err(WriteErr::Conflict);
+    }
     // Touch TTL whenever you set the record
-    conn.expire(key, EXPIRY_SECS)?;
-
-    Ok(done)
+    let done: Option<()> = redis::pipe()
+        .atomic()
+        .set_ex(&key, bytes, EXPIRY_SECS)
+        .ignore()
+        .set_ex(&version_key, version + 1, EXPIRY_SECS)
+        .ignore()
+        .query(&mut conn)?;
+    match done {
+        Some(()) => Ok(version + 1),
+        None => Err(WriteErr::Conflict),
+    }
 }
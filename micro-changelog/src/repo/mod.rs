@@ -9,6 +9,9 @@ pub enum WriteErr {
     Redis(RedisError),
     Serialization(std::boxed::Box<bincode::ErrorKind>),
     EIDRepo,
+    /// The record was written by someone else since it was
+    /// fetched.  Fetch it again and retry.
+    Conflict,
 }
 impl From<Box<bincode::ErrorKind>> for WriteErr {
     fn from(ek: Box<bincode::ErrorKind>) -> Self {
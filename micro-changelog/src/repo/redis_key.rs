@@ -21,6 +21,13 @@ impl KeyProvider {
     pub fn game_states(&self, game_id: &GameId) -> String {
         format!("/{}/micro_changelog/game_states/{}", (self.0).0, game_id.0)
     }
+    pub fn game_state_versions(&self, game_id: &GameId) -> String {
+        format!(
+            "/{}/micro_changelog/game_state_versions/{}",
+            (self.0).0,
+            game_id.0
+        )
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -24,8 +24,12 @@ pub fn process(topics: StreamTopics, components: &crate::Components) {
                         (entry_id, StreamData::MA(move_acc)) => {
                             info!("Stream: Move Accepted {:?}", &move_acc);
                             match update_game_state(&move_acc, &components) {
-                                Err(e) => error!("err updating game state {:?}", e),
-                                Ok(gs) => {
+                                Err(e) => log_save_err(e),
+                                Ok(None) => {
+                                    warn!("Dropping duplicate move {:?}", &move_acc.event_id);
+                                    ma_processed.push(entry_id);
+                                }
+                                Ok(Some(gs)) => {
                                     // These next two ops are concurrent in the kafka impl
                                     if let Err(e) = xadd_game_states_changelog(
                                         gs,
@@ -51,8 +55,10 @@ pub fn process(topics: StreamTopics, components: &crate::Components) {
                             } else {
                                 info!("Stream: Game State (trimmed)");
                             }
-                            if let Err(e) = game_states_repo::write(&gs.game_id, &gs, &components) {
-                                error!("Error saving game state {:#?}", e)
+                            match save_game_state(&gs, components) {
+                                Ok(true) => (),
+                                Ok(false) => info!("Ignoring stale game state {:?}", &gs.game_id),
+                                Err(e) => log_save_err(e),
                             }
 
                             gs_processed.push(entry_id);
@@ -90,49 +96,55 @@ pub fn process(topics: StreamTopics, components: &crate::Components) {
     }
 }
 
+/// Applies the move to the saved game state.  Moves which
+/// were already applied leave the game alone, and give None.
 fn update_game_state(
     move_acc: &MoveMade,
     components: &Components,
-) -> Result<GameState, GameStateSaveErr> {
+) -> Result<Option<GameState>, GameStateSaveErr> {
     let game_id = move_acc.game_id.clone();
-    let old_game_state = game_states_repo::fetch(&move_acc.game_id, &components);
-    let new_game_state = old_game_state.map(|maybe_og| {
-        let mut orig = maybe_og.unwrap_or(GameState {
-            game_id: move_acc.game_id.clone(),
-            board: Board::default(),
-            captures: Captures::default(),
-            turn: 1,
-            moves: vec![],
-            player_up: Player::BLACK,
-        });
-        orig.turn += 1;
-        orig.player_up = match move_acc.player {
-            Player::BLACK => Player::WHITE,
-            _ => Player::BLACK,
-        };
-        let caps = move_acc.captured.len() as u16;
-        match move_acc.player {
-            Player::BLACK => orig.captures.black += caps,
-            Player::WHITE => orig.captures.white += caps,
-        }
-        for c in &move_acc.captured {
-            orig.board.pieces.remove(c);
+    loop {
+        let (mut new_game_state, version) = game_states_repo::fetch(&game_id, components)?
+            .unwrap_or_else(|| {
+                let started =
+                    GameState::new(game_id.clone(), BoardSize::default(), Rules::default(), 0);
+                (started, 0)
+            });
+        if new_game_state.has_played(move_acc) {
+            return Ok(None);
         }
-        if let Some(c) = &move_acc.coord {
-            orig.board.pieces.insert(*c, move_acc.player);
+        new_game_state.apply(move_acc)?;
+        match game_states_repo::write(&game_id, &new_game_state, version, components) {
+            Err(WriteErr::Conflict) => warn!("Retrying move {:?}", &move_acc.event_id),
+            Err(e) => return Err(e.into()),
+            Ok(_) => return Ok(Some(new_game_state)),
         }
+    }
+}
 
-        orig.moves.push(move_acc.clone());
-        orig
-    })?;
-    game_states_repo::write(&game_id, &new_game_state, &components)?;
-    Ok(new_game_state)
+/// Saves a game state published to the changelog, unless
+/// we already hold the same or a later revision of it.
+/// Gives false for a stale state.
+fn save_game_state(gs: &GameState, components: &Components) -> Result<bool, GameStateSaveErr> {
+    loop {
+        let version = match game_states_repo::fetch(&gs.game_id, components)? {
+            Some((held, _)) if held.revision >= gs.revision => return Ok(false),
+            Some((_, version)) => version,
+            None => 0,
+        };
+        match game_states_repo::write(&gs.game_id, gs, version, components) {
+            Err(WriteErr::Conflict) => warn!("Retrying write of {:?}", &gs.game_id),
+            Err(e) => return Err(e.into()),
+            Ok(_) => return Ok(true),
+        }
+    }
 }
 
 #[derive(Debug)]
 enum GameStateSaveErr {
     W(WriteErr),
     F(FetchErr),
+    A(ApplyErr),
 }
 impl From<WriteErr> for GameStateSaveErr {
     fn from(w: WriteErr) -> Self {
@@ -144,6 +156,19 @@ impl From<FetchErr> for GameStateSaveErr {
         GameStateSaveErr::F(f)
     }
 }
+impl From<ApplyErr> for GameStateSaveErr {
+    fn from(a: ApplyErr) -> Self {
+        GameStateSaveErr::A(a)
+    }
+}
+
+fn log_save_err(e: GameStateSaveErr) {
+    match e {
+        GameStateSaveErr::W(w) => error!("could not write game state {:?}", w),
+        GameStateSaveErr::F(f) => error!("could not fetch game state {:?}", f),
+        GameStateSaveErr::A(a) => error!("could not apply move {:?}", a),
+    }
+}
 
 fn xadd_move_made(
     mm: &MoveMade,
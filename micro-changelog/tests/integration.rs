@@ -106,14 +106,17 @@ fn test_process_move() {
     assert_eq!(payload.0, "data");
     let expected_game_state = GameState {
         game_id: game_id.clone(),
-        board: Board {
-            pieces: [(placement, Player::BLACK)].iter().cloned().collect(),
-            size: 19,
-        },
+        board: [(placement, Player::BLACK)].iter().cloned().collect(),
         moves: vec![move_made],
         turn: 2,
         player_up: Player::WHITE,
         captures: Captures { black: 0, white: 0 },
+        ko: None,
+        rules: Rules::default(),
+        game_over: false,
+        result: None,
+        handicap: vec![],
+        revision: 1,
     };
     assert_eq!(
         bincode::deserialize::<GameState>(&payload.1.as_ref().unwrap()).unwrap(),
@@ -127,6 +130,63 @@ fn test_process_move() {
     clean_keys(keys_to_clean, &client);
 }
 
+/// A write against a version which someone else has since
+/// replaced is refused, and succeeds once fetched again
+#[test]
+fn test_write_conflict() {
+    let components = test_components();
+    let game_id = GameId(uuid::Uuid::new_v4());
+    let keys_to_clean = vec![
+        components.redis_key_provider.game_states(&game_id),
+        components.redis_key_provider.game_state_versions(&game_id),
+    ];
+    panic_cleanup(vec![], keys_to_clean.clone());
+
+    let started = GameState::new(game_id.clone(), BoardSize::default(), Rules::default(), 0);
+    assert_eq!(
+        repo::game_states_repo::write(&game_id, &started, 0, &components).unwrap(),
+        1
+    );
+    let (fetched, version) = repo::game_states_repo::fetch(&game_id, &components)
+        .unwrap()
+        .unwrap();
+    assert_eq!((&fetched, version), (&started, 1));
+
+    // another consumer gets there first
+    let newer = GameState {
+        revision: 1,
+        ..started.clone()
+    };
+    assert_eq!(
+        repo::game_states_repo::write(&game_id, &newer, version, &components).unwrap(),
+        2
+    );
+    let stale = GameState {
+        revision: 1,
+        ..fetched
+    };
+    match repo::game_states_repo::write(&game_id, &stale, version, &components) {
+        Err(repo::WriteErr::Conflict) => (),
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+
+    // the retry sees the newer copy
+    let (fetched, version) = repo::game_states_repo::fetch(&game_id, &components)
+        .unwrap()
+        .unwrap();
+    assert_eq!((&fetched, version), (&newer, 2));
+    let next = GameState {
+        revision: 2,
+        ..fetched
+    };
+    assert_eq!(
+        repo::game_states_repo::write(&game_id, &next, version, &components).unwrap(),
+        3
+    );
+
+    clean_keys(keys_to_clean, &redis_client());
+}
+
 fn panic_cleanup(stream_names: Vec<String>, keys: Vec<String>) {
     let client = redis_client();
     std::panic::set_hook(Box::new(move |e| {
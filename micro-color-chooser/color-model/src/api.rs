@@ -4,6 +4,7 @@ use serde_derive::{Deserialize, Serialize};
 
 /// emitted by the game lobby
 pub use lobby_model::api::GameReady;
+pub use lobby_model::BoardSize;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ChooseColorPref {
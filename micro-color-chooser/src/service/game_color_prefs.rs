@@ -156,7 +156,7 @@ mod tests {
             color_pref: ColorPref::Black,
             client_id: another_cid.clone(),
         };
-        let board_size = 9;
+        let board_size = api::BoardSize::square(9);
 
         let repos = Repos {
             prefs: Rc::new(PrefsTwo(one_pref.clone(), another_pref.clone())),
@@ -188,7 +188,7 @@ mod tests {
             color_pref: ColorPref::Black,
             client_id: cid.clone(),
         };
-        let board_size = 9;
+        let board_size = api::BoardSize::square(9);
         let repos = Repos {
             prefs: Rc::new(PrefsOne(pref.clone())),
             game_ready: Rc::new(SGReady(GameReady {
@@ -227,7 +227,7 @@ mod tests {
         let another_sid = SessionId::new();
         let sessions = (sid.clone(), another_sid.clone());
 
-        let board_size = 9;
+        let board_size = api::BoardSize::square(9);
         let game_ready = GameReady {
             sessions: sessions.clone(),
             game_id: gid.clone(),
@@ -267,7 +267,7 @@ mod tests {
 
         let sessions = (sid.clone(), SessionId::new());
 
-        let board_size = 9;
+        let board_size = api::BoardSize::square(9);
         let game_ready = GameReady {
             sessions: sessions.clone(),
             game_id: gid.clone(),
@@ -296,7 +296,7 @@ mod tests {
 
         let sessions = (sid, SessionId::new());
 
-        let board_size = 9;
+        let board_size = api::BoardSize::square(9);
         let game_ready = GameReady {
             sessions: sessions.clone(),
             game_id: gid,
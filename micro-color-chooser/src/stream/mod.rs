@@ -336,7 +336,7 @@ mod tests {
             color_pref: ColorPref::Black,
         });
 
-        let board_size = 9;
+        let board_size = BoardSize::square(9);
         let game_ready = StreamInput::GR(GameReady {
             game_id,
             sessions,
@@ -368,7 +368,7 @@ mod tests {
             color_pref: ColorPref::Black,
         });
 
-        let board_size = 9;
+        let board_size = BoardSize::square(9);
         let game_ready = StreamInput::GR(GameReady {
             game_id,
             sessions,
@@ -417,7 +417,7 @@ mod tests {
             color_pref: ColorPref::White,
         });
         let game_id = GameId::new();
-        let board_size = 9;
+        let board_size = BoardSize::square(9);
         let game_ready = StreamInput::GR(GameReady {
             game_id,
             sessions,
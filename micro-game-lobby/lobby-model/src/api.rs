@@ -1,5 +1,6 @@
 use crate::*;
 use core_model::*;
+use move_model::{Coord, Rules};
 use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -15,7 +16,12 @@ pub struct CreateGame {
     pub visibility: Visibility,
     pub game_id: Option<GameId>,
     pub session_id: SessionId,
-    pub board_size: u16,
+    pub board_size: BoardSize,
+    pub rules: Rules,
+    /// Black's handicap stones, if any
+    pub handicap: Vec<Coord>,
+    /// Rated games never allow an undo
+    pub rated: bool,
 }
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct JoinPrivateGame {
@@ -38,7 +44,7 @@ pub struct GameReady {
     pub game_id: GameId,
     pub sessions: (SessionId, SessionId),
     pub event_id: EventId,
-    pub board_size: u16,
+    pub board_size: BoardSize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -3,6 +3,8 @@ extern crate serde_derive;
 pub mod api;
 
 use core_model::*;
+pub use move_model::BoardSize;
+use move_model::{Coord, Rules};
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashSet;
 
@@ -11,7 +13,9 @@ pub struct Game {
     pub game_id: GameId,
     pub visibility: Visibility,
     pub creator: SessionId,
-    pub board_size: u16,
+    pub board_size: BoardSize,
+    pub rules: Rules,
+    pub handicap: Vec<Coord>,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -42,6 +42,7 @@ impl AsBytes for GameLobby {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use move_model::Rules;
 
     #[test]
     fn lobby_as_bytes() {
@@ -49,9 +50,11 @@ mod tests {
         assert!(lobby.as_bytes().is_ok());
         let next = lobby.open(Game {
             game_id: GameId::new(),
-            board_size: 3,
+            board_size: BoardSize::square(3),
             creator: SessionId::new(),
             visibility: Visibility::Private,
+            rules: Rules::default(),
+            handicap: vec![],
         });
         assert!(!next.games.is_empty());
         assert!(next.as_bytes().is_ok());
@@ -63,16 +66,20 @@ mod tests {
 
         let one = lobby.open(Game {
             game_id: GameId::new(),
-            board_size: 19,
+            board_size: BoardSize::square(19),
             creator: SessionId::new(),
             visibility: Visibility::Public,
+            rules: Rules::default(),
+            handicap: vec![],
         });
         assert_eq!(one.games.len(), 1);
         let two = one.open(Game {
             game_id: GameId::new(),
-            board_size: 13,
+            board_size: BoardSize::square(13),
             creator: SessionId::new(),
             visibility: Visibility::Private,
+            rules: Rules::default(),
+            handicap: vec![],
         });
         assert_eq!(two.games.len(), 2)
     }
@@ -83,9 +90,11 @@ mod tests {
 
         let game = Game {
             game_id: GameId::new(),
-            board_size: 19,
+            board_size: BoardSize::square(19),
             creator: SessionId::new(),
             visibility: Visibility::Public,
+            rules: Rules::default(),
+            handicap: vec![],
         };
 
         let one = lobby.open(game.clone());
@@ -102,9 +111,11 @@ mod tests {
         let creator = sid.clone();
         let one = lobby.open(Game {
             game_id: GameId::new(),
-            board_size: 19,
+            board_size: BoardSize::square(19),
             creator,
             visibility: Visibility::Public,
+            rules: Rules::default(),
+            handicap: vec![],
         });
         assert_eq!(one.games.len(), 1);
         let done = one.abandon(&sid);
@@ -7,3 +7,6 @@ pub mod topics;
 pub use components::*;
 
 pub const PUBLIC_GAME_BOARD_SIZE: u16 = 19;
+/// Nobody may take moves back in a rated game,
+/// whatever its creator asked for
+pub const RATED_GAME_UNDO_POLICY: move_model::UndoPolicy = move_model::UndoPolicy::Disabled;
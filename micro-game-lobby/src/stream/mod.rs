@@ -6,12 +6,12 @@ pub use xadd::*;
 
 use crate::components::Components;
 use crate::game_lobby::GameLobbyOps;
-use crate::PUBLIC_GAME_BOARD_SIZE;
+use crate::{PUBLIC_GAME_BOARD_SIZE, RATED_GAME_UNDO_POLICY};
 use core_model::*;
 use lobby_model::api::*;
 use lobby_model::*;
 use log::{error, trace};
-use move_model::GameState;
+use move_model::{GameState, Rules};
 use redis_streams::Message;
 
 pub const GROUP_NAME: &str = "micro-game-lobby";
@@ -71,7 +71,9 @@ impl LobbyStreams {
                 } else {
                     let game_id = GameId::new();
                     let updated: GameLobby = lobby.open(Game {
-                        board_size: PUBLIC_GAME_BOARD_SIZE,
+                        board_size: BoardSize::square(PUBLIC_GAME_BOARD_SIZE),
+                        rules: Rules::default(),
+                        handicap: vec![],
                         creator: session_id.clone(),
                         visibility,
                         game_id: game_id.clone(),
@@ -110,6 +112,8 @@ impl LobbyStreams {
                 let updated: GameLobby = lobby.open(Game {
                     game_id: game_id.clone(),
                     board_size: cg.board_size,
+                    rules: rules_for(cg.rated, cg.rules),
+                    handicap: cg.handicap.clone(),
                     creator: session_id.clone(),
                     visibility: cg.visibility,
                 });
@@ -191,6 +195,19 @@ impl LobbyStreams {
         })
     }
 }
+/// The client chooses the rules of a game, but the undo
+/// policy of a rated one is fixed here
+fn rules_for(rated: bool, rules: Rules) -> Rules {
+    if rated {
+        Rules {
+            undo: RATED_GAME_UNDO_POLICY,
+            ..rules
+        }
+    } else {
+        rules
+    }
+}
+
 fn ready_game(session_id: &SessionId, lobby: &GameLobby, queued: &Game, reg: &Components) {
     let updated: GameLobby = lobby.ready(queued);
     if let Err(_) = reg.game_lobby_repo.put(&updated) {
@@ -205,23 +222,18 @@ fn ready_game(session_id: &SessionId, lobby: &GameLobby, queued: &Game, reg: &Co
             error!("XADD Game ready")
         } else {
             trace!("Game ready. Lobby: {:?}", &updated);
-            init_changelog(&queued.game_id, queued.board_size, &reg)
+            init_changelog(queued, reg)
         }
     }
 }
 
-fn init_changelog(game_id: &GameId, board_size: u16, reg: &Components) {
-    if let Err(chgerr) = reg.xadd.xadd(StreamOutput::LOG(GameState {
-        game_id: game_id.clone(),
-        board: move_model::Board {
-            size: board_size,
-            ..Default::default()
-        },
-        captures: move_model::Captures::default(),
-        turn: 1,
-        moves: vec![],
-        player_up: move_model::Player::BLACK,
-    })) {
+fn init_changelog(game: &Game, reg: &Components) {
+    if let Err(chgerr) = reg.xadd.xadd(StreamOutput::LOG(GameState::starting(
+        game.game_id.clone(),
+        game.board_size,
+        game.rules,
+        game.handicap.clone(),
+    ))) {
         error!("could not write game state changelog {:?}", chgerr)
     }
 }
@@ -434,6 +446,30 @@ mod test {
             },
             default(timeout) => panic!("GR time out")
         }
+
+        // Public games keep the default undo policy
+        select! {
+            recv(xadd_out) -> msg => match msg {
+                Ok(StreamOutput::LOG(game_state)) => {
+                    assert_eq!(game_state.rules, Rules::default())
+                }
+                _ => panic!("wrong output")
+            },
+            default(timeout) => panic!("LOG time out")
+        }
+    }
+
+    #[test]
+    fn test_rated_rules_ignore_undo_policy() {
+        let rules = Rules {
+            undo: move_model::UndoPolicy::Unlimited,
+            ..Rules::default()
+        };
+        assert_eq!(
+            rules_for(true, rules).undo,
+            move_model::UndoPolicy::Disabled
+        );
+        assert_eq!(rules_for(false, rules), rules)
     }
 
     fn quick_xid(ms: u64) -> XId {
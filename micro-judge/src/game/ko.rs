@@ -0,0 +1,192 @@
+use super::removed_by;
+use log::error;
+use move_model::*;
+
+/// Checks whether placing a stone violates the ko rule
+/// in effect for this game.  The simple ko point recorded
+/// by changelog is always forbidden.  Under positional
+/// superko, we also replay the game's history and forbid
+/// any move which recreates an earlier board position.
+/// If the history can't be replayed, the move is refused.
+pub fn violates_ko(player: Player, placement: Coord, game_state: &GameState) -> bool {
+    if game_state.ko == Some(placement) {
+        return true;
+    }
+
+    match game_state.rules.ko {
+        KoRule::Simple => false,
+        KoRule::PositionalSuperko => repeats_position(player, placement, game_state),
+    }
+}
+
+fn repeats_position(player: Player, placement: Coord, game_state: &GameState) -> bool {
+    let mut next = game_state.board.clone();
+    next.place(placement, player);
+    for c in removed_by(player, placement, &game_state.board) {
+        next.remove(c);
+    }
+
+    let repeats = |position: &Board| position.zobrist() == next.zobrist() && *position == next;
+    let mut replay = game_state.restart();
+    if repeats(&replay.board) {
+        return true;
+    }
+    for m in &game_state.moves {
+        if let Err(e) = replay.apply(m) {
+            error!(
+                "superko: could not replay {:?}: {:?}",
+                game_state.game_id, e
+            );
+            return true;
+        }
+        if repeats(&replay.board) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::captures_for;
+    use core_model::*;
+
+    fn play(gs: &mut GameState, player: Player, coord: Coord) {
+        let captured: Vec<Coord> = captures_for(player, coord, &gs.board)
+            .iter()
+            .cloned()
+            .collect();
+        let mm = MoveMade {
+            game_id: gs.game_id.clone(),
+            reply_to: ReqId::new(),
+            event_id: EventId::new(),
+            player,
+            coord: Some(coord),
+            captured,
+        };
+        gs.apply(&mm).unwrap()
+    }
+
+    ///  . B W .
+    ///  B W . W
+    ///  . B W .
+    fn ko_shape(rules: Rules) -> GameState {
+        let mut gs = GameState {
+            game_id: GameId::new(),
+            board: Board::default(),
+            captures: Captures::default(),
+            turn: 1,
+            player_up: Player::BLACK,
+            moves: vec![],
+            ko: None,
+            rules,
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
+        };
+        play(&mut gs, Player::BLACK, Coord::of(1, 0));
+        play(&mut gs, Player::WHITE, Coord::of(2, 0));
+        play(&mut gs, Player::BLACK, Coord::of(0, 1));
+        play(&mut gs, Player::WHITE, Coord::of(3, 1));
+        play(&mut gs, Player::BLACK, Coord::of(1, 2));
+        play(&mut gs, Player::WHITE, Coord::of(2, 2));
+        play(&mut gs, Player::BLACK, Coord::of(10, 10));
+        play(&mut gs, Player::WHITE, Coord::of(1, 1));
+        gs
+    }
+
+    #[test]
+    fn simple_ko_forbids_immediate_recapture() {
+        let mut gs = ko_shape(Rules::default());
+        play(&mut gs, Player::BLACK, Coord::of(2, 1));
+        assert_eq!(gs.ko, Some(Coord::of(1, 1)));
+        assert!(violates_ko(Player::WHITE, Coord::of(1, 1), &gs))
+    }
+
+    #[test]
+    fn simple_ko_allows_recapture_after_threat() {
+        let mut gs = ko_shape(Rules::default());
+        play(&mut gs, Player::BLACK, Coord::of(2, 1));
+        play(&mut gs, Player::WHITE, Coord::of(15, 15));
+        play(&mut gs, Player::BLACK, Coord::of(15, 16));
+        assert!(!violates_ko(Player::WHITE, Coord::of(1, 1), &gs))
+    }
+
+    /// Three kos like the one above, stacked down the left
+    /// edge.  White holds the first and third, black the
+    /// second.
+    fn triple_ko(rules: Rules) -> GameState {
+        let mut gs = GameState::new(GameId::new(), BoardSize::default(), rules, 0);
+        let mut black = vec![];
+        let mut white = vec![];
+        for y in &[0, 5, 10] {
+            black.extend(vec![
+                Coord::of(1, *y),
+                Coord::of(0, y + 1),
+                Coord::of(1, y + 2),
+            ]);
+            white.extend(vec![
+                Coord::of(2, *y),
+                Coord::of(3, y + 1),
+                Coord::of(2, y + 2),
+            ]);
+        }
+        black.extend(vec![Coord::of(2, 6), Coord::of(10, 10)]);
+        white.extend(vec![Coord::of(1, 1), Coord::of(1, 11)]);
+        for (b, w) in black.into_iter().zip(white) {
+            play(&mut gs, Player::BLACK, b);
+            play(&mut gs, Player::WHITE, w);
+        }
+        gs
+    }
+
+    /// Each side takes a ko in turn, so no retake is
+    /// immediate, until white's last capture would bring
+    /// back the position the cycle started from
+    fn cycle_triple_ko(gs: &mut GameState) {
+        play(gs, Player::BLACK, Coord::of(2, 1));
+        play(gs, Player::WHITE, Coord::of(1, 6));
+        play(gs, Player::BLACK, Coord::of(2, 11));
+        play(gs, Player::WHITE, Coord::of(1, 1));
+        play(gs, Player::BLACK, Coord::of(2, 6));
+        assert_eq!(gs.ko, Some(Coord::of(1, 6)));
+    }
+
+    #[test]
+    fn superko_forbids_repeated_position() {
+        let mut gs = triple_ko(Rules {
+            ko: KoRule::PositionalSuperko,
+            ..Rules::default()
+        });
+        cycle_triple_ko(&mut gs);
+        assert!(violates_ko(Player::WHITE, Coord::of(1, 11), &gs))
+    }
+
+    #[test]
+    fn simple_ko_allows_triple_ko_cycle() {
+        let mut gs = triple_ko(Rules::default());
+        cycle_triple_ko(&mut gs);
+        assert!(!violates_ko(Player::WHITE, Coord::of(1, 11), &gs))
+    }
+
+    #[test]
+    fn superko_refuses_moves_when_history_is_broken() {
+        let mut gs = ko_shape(Rules {
+            ko: KoRule::PositionalSuperko,
+            ..Rules::default()
+        });
+        gs.moves[0].captured = vec![Coord::of(5, 5)];
+        assert!(violates_ko(Player::BLACK, Coord::of(2, 1), &gs))
+    }
+
+    #[test]
+    fn superko_allows_new_position() {
+        let gs = ko_shape(Rules {
+            ko: KoRule::PositionalSuperko,
+            ..Rules::default()
+        });
+        assert!(!violates_ko(Player::BLACK, Coord::of(2, 1), &gs))
+    }
+}
@@ -0,0 +1,122 @@
+use super::{final_score, score_result};
+use core_model::EventId;
+use move_model::*;
+
+/// Marks the whole group at `coord` as dead, or as alive if it
+/// was already marked.  Any acceptances are cleared, since the
+/// score has changed.  Returns false if there is no stone there.
+pub fn toggle_dead(marking: &mut Marking, coord: Coord, board: &Board) -> bool {
+    let group = connected(coord, board);
+    if group.is_empty() {
+        return false;
+    }
+    if marking.dead.contains(&coord) {
+        marking.dead.retain(|c| !group.contains(c))
+    } else {
+        marking.dead.extend(group);
+        marking.dead.sort_by_key(|c| (c.x, c.y))
+    }
+    marking.accepted.clear();
+    true
+}
+
+/// Records a player's acceptance.  The marking is
+/// finalized once both players have accepted.
+pub fn accept(marking: &mut Marking, player: Player) {
+    if !marking.accepted.contains(&player) {
+        marking.accepted.push(player)
+    }
+    marking.finalized =
+        marking.accepted.contains(&Player::BLACK) && marking.accepted.contains(&Player::WHITE)
+}
+
+pub fn dead_stones_marked(marking: &Marking, game_state: &GameState) -> DeadStonesMarked {
+    DeadStonesMarked {
+        game_id: marking.game_id.clone(),
+        event_id: EventId::new(),
+        dead: marking.dead.clone(),
+        accepted: marking.accepted.clone(),
+        score: final_score(game_state, &marking.dead),
+    }
+}
+
+pub fn score_finalized(marking: &Marking, game_state: &GameState) -> ScoreFinalized {
+    let score = final_score(game_state, &marking.dead);
+    ScoreFinalized {
+        game_id: marking.game_id.clone(),
+        event_id: EventId::new(),
+        dead: marking.dead.clone(),
+        result: score_result(&score, &game_state.rules),
+        score,
+    }
+}
+
+/// The finished game with its result, as a new revision
+/// so that it replaces the state which changelog holds
+pub fn scored_state(finalized: &ScoreFinalized, game_state: &GameState) -> GameState {
+    GameState {
+        result: Some(finalized.result),
+        revision: game_state.revision + 1,
+        ..game_state.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_model::GameId;
+
+    fn board() -> Board {
+        [
+            (Coord::of(0, 0), Player::WHITE),
+            (Coord::of(1, 0), Player::WHITE),
+            (Coord::of(5, 5), Player::BLACK),
+        ]
+        .iter()
+        .cloned()
+        .collect()
+    }
+
+    #[test]
+    fn toggle_whole_group() {
+        let mut marking = Marking::new(GameId::new());
+        assert!(toggle_dead(&mut marking, Coord::of(1, 0), &board()));
+        assert_eq!(marking.dead, vec![Coord::of(0, 0), Coord::of(1, 0)]);
+        assert!(toggle_dead(&mut marking, Coord::of(0, 0), &board()));
+        assert!(marking.dead.is_empty());
+        assert!(!toggle_dead(&mut marking, Coord::of(9, 9), &board()));
+    }
+
+    #[test]
+    fn toggle_clears_acceptance() {
+        let mut marking = Marking::new(GameId::new());
+        accept(&mut marking, Player::BLACK);
+        toggle_dead(&mut marking, Coord::of(5, 5), &board());
+        assert!(marking.accepted.is_empty());
+        accept(&mut marking, Player::WHITE);
+        assert!(!marking.finalized);
+        accept(&mut marking, Player::BLACK);
+        assert!(marking.finalized);
+    }
+
+    #[test]
+    fn scored_state_replaces_the_one_held() {
+        let mut game_state =
+            GameState::new(GameId::new(), BoardSize::square(9), Rules::default(), 0);
+        game_state.board = board();
+        game_state.game_over = true;
+        game_state.revision = 3;
+        let mut marking = Marking::new(game_state.game_id.clone());
+        toggle_dead(&mut marking, Coord::of(5, 5), &game_state.board);
+        accept(&mut marking, Player::BLACK);
+        accept(&mut marking, Player::WHITE);
+
+        let finalized = score_finalized(&marking, &game_state);
+        let scored = scored_state(&finalized, &game_state);
+        assert_eq!(scored.result, Some(finalized.result));
+        assert_eq!(finalized.result.winner, Some(Player::WHITE));
+        // changelog refuses any state no newer than its own
+        assert!(scored.revision > game_state.revision);
+        assert_eq!(scored.board, game_state.board)
+    }
+}
@@ -1,22 +1,32 @@
-pub use capturing::captures_for;
 use core_model::EventId;
+pub use marking::*;
 use move_model::*;
+pub use move_model::{captures_for, removed_by, suicide_for};
 
+use ko::violates_ko;
 use log::info;
+use std::collections::HashSet;
 
-mod capturing;
+mod ko;
+mod marking;
 
+#[derive(Debug)]
 pub enum Judgement {
     Accepted(MoveMade),
-    Rejected,
+    /// The move is accepted, and it meets the end
+    /// condition of the game's variant
+    Decisive(MoveMade, GameResult),
+    Rejected(RejectReason),
 }
 pub fn judge(mm: &MakeMove, game_state: &GameState) -> Judgement {
     info!("Judge {:?}", mm);
-    if validate_move(mm, game_state) {
+    if let Err(reason) = validate_move(mm, game_state) {
+        Judgement::Rejected(reason)
+    } else {
         let captured: Vec<Coord> = mm
             .coord
             .map(|c| {
-                captures_for(mm.player, c, &game_state.board)
+                removed_by(mm.player, c, &game_state.board)
                     .iter()
                     .cloned()
                     .collect()
@@ -31,31 +41,287 @@ pub fn judge(mm: &MakeMove, game_state: &GameState) -> Judgement {
             game_id: mm.game_id.clone(),
             reply_to: mm.req_id.clone(),
         };
-        Judgement::Accepted(move_made)
-    } else {
-        Judgement::Rejected
-    }
-}
-
-fn validate_move(make_move: &MakeMove, game_state: &GameState) -> bool {
-    let correct_player = make_move.player == game_state.player_up;
-    let coord = make_move.coord;
-    let passing = coord.is_none();
-    let coord_exists = || {
-        if let Some(c) = coord {
-            let size = game_state.board.size;
-            size > c.x && size > c.y
-        } else {
-            false
-        }
-    };
-    let valid_coord = || {
-        coord_exists()
-            && game_state
-                .board
-                .pieces
-                .get(&make_move.coord.unwrap())
-                .is_none()
-    };
-    correct_player && (passing || valid_coord())
+        match game_state.rules.variant.decides(&move_made) {
+            Some(result) => Judgement::Decisive(move_made, result),
+            None => Judgement::Accepted(move_made),
+        }
+    }
+}
+
+/// A pass which answers the opponent's pass ends the game
+pub fn ends_game(move_made: &MoveMade, game_state: &GameState) -> bool {
+    move_made.coord.is_none()
+        && game_state
+            .moves
+            .last()
+            .map(|m| m.coord.is_none())
+            .unwrap_or(false)
+}
+
+/// Counts the board as it stood when the game ended,
+/// taking off any stones marked dead
+pub fn final_score(game_state: &GameState, dead: &[Coord]) -> FinalScore {
+    let dead: HashSet<Coord> = dead.iter().cloned().collect();
+    score(
+        &game_state.board,
+        &game_state.captures,
+        &dead,
+        game_state.rules.komi.0,
+    )
+}
+
+/// The winner under the game's scoring rule.  Final once
+/// both players accept the dead stones.
+pub fn score_result(score: &FinalScore, rules: &Rules) -> GameResult {
+    GameResult {
+        winner: score.counted(rules.scoring).winner,
+        reason: EndReason::Score,
+    }
+}
+
+/// The opponent of the resigning player wins
+pub fn resignation(resign: &Resign) -> GameResult {
+    GameResult {
+        winner: Some(match resign.player {
+            Player::BLACK => Player::WHITE,
+            Player::WHITE => Player::BLACK,
+        }),
+        reason: EndReason::Resignation,
+    }
+}
+
+fn validate_move(make_move: &MakeMove, game_state: &GameState) -> Result<(), RejectReason> {
+    if game_state.game_over {
+        return Err(RejectReason::GameOver);
+    }
+    if make_move.player != game_state.player_up {
+        return Err(RejectReason::WrongPlayer);
+    }
+    if let Some(c) = make_move.coord {
+        if !game_state.board.size.contains(c) {
+            return Err(RejectReason::OutOfBounds);
+        }
+        if game_state.board.get(c).is_some() {
+            return Err(RejectReason::Occupied);
+        }
+        if violates_ko(make_move.player, c, game_state) {
+            return Err(RejectReason::Ko);
+        }
+        if illegal_suicide(make_move.player, c, game_state) {
+            return Err(RejectReason::Suicide);
+        }
+    }
+    Ok(())
+}
+
+fn illegal_suicide(player: Player, placement: Coord, game_state: &GameState) -> bool {
+    let suicided = suicide_for(player, placement, &game_state.board);
+    match game_state.rules.suicide {
+        SuicideRule::Forbidden => !suicided.is_empty(),
+        SuicideRule::MultiStoneAllowed => suicided.len() == 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_model::*;
+
+    /// White's corner group at (0,0) and (1,0) would
+    /// be left without liberties by a move at (1,0)
+    fn corner(suicide: SuicideRule) -> GameState {
+        let board = [
+            (Coord::of(0, 0), Player::WHITE),
+            (Coord::of(2, 0), Player::BLACK),
+            (Coord::of(0, 1), Player::BLACK),
+            (Coord::of(1, 1), Player::BLACK),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        GameState {
+            game_id: GameId::new(),
+            board,
+            captures: Captures::default(),
+            turn: 5,
+            player_up: Player::WHITE,
+            moves: vec![],
+            ko: None,
+            rules: Rules {
+                suicide,
+                ..Rules::default()
+            },
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
+        }
+    }
+
+    fn make_move(gs: &GameState, coord: Coord) -> MakeMove {
+        MakeMove {
+            game_id: gs.game_id.clone(),
+            req_id: ReqId::new(),
+            player: gs.player_up,
+            coord: Some(coord),
+        }
+    }
+
+    #[test]
+    fn suicide_forbidden() {
+        let gs = corner(SuicideRule::Forbidden);
+        let mm = make_move(&gs, Coord::of(1, 0));
+        assert!(matches!(
+            judge(&mm, &gs),
+            Judgement::Rejected(RejectReason::Suicide)
+        ))
+    }
+
+    #[test]
+    fn multi_stone_suicide_allowed() {
+        let gs = corner(SuicideRule::MultiStoneAllowed);
+        let mm = make_move(&gs, Coord::of(1, 0));
+        match judge(&mm, &gs) {
+            Judgement::Accepted(move_made) => {
+                assert!(move_made.is_suicide());
+                assert_eq!(move_made.captured.len(), 2)
+            }
+            other => panic!("not accepted {:?}", other),
+        }
+    }
+
+    #[test]
+    fn single_stone_suicide_never_allowed() {
+        let mut gs = corner(SuicideRule::MultiStoneAllowed);
+        gs.board = [
+            (Coord::of(1, 0), Player::WHITE),
+            (Coord::of(0, 1), Player::WHITE),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        gs.player_up = Player::BLACK;
+        let mm = make_move(&gs, Coord::of(0, 0));
+        assert!(matches!(
+            judge(&mm, &gs),
+            Judgement::Rejected(RejectReason::Suicide)
+        ))
+    }
+
+    #[test]
+    fn wrong_player_rejected() {
+        let gs = corner(SuicideRule::Forbidden);
+        let mut mm = make_move(&gs, Coord::of(5, 5));
+        mm.player = Player::BLACK;
+        assert!(matches!(
+            judge(&mm, &gs),
+            Judgement::Rejected(RejectReason::WrongPlayer)
+        ))
+    }
+
+    #[test]
+    fn out_of_bounds_rejected() {
+        let gs = corner(SuicideRule::Forbidden);
+        let mm = make_move(&gs, Coord::of(19, 0));
+        assert!(matches!(
+            judge(&mm, &gs),
+            Judgement::Rejected(RejectReason::OutOfBounds)
+        ))
+    }
+
+    #[test]
+    fn occupied_rejected() {
+        let gs = corner(SuicideRule::Forbidden);
+        let mm = make_move(&gs, Coord::of(0, 0));
+        assert!(matches!(
+            judge(&mm, &gs),
+            Judgement::Rejected(RejectReason::Occupied)
+        ))
+    }
+
+    #[test]
+    fn pass_accepted() {
+        let gs = corner(SuicideRule::Forbidden);
+        let mut mm = make_move(&gs, Coord::of(0, 0));
+        mm.coord = None;
+        assert!(matches!(judge(&mm, &gs), Judgement::Accepted(_)))
+    }
+
+    #[test]
+    fn second_pass_ends_game() {
+        let mut gs = corner(SuicideRule::Forbidden);
+        let mut mm = make_move(&gs, Coord::of(0, 0));
+        mm.coord = None;
+        let first_pass = match judge(&mm, &gs) {
+            Judgement::Accepted(move_made) => move_made,
+            other => panic!("not accepted {:?}", other),
+        };
+        assert!(!ends_game(&first_pass, &gs));
+
+        gs.moves.push(first_pass.clone());
+        gs.player_up = Player::BLACK;
+        mm.player = Player::BLACK;
+        match judge(&mm, &gs) {
+            Judgement::Accepted(second_pass) => assert!(ends_game(&second_pass, &gs)),
+            other => panic!("not accepted {:?}", other),
+        }
+    }
+
+    #[test]
+    fn atari_go_capture_decides() {
+        let mut gs = corner(SuicideRule::Forbidden);
+        gs.rules.variant = Variant::AtariGo;
+        gs.player_up = Player::BLACK;
+        let mm = make_move(&gs, Coord::of(1, 0));
+        match judge(&mm, &gs) {
+            Judgement::Decisive(move_made, result) => {
+                assert_eq!(move_made.captured, vec![Coord::of(0, 0)]);
+                assert_eq!(result.winner, Some(Player::BLACK));
+                assert_eq!(result.reason, EndReason::FirstCapture)
+            }
+            other => panic!("not decisive {:?}", other),
+        }
+        let quiet = make_move(&gs, Coord::of(5, 5));
+        assert!(matches!(judge(&quiet, &gs), Judgement::Accepted(_)))
+    }
+
+    #[test]
+    fn game_rules_decide_score() {
+        let mut gs = corner(SuicideRule::Forbidden);
+        gs.rules.komi = Komi(0.5);
+        let score = final_score(&gs, &[]);
+        assert_eq!(score.komi, 0.5);
+        let split = FinalScore {
+            territory: Score {
+                winner: Some(Player::WHITE),
+                ..score.area
+            },
+            ..score
+        };
+        assert_eq!(score_result(&split, &gs.rules).winner, Some(Player::BLACK));
+        gs.rules.scoring = ScoringRule::Territory;
+        assert_eq!(score_result(&split, &gs.rules).winner, Some(Player::WHITE))
+    }
+
+    #[test]
+    fn resigning_player_loses() {
+        let gs = corner(SuicideRule::Forbidden);
+        let result = resignation(&Resign {
+            game_id: gs.game_id,
+            player: Player::WHITE,
+        });
+        assert_eq!(result.winner, Some(Player::BLACK));
+        assert_eq!(result.reason, EndReason::Resignation)
+    }
+
+    #[test]
+    fn finished_game_rejected() {
+        let mut gs = corner(SuicideRule::Forbidden);
+        gs.game_over = true;
+        let mm = make_move(&gs, Coord::of(5, 5));
+        assert!(matches!(
+            judge(&mm, &gs),
+            Judgement::Rejected(RejectReason::GameOver)
+        ))
+    }
 }
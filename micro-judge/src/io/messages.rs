@@ -18,6 +18,10 @@ pub type XReadResult = Vec<HashMap<String, Vec<HashMap<String, HashMap<String, V
 pub enum StreamData {
     MM(MakeMove),
     GS(GameState),
+    TD(ToggleDeadStone),
+    AS(AcceptScore),
+    RP(ResumePlay),
+    RG(Resign),
 }
 
 pub fn read_sorted(
@@ -29,8 +33,15 @@ pub fn read_sorted(
         .block(BLOCK_MS)
         .group(GROUP_NAME, "singleton");
     let ser = conn.xread_options(
-        &[&topics.make_move_cmd, &topics.game_states_changelog],
-        &[">", ">"],
+        &[
+            &topics.make_move_cmd,
+            &topics.game_states_changelog,
+            &topics.toggle_dead_stone_cmd,
+            &topics.accept_score_cmd,
+            &topics.resume_play_cmd,
+            &topics.resign_cmd,
+        ],
+        &[">", ">", ">", ">", ">", ">"],
         opts,
     )?;
 
@@ -64,6 +75,10 @@ fn deser(xread_result: XReadResult, topics: &StreamTopics) -> HashMap<XReadEntry
     let mut stream_data = HashMap::new();
     let make_move_topic = &topics.make_move_cmd;
     let game_states_topic = &topics.game_states_changelog;
+    let toggle_dead_stone_topic = &topics.toggle_dead_stone_cmd;
+    let accept_score_topic = &topics.accept_score_cmd;
+    let resume_play_topic = &topics.resume_play_cmd;
+    let resign_topic = &topics.resign_cmd;
     for hash in xread_result.iter() {
         for (xread_topic, xread_move_data) in hash.iter() {
             if &xread_topic[..] == make_move_topic {
@@ -92,6 +107,58 @@ fn deser(xread_result: XReadResult, topics: &StreamTopics) -> HashMap<XReadEntry
                         }
                     }
                 }
+            } else if &xread_topic[..] == toggle_dead_stone_topic {
+                for with_timestamps in xread_move_data {
+                    for (k, v) in with_timestamps {
+                        if let (Ok(seq_no), Some(td)) = (
+                            XReadEntryId::from_str(k),
+                            v.get("data").and_then(|b| bincode::deserialize(b).ok()),
+                        ) {
+                            stream_data.insert(seq_no, StreamData::TD(td));
+                        } else {
+                            error!("Deser error around toggle dead stone cmd")
+                        }
+                    }
+                }
+            } else if &xread_topic[..] == accept_score_topic {
+                for with_timestamps in xread_move_data {
+                    for (k, v) in with_timestamps {
+                        if let (Ok(seq_no), Some(acc)) = (
+                            XReadEntryId::from_str(k),
+                            v.get("data").and_then(|b| bincode::deserialize(b).ok()),
+                        ) {
+                            stream_data.insert(seq_no, StreamData::AS(acc));
+                        } else {
+                            error!("Deser error around accept score cmd")
+                        }
+                    }
+                }
+            } else if &xread_topic[..] == resume_play_topic {
+                for with_timestamps in xread_move_data {
+                    for (k, v) in with_timestamps {
+                        if let (Ok(seq_no), Some(rp)) = (
+                            XReadEntryId::from_str(k),
+                            v.get("data").and_then(|b| bincode::deserialize(b).ok()),
+                        ) {
+                            stream_data.insert(seq_no, StreamData::RP(rp));
+                        } else {
+                            error!("Deser error around resume play cmd")
+                        }
+                    }
+                }
+            } else if &xread_topic[..] == resign_topic {
+                for with_timestamps in xread_move_data {
+                    for (k, v) in with_timestamps {
+                        if let (Ok(seq_no), Some(rg)) = (
+                            XReadEntryId::from_str(k),
+                            v.get("data").and_then(|b| bincode::deserialize(b).ok()),
+                        ) {
+                            stream_data.insert(seq_no, StreamData::RG(rg));
+                        } else {
+                            error!("Deser error around resign cmd")
+                        }
+                    }
+                }
             } else {
                 println!("Ignoring topic {}", &xread_topic[..])
             }
@@ -121,7 +188,7 @@ fn deser_make_move_command(
     Ok(MakeMove {
         game_id: GameId(Uuid::from_str(&values_as_strings["game_id"])?),
         req_id: ReqId(Uuid::from_str(&values_as_strings["req_id"])?),
-        player: Player::from_str(&values_as_strings["player"]),
+        player: Player::from(values_as_strings["player"].as_str()),
         coord,
     })
 }
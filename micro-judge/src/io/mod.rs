@@ -9,6 +9,9 @@ pub enum WriteErr {
     Redis(redis::RedisError),
     Serialization(std::boxed::Box<bincode::ErrorKind>),
     EIDRepo,
+    /// The record was written by someone else since it was
+    /// fetched.  Fetch it again and retry.
+    Conflict,
 }
 impl From<std::boxed::Box<bincode::ErrorKind>> for WriteErr {
     fn from(ek: std::boxed::Box<bincode::ErrorKind>) -> Self {
@@ -13,3 +13,12 @@ pub fn entry_ids_hash_key(namespace: &RedisKeyNamespace) -> String {
 pub fn game_states_key(namespace: &RedisKeyNamespace, game_id: &GameId) -> String {
     format!("/{}/micro_judge/game_states/{}", namespace.0, game_id.0)
 }
+pub fn game_state_version_key(namespace: &RedisKeyNamespace, game_id: &GameId) -> String {
+    format!(
+        "/{}/micro_judge/game_state_versions/{}",
+        namespace.0, game_id.0
+    )
+}
+pub fn marking_key(namespace: &RedisKeyNamespace, game_id: &GameId) -> String {
+    format!("/{}/micro_judge/marking/{}", namespace.0, game_id.0)
+}
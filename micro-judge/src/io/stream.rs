@@ -4,6 +4,8 @@ use super::topics::*;
 use super::WriteErr;
 use crate::game::*;
 use crate::repo::game_states::GameStatesRepo;
+use crate::repo::marking::MarkingRepo;
+use core_model::{EventId, GameId};
 use move_model::*;
 use redis::{Client, Commands};
 use std::rc::Rc;
@@ -16,6 +18,10 @@ pub fn process(opts: StreamOpts) {
         if let Ok(xread_result) = read_sorted(&opts.topics, &opts.client) {
             let mut mm_processed = vec![];
             let mut gs_processed = vec![];
+            let mut td_processed = vec![];
+            let mut as_processed = vec![];
+            let mut rp_processed = vec![];
+            let mut rg_processed = vec![];
             for time_ordered_event in xread_result {
                 match time_ordered_event {
                     (entry_id, StreamData::MM(mm)) => {
@@ -33,10 +39,32 @@ pub fn process(opts: StreamOpts) {
                                     } else {
                                         info!("👩‍⚖️ {:?} OK", &mm.game_id)
                                     }
+
+                                    if ends_game(&move_made, &game_state) {
+                                        game_over(&game_state, &opts)
+                                    }
+                                }
+                                Judgement::Decisive(move_made, result) => {
+                                    if let Err(e) = xadd_move_accepted(
+                                        &move_made,
+                                        &opts.client,
+                                        &opts.topics.move_accepted_ev,
+                                    ) {
+                                        error!("Error XADD to move_accepted {:?}", e)
+                                    } else {
+                                        info!("👩‍⚖️ {:?} OK", &mm.game_id);
+                                        decided(&mm.game_id, result, &opts)
+                                    }
+                                }
+                                Judgement::Rejected(reason) => {
+                                    warn!("MOVE REJECTED ({:?}): {:#?}", reason, mm);
+                                    reject(&mm, reason, &opts)
                                 }
-                                Judgement::Rejected => warn!("MOVE REJECTED: {:#?}", mm),
                             },
-                            Ok(None) => warn!("No game state for game {}", mm.game_id.0),
+                            Ok(None) => {
+                                warn!("No game state for game {}", mm.game_id.0);
+                                reject(&mm, RejectReason::UnknownGame, &opts)
+                            }
                             Err(e) => error!("Deser error ({:?})!", e),
                         }
 
@@ -44,16 +72,45 @@ pub fn process(opts: StreamOpts) {
                     }
                     (entry_id, StreamData::GS(game_state)) => {
                         info!("Stream: Game State {:?}", &game_state.game_id);
-                        if let Err(e) = &opts
-                            .game_states_repo
-                            .write(&game_state.game_id, &game_state)
-                        {
-                            error!("error writing game state {:?}  -- advancing eid pointer", e)
-                        }
+                        save_game_state(&game_state, &opts);
 
                         gs_processed.push(entry_id);
-
-                        info!("💾 Game State Saved {:?}", &game_state.game_id);
+                    }
+                    (entry_id, StreamData::TD(td)) => {
+                        info!("Stream: Toggle Dead Stone {:?}", &td);
+                        if let Some((game_state, mut marking)) = marking_for(&td.game_id, &opts) {
+                            if toggle_dead(&mut marking, td.coord, &game_state.board) {
+                                save_marking(&marking, &game_state, &opts)
+                            }
+                        }
+                        td_processed.push(entry_id);
+                    }
+                    (entry_id, StreamData::AS(acc)) => {
+                        info!("Stream: Accept Score {:?}", &acc);
+                        if let Some((game_state, mut marking)) = marking_for(&acc.game_id, &opts) {
+                            accept(&mut marking, acc.player);
+                            save_marking(&marking, &game_state, &opts)
+                        }
+                        as_processed.push(entry_id);
+                    }
+                    (entry_id, StreamData::RP(rp)) => {
+                        info!("Stream: Resume Play {:?}", &rp);
+                        if let Some((game_state, _)) = marking_for(&rp.game_id, &opts) {
+                            resume_play(&rp, game_state, &opts)
+                        }
+                        rp_processed.push(entry_id);
+                    }
+                    (entry_id, StreamData::RG(rg)) => {
+                        info!("Stream: Resign {:?}", &rg);
+                        match opts.game_states_repo.fetch(&rg.game_id) {
+                            Ok(Some(game_state)) if !game_state.game_over => {
+                                resign(&rg, game_state, &opts)
+                            }
+                            Ok(Some(_)) => warn!("Game {:?} is already over", &rg.game_id),
+                            Ok(None) => warn!("No game state for game {}", rg.game_id.0),
+                            Err(e) => error!("Deser error ({:?})!", e),
+                        }
+                        rg_processed.push(entry_id);
                     }
                 }
             }
@@ -78,6 +135,18 @@ pub fn process(opts: StreamOpts) {
                     error!("ack in game states failed {:?} ", e);
                 }
             }
+            for (topic, processed) in &[
+                (&opts.topics.toggle_dead_stone_cmd, &td_processed),
+                (&opts.topics.accept_score_cmd, &as_processed),
+                (&opts.topics.resume_play_cmd, &rp_processed),
+                (&opts.topics.resign_cmd, &rg_processed),
+            ] {
+                if !processed.is_empty() {
+                    if let Err(e) = ack(topic, GROUP_NAME, processed, &opts.client) {
+                        error!("ack in {} failed {:?} ", topic, e);
+                    }
+                }
+            }
         }
     }
 }
@@ -100,12 +169,27 @@ pub fn create_consumer_groups(topics: &StreamTopics, client: &Client) {
             e
         );
     }
+    for topic in &[
+        &topics.toggle_dead_stone_cmd,
+        &topics.accept_score_cmd,
+        &topics.resume_play_cmd,
+        &topics.resign_cmd,
+    ] {
+        let created: Result<(), _> = conn.xgroup_create_mkstream(*topic, GROUP_NAME, "$");
+        if let Err(e) = created {
+            warn!(
+                "Ignoring error creating {} consumer group (it probably exists already) {:?}",
+                topic, e
+            );
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct StreamOpts {
     pub topics: StreamTopics,
     pub game_states_repo: GameStatesRepo,
+    pub marking_repo: MarkingRepo,
     pub client: Rc<Client>,
 }
 impl Default for StreamOpts {
@@ -115,6 +199,10 @@ impl Default for StreamOpts {
         StreamOpts {
             topics: StreamTopics::default(),
             game_states_repo: GameStatesRepo {
+                namespace: namespace.clone(),
+                client: client.clone(),
+            },
+            marking_repo: MarkingRepo {
                 namespace,
                 client: client.clone(),
             },
@@ -123,6 +211,215 @@ impl Default for StreamOpts {
     }
 }
 
+fn reject(mm: &MakeMove, reason: RejectReason, opts: &StreamOpts) {
+    let move_rejected = MoveRejected {
+        game_id: mm.game_id.clone(),
+        reply_to: mm.req_id.clone(),
+        event_id: EventId::new(),
+        player: mm.player,
+        coord: mm.coord,
+        reason,
+    };
+    if let Err(e) = xadd_move_rejected(&move_rejected, &opts.client, &opts.topics.move_rejected_ev)
+    {
+        error!("Error XADD to move_rejected {:?}", e)
+    }
+}
+
+/// Keeps a copy of the changelog's game state, unless we
+/// already hold the same or a later revision of it.  A
+/// write which conflicts with another consumer is tried
+/// again against the newer copy.
+fn save_game_state(game_state: &GameState, opts: &StreamOpts) {
+    let game_id = &game_state.game_id;
+    loop {
+        let version = match opts.game_states_repo.fetch_versioned(game_id) {
+            Ok(Some((held, _))) if held.revision >= game_state.revision => {
+                info!("Ignoring stale game state {:?}", game_id);
+                return;
+            }
+            Ok(Some((_, version))) => version,
+            Ok(None) => 0,
+            Err(e) => {
+                error!(
+                    "error fetching game state {:?}  -- advancing eid pointer",
+                    e
+                );
+                return;
+            }
+        };
+        match opts.game_states_repo.write(game_id, game_state, version) {
+            Err(WriteErr::Conflict) => warn!("Retrying write of {:?}", game_id),
+            Err(e) => {
+                error!("error writing game state {:?}  -- advancing eid pointer", e);
+                return;
+            }
+            Ok(_) => {
+                info!("💾 Game State Saved {:?}", game_id);
+                return;
+            }
+        }
+    }
+}
+
+fn game_over(game_state: &GameState, opts: &StreamOpts) {
+    let score = final_score(game_state, &[]);
+    let game_over = GameOver {
+        game_id: game_state.game_id.clone(),
+        event_id: EventId::new(),
+        result: score_result(&score, &game_state.rules),
+        score: Some(score),
+    };
+    info!("🏁 {:?} {:?}", &game_over.game_id, &game_over.score);
+    if let Err(e) = xadd_game_over(&game_over, &opts.client, &opts.topics.game_over_ev) {
+        error!("Error XADD to game_over {:?}", e)
+    }
+}
+
+/// Announces a game won by its variant's end condition.
+/// Changelog records the result when it applies the move.
+fn decided(game_id: &GameId, result: GameResult, opts: &StreamOpts) {
+    let game_over = GameOver {
+        game_id: game_id.clone(),
+        event_id: EventId::new(),
+        result,
+        score: None,
+    };
+    info!("🎯 {:?} {:?}", &game_over.game_id, &result);
+    if let Err(e) = xadd_game_over(&game_over, &opts.client, &opts.topics.game_over_ev) {
+        error!("Error XADD to game_over {:?}", e)
+    }
+}
+
+/// Records the result in the game state, and announces
+/// that the game is over
+fn resign(rg: &Resign, game_state: GameState, opts: &StreamOpts) {
+    let result = resignation(rg);
+    let resigned = GameState {
+        game_over: true,
+        result: Some(result),
+        revision: game_state.revision + 1,
+        ..game_state
+    };
+    let game_over = GameOver {
+        game_id: rg.game_id.clone(),
+        event_id: EventId::new(),
+        result,
+        score: None,
+    };
+    info!("🏳️ {:?} {:?}", &game_over.game_id, &result);
+    if let Err(e) = resigned
+        .serialize()
+        .map_err(WriteErr::from)
+        .and_then(|b| xadd_bytes(&b, &opts.client, &opts.topics.game_states_changelog))
+    {
+        error!("Error XADD to game_states_changelog {:?}", e)
+    } else if let Err(e) = xadd_game_over(&game_over, &opts.client, &opts.topics.game_over_ev) {
+        error!("Error XADD to game_over {:?}", e)
+    }
+}
+
+/// Dead stones may only be marked once the game is over,
+/// and until the result is decided
+fn marking_for(game_id: &GameId, opts: &StreamOpts) -> Option<(GameState, Marking)> {
+    match (
+        opts.game_states_repo.fetch(game_id),
+        opts.marking_repo.fetch(game_id),
+    ) {
+        (Ok(Some(game_state)), Ok(marking))
+            if game_state.game_over && game_state.result.is_none() =>
+        {
+            let marking = marking.unwrap_or_else(|| Marking::new(game_id.clone()));
+            if marking.finalized {
+                warn!("Score already final for {:?}", game_id);
+                None
+            } else {
+                Some((game_state, marking))
+            }
+        }
+        (Ok(_), Ok(_)) => {
+            warn!("Game {:?} is not over, ignoring marking", game_id);
+            None
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            error!("Error fetching marking for {:?}: {:?}", game_id, e);
+            None
+        }
+    }
+}
+
+fn save_marking(marking: &Marking, game_state: &GameState, opts: &StreamOpts) {
+    if let Err(e) = opts.marking_repo.write(marking) {
+        error!("Error writing marking {:?}", e)
+    } else if marking.finalized {
+        let finalized = score_finalized(marking, game_state);
+        info!("🏁 Final {:?} {:?}", &finalized.game_id, &finalized.score);
+        if let Err(e) = scored_state(&finalized, game_state)
+            .serialize()
+            .map_err(WriteErr::from)
+            .and_then(|b| xadd_bytes(&b, &opts.client, &opts.topics.game_states_changelog))
+        {
+            error!("Error XADD to game_states_changelog {:?}", e)
+        } else if let Err(e) = finalized
+            .serialize()
+            .map_err(WriteErr::from)
+            .and_then(|b| xadd_bytes(&b, &opts.client, &opts.topics.score_finalized_ev))
+        {
+            error!("Error XADD to score_finalized {:?}", e)
+        }
+    } else if let Err(e) = dead_stones_marked(marking, game_state)
+        .serialize()
+        .map_err(WriteErr::from)
+        .and_then(|b| xadd_bytes(&b, &opts.client, &opts.topics.dead_stones_marked_ev))
+    {
+        error!("Error XADD to dead_stones_marked {:?}", e)
+    }
+}
+
+/// Clears the marking and reopens the game by publishing
+/// its state to the changelog, no longer over
+fn resume_play(rp: &ResumePlay, game_state: GameState, opts: &StreamOpts) {
+    let resumed = GameState {
+        game_over: false,
+        result: None,
+        revision: game_state.revision + 1,
+        ..game_state
+    };
+    let play_resumed = PlayResumed {
+        game_id: rp.game_id.clone(),
+        event_id: EventId::new(),
+        player: rp.player,
+    };
+    if let Err(e) = opts.marking_repo.delete(&rp.game_id) {
+        error!("Error deleting marking {:?}", e)
+    } else if let Err(e) = resumed
+        .serialize()
+        .map_err(WriteErr::from)
+        .and_then(|b| xadd_bytes(&b, &opts.client, &opts.topics.game_states_changelog))
+    {
+        error!("Error XADD to game_states_changelog {:?}", e)
+    } else if let Err(e) = play_resumed
+        .serialize()
+        .map_err(WriteErr::from)
+        .and_then(|b| xadd_bytes(&b, &opts.client, &opts.topics.play_resumed_ev))
+    {
+        error!("Error XADD to play_resumed {:?}", e)
+    }
+}
+
+fn xadd_bytes(data: &[u8], client: &Client, stream_name: &str) -> Result<String, WriteErr> {
+    let mut conn = client.get_connection()?;
+    Ok(redis::cmd("XADD")
+        .arg(stream_name)
+        .arg("MAXLEN")
+        .arg("~")
+        .arg("1000")
+        .arg("*")
+        .arg("data")
+        .arg(data)
+        .query::<String>(&mut conn)?)
+}
+
 fn xadd_move_accepted(
     move_made: &MoveMade,
     client: &Client,
@@ -139,3 +436,37 @@ fn xadd_move_accepted(
         .arg(move_made.serialize()?)
         .query::<String>(&mut conn)?)
 }
+
+fn xadd_game_over(
+    game_over: &GameOver,
+    client: &Client,
+    stream_name: &str,
+) -> Result<String, WriteErr> {
+    let mut conn = client.get_connection()?;
+    Ok(redis::cmd("XADD")
+        .arg(stream_name)
+        .arg("MAXLEN")
+        .arg("~")
+        .arg("1000")
+        .arg("*")
+        .arg("data")
+        .arg(game_over.serialize()?)
+        .query::<String>(&mut conn)?)
+}
+
+fn xadd_move_rejected(
+    move_rejected: &MoveRejected,
+    client: &Client,
+    stream_name: &str,
+) -> Result<String, WriteErr> {
+    let mut conn = client.get_connection()?;
+    Ok(redis::cmd("XADD")
+        .arg(stream_name)
+        .arg("MAXLEN")
+        .arg("~")
+        .arg("1000")
+        .arg("*")
+        .arg("data")
+        .arg(move_rejected.serialize()?)
+        .query::<String>(&mut conn)?)
+}
@@ -1,12 +1,30 @@
 const DEFAULT_MAKE_MOVE_CMD: &str = "bugout-make-move-cmd";
 const DEFAULT_GAME_STATES_CHANGELOG: &str = "bugout-game-states";
 const DEFAULT_MOVE_ACCEPTED_EV: &str = "bugout-move-accepted-ev";
+const DEFAULT_MOVE_REJECTED_EV: &str = "bugout-move-rejected-ev";
+const DEFAULT_GAME_OVER_EV: &str = "bugout-game-over-ev";
+const DEFAULT_TOGGLE_DEAD_STONE_CMD: &str = "bugout-toggle-dead-stone-cmd";
+const DEFAULT_ACCEPT_SCORE_CMD: &str = "bugout-accept-score-cmd";
+const DEFAULT_RESUME_PLAY_CMD: &str = "bugout-resume-play-cmd";
+const DEFAULT_DEAD_STONES_MARKED_EV: &str = "bugout-dead-stones-marked-ev";
+const DEFAULT_SCORE_FINALIZED_EV: &str = "bugout-score-finalized-ev";
+const DEFAULT_PLAY_RESUMED_EV: &str = "bugout-play-resumed-ev";
+const DEFAULT_RESIGN_CMD: &str = "bugout-resign-cmd";
 
 #[derive(Clone)]
 pub struct StreamTopics {
     pub make_move_cmd: String,
     pub game_states_changelog: String,
     pub move_accepted_ev: String,
+    pub move_rejected_ev: String,
+    pub game_over_ev: String,
+    pub toggle_dead_stone_cmd: String,
+    pub accept_score_cmd: String,
+    pub resume_play_cmd: String,
+    pub dead_stones_marked_ev: String,
+    pub score_finalized_ev: String,
+    pub play_resumed_ev: String,
+    pub resign_cmd: String,
 }
 impl Default for StreamTopics {
     fn default() -> Self {
@@ -14,6 +32,15 @@ impl Default for StreamTopics {
             make_move_cmd: DEFAULT_MAKE_MOVE_CMD.to_string(),
             game_states_changelog: DEFAULT_GAME_STATES_CHANGELOG.to_string(),
             move_accepted_ev: DEFAULT_MOVE_ACCEPTED_EV.to_string(),
+            move_rejected_ev: DEFAULT_MOVE_REJECTED_EV.to_string(),
+            game_over_ev: DEFAULT_GAME_OVER_EV.to_string(),
+            toggle_dead_stone_cmd: DEFAULT_TOGGLE_DEAD_STONE_CMD.to_string(),
+            accept_score_cmd: DEFAULT_ACCEPT_SCORE_CMD.to_string(),
+            resume_play_cmd: DEFAULT_RESUME_PLAY_CMD.to_string(),
+            dead_stones_marked_ev: DEFAULT_DEAD_STONES_MARKED_EV.to_string(),
+            score_finalized_ev: DEFAULT_SCORE_FINALIZED_EV.to_string(),
+            play_resumed_ev: DEFAULT_PLAY_RESUMED_EV.to_string(),
+            resign_cmd: DEFAULT_RESIGN_CMD.to_string(),
         }
     }
 }
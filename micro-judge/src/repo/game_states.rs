@@ -1,4 +1,4 @@
-use crate::io::redis_keys::{game_states_key, RedisKeyNamespace};
+use crate::io::redis_keys::{game_state_version_key, game_states_key, RedisKeyNamespace};
 use crate::io::{FetchErr, WriteErr};
 
 use core_model::GameId;
@@ -15,13 +15,24 @@ pub struct GameStatesRepo {
 
 impl GameStatesRepo {
     pub fn fetch(&self, game_id: &GameId) -> Result<Option<GameState>, FetchErr> {
+        Ok(self
+            .fetch_versioned(game_id)?
+            .map(|(game_state, _)| game_state))
+    }
+
+    /// Fetches the game state along with the version of its
+    /// record, which must be given back when writing it
+    pub fn fetch_versioned(&self, game_id: &GameId) -> Result<Option<(GameState, u64)>, FetchErr> {
         let mut conn = self.client.get_connection().unwrap();
-        let key = game_states_key(&self.namespace, &game_id);
-        let bin_data: Option<Vec<u8>> = conn.get(&key)?;
+        let key = game_states_key(&self.namespace, game_id);
+        let version_key = game_state_version_key(&self.namespace, game_id);
+        let (bin_data, version): (Option<Vec<u8>>, Option<u64>) =
+            conn.get(&[&key, &version_key])?;
         let r = if let Some(b) = bin_data {
             // Touch TTL whenever you get the record
             conn.expire(key, EXPIRY_SECS)?;
-            Some(GameState::from(&b)?)
+            conn.expire(version_key, EXPIRY_SECS)?;
+            Some((GameState::from(&b)?, version.unwrap_or(0)))
         } else {
             None
         };
@@ -29,13 +40,40 @@ impl GameStatesRepo {
         Ok(r)
     }
 
-    pub fn write(&self, game_id: &GameId, game_state: &GameState) -> Result<String, WriteErr> {
+    /// Writes the game state, provided that nobody has written
+    /// it since the given version was fetched.  Records which
+    /// were never written are at version zero.  Gives the new
+    /// version, or `WriteErr::Conflict` for the caller to retry.
+    pub fn write(
+        &self,
+        game_id: &GameId,
+        game_state: &GameState,
+        version: u64,
+    ) -> Result<u64, WriteErr> {
         let mut conn = self.client.get_connection().unwrap();
 
-        let key = game_states_key(&self.namespace, &game_id);
-        let done = conn.set(&key, game_state.serialize()?)?;
+        let key = game_states_key(&self.namespace, game_id);
+        let version_key = game_state_version_key(&self.namespace, game_id);
+        let bytes = game_state.serialize()?;
+        redis::cmd("WATCH")
+            .arg(&version_key)
+            .query::<()>(&mut conn)?;
+        let current: Option<u64> = conn.get(&version_key)?;
+        if current.unwrap_or(0) != version {
+            redis::cmd("UNWATCH").query::<()>(&mut conn)?;
+            return Err(WriteErr::Conflict);
+        }
         // Touch TTL whenever you set the record
-        conn.expire(key, EXPIRY_SECS)?;
-        Ok(done)
+        let done: Option<()> = redis::pipe()
+            .atomic()
+            .set_ex(&key, bytes, EXPIRY_SECS)
+            .ignore()
+            .set_ex(&version_key, version + 1, EXPIRY_SECS)
+            .ignore()
+            .query(&mut conn)?;
+        match done {
+            Some(()) => Ok(version + 1),
+            None => Err(WriteErr::Conflict),
+        }
     }
 }
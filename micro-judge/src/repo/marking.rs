@@ -0,0 +1,41 @@
+use crate::io::redis_keys::{marking_key, RedisKeyNamespace};
+use crate::io::{FetchErr, WriteErr};
+
+use core_model::GameId;
+use move_model::Marking;
+use redis::{Client, Commands};
+
+const EXPIRY_SECS: usize = 86400;
+
+/// Tracks the dead stones marked in each finished game
+#[derive(Clone, Debug)]
+pub struct MarkingRepo {
+    pub namespace: RedisKeyNamespace,
+    pub client: std::rc::Rc<Client>,
+}
+
+impl MarkingRepo {
+    pub fn fetch(&self, game_id: &GameId) -> Result<Option<Marking>, FetchErr> {
+        let mut conn = self.client.get_connection()?;
+        let key = marking_key(&self.namespace, &game_id);
+        let bin_data: Option<Vec<u8>> = conn.get(&key)?;
+        Ok(if let Some(b) = bin_data {
+            Some(Marking::from(&b)?)
+        } else {
+            None
+        })
+    }
+
+    pub fn write(&self, marking: &Marking) -> Result<String, WriteErr> {
+        let mut conn = self.client.get_connection()?;
+        let key = marking_key(&self.namespace, &marking.game_id);
+        let done = conn.set(&key, marking.serialize()?)?;
+        conn.expire(key, EXPIRY_SECS)?;
+        Ok(done)
+    }
+
+    pub fn delete(&self, game_id: &GameId) -> Result<(), WriteErr> {
+        let mut conn = self.client.get_connection()?;
+        Ok(conn.del(marking_key(&self.namespace, &game_id))?)
+    }
+}
@@ -1 +1,2 @@
 pub mod game_states;
+pub mod marking;
@@ -13,6 +13,7 @@ extern crate micro_judge;
 use core_model::*;
 use micro_judge::io::{redis_keys, stream, topics};
 use micro_judge::repo::game_states::GameStatesRepo;
+use micro_judge::repo::marking::MarkingRepo;
 use move_model::*;
 use redis::Commands;
 use redis_keys::RedisKeyNamespace;
@@ -34,6 +35,15 @@ Use one of the following:
 const TEST_GAME_STATES_TOPIC: &str = "bugtest-game-states";
 const TEST_MAKE_MOVE_CMD_TOPIC: &str = "bugtest-make-move-cmd";
 const TEST_MOVE_ACCEPTED_EV_TOPIC: &str = "bugtest-move-accepted-ev";
+const TEST_MOVE_REJECTED_EV_TOPIC: &str = "bugtest-move-rejected-ev";
+const TEST_GAME_OVER_EV_TOPIC: &str = "bugtest-game-over-ev";
+const TEST_TOGGLE_DEAD_STONE_CMD_TOPIC: &str = "bugtest-toggle-dead-stone-cmd";
+const TEST_ACCEPT_SCORE_CMD_TOPIC: &str = "bugtest-accept-score-cmd";
+const TEST_RESUME_PLAY_CMD_TOPIC: &str = "bugtest-resume-play-cmd";
+const TEST_DEAD_STONES_MARKED_EV_TOPIC: &str = "bugtest-dead-stones-marked-ev";
+const TEST_SCORE_FINALIZED_EV_TOPIC: &str = "bugtest-score-finalized-ev";
+const TEST_PLAY_RESUMED_EV_TOPIC: &str = "bugtest-play-resumed-ev";
+const TEST_RESIGN_CMD_TOPIC: &str = "bugtest-resign-cmd";
 
 static FIRST_TEST_COMPLETE: AtomicBool = AtomicBool::new(false);
 
@@ -55,11 +65,24 @@ fn test_opts() -> stream::StreamOpts {
             make_move_cmd: TEST_MAKE_MOVE_CMD_TOPIC.to_string(),
             game_states_changelog: TEST_GAME_STATES_TOPIC.to_string(),
             move_accepted_ev: TEST_MOVE_ACCEPTED_EV_TOPIC.to_string(),
+            move_rejected_ev: TEST_MOVE_REJECTED_EV_TOPIC.to_string(),
+            game_over_ev: TEST_GAME_OVER_EV_TOPIC.to_string(),
+            toggle_dead_stone_cmd: TEST_TOGGLE_DEAD_STONE_CMD_TOPIC.to_string(),
+            accept_score_cmd: TEST_ACCEPT_SCORE_CMD_TOPIC.to_string(),
+            resume_play_cmd: TEST_RESUME_PLAY_CMD_TOPIC.to_string(),
+            dead_stones_marked_ev: TEST_DEAD_STONES_MARKED_EV_TOPIC.to_string(),
+            score_finalized_ev: TEST_SCORE_FINALIZED_EV_TOPIC.to_string(),
+            play_resumed_ev: TEST_PLAY_RESUMED_EV_TOPIC.to_string(),
+            resign_cmd: TEST_RESIGN_CMD_TOPIC.to_string(),
         },
         game_states_repo: GameStatesRepo {
             namespace: test_namespace(),
             client: client.clone(),
         },
+        marking_repo: MarkingRepo {
+            namespace: test_namespace(),
+            client: client.clone(),
+        },
         client,
     }
 }
@@ -102,6 +125,12 @@ fn test_emitted_game_states() {
         turn: 1,
         moves: vec![],
         player_up: Player::BLACK,
+        ko: None,
+        rules: Rules::default(),
+        game_over: false,
+        result: None,
+        handicap: vec![],
+        revision: 0,
     };
     redis::cmd("XADD")
         .arg(TEST_GAME_STATES_TOPIC)
@@ -176,6 +205,12 @@ fn test_moves_processed() {
         turn: 1,
         moves: vec![],
         player_up: Player::BLACK,
+        ko: None,
+        rules: Rules::default(),
+        game_over: false,
+        result: None,
+        handicap: vec![],
+        revision: 0,
     };
     redis::cmd("XADD")
         .arg(TEST_GAME_STATES_TOPIC)
@@ -224,10 +259,7 @@ fn test_moves_processed() {
 
         thread::sleep(Duration::from_millis(10));
         let old_game_state = current_game_state.clone();
-        current_game_state
-            .board
-            .pieces
-            .insert(*move_coord, *move_player);
+        current_game_state.board.place(*move_coord, *move_player);
         current_game_state.player_up = match *move_player {
             Player::BLACK => Player::WHITE,
             _ => Player::BLACK,
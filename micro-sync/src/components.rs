@@ -1,4 +1,4 @@
-use crate::repo::{HistoryRepo, ReplyOnMoveRepo};
+use crate::repo::{GameOverRepo, HistoryRepo, ReplyOnMoveRepo, SnapshotRepo};
 use crate::stream::{XAdd, XRead};
 use redis::Client;
 use std::rc::Rc;
@@ -6,6 +6,8 @@ use std::rc::Rc;
 pub struct Components {
     pub history_repo: Box<dyn HistoryRepo>,
     pub reply_repo: Box<dyn ReplyOnMoveRepo>,
+    pub game_over_repo: Box<dyn GameOverRepo>,
+    pub snapshot_repo: Box<dyn SnapshotRepo>,
     pub xread: Box<dyn XRead>,
     pub xadd: Box<dyn XAdd>,
 }
@@ -15,6 +17,8 @@ impl Components {
         Components {
             history_repo: Box::new(client.clone()),
             reply_repo: Box::new(client.clone()),
+            game_over_repo: Box::new(client.clone()),
+            snapshot_repo: Box::new(client.clone()),
             xread: Box::new(client.clone()),
             xadd: Box::new(client.clone()),
         }
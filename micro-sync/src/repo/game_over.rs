@@ -0,0 +1,60 @@
+use super::*;
+use crate::core_model::*;
+use crate::move_model::GameOver;
+use redis::{Client, Commands};
+use std::rc::Rc;
+
+/// Remembers which games have finished, so that
+/// we never ask the backend to make a move in them.
+/// Games which are resumed after scoring are removed.
+pub trait GameOverRepo {
+    fn get(&self, game_id: &GameId) -> Result<Option<GameOver>, FetchErr>;
+    fn put(&self, game_over: &GameOver) -> Result<(), WriteErr>;
+    fn del(&self, game_id: &GameId) -> Result<(), WriteErr>;
+}
+
+impl GameOverRepo for Rc<Client> {
+    fn get(&self, game_id: &GameId) -> Result<Option<GameOver>, FetchErr> {
+        match self.get_connection() {
+            Ok(mut conn) => {
+                let key = redis_key(game_id);
+                let data: Result<Option<Vec<u8>>, _> =
+                    conn.get(&key).map_err(|e| FetchErr::Redis(e));
+
+                if data.is_ok() {
+                    touch_ttl(&mut conn, &key)
+                }
+
+                match data {
+                    Ok(Some(bytes)) => {
+                        let deser: Result<GameOver, _> = bincode::deserialize(&bytes);
+                        deser.map(|go| Some(go)).map_err(|e| FetchErr::Deser(e))
+                    }
+                    Ok(None) => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(FetchErr::Redis(e)),
+        }
+    }
+
+    fn put(&self, game_over: &GameOver) -> Result<(), WriteErr> {
+        let key = redis_key(&game_over.game_id);
+        if let (Ok(mut conn), Ok(bytes)) = (self.get_connection(), bincode::serialize(game_over)) {
+            let done = conn.set(&key, bytes).map_err(|_| WriteErr)?;
+            touch_ttl(&mut conn, &key);
+            Ok(done)
+        } else {
+            Err(WriteErr)
+        }
+    }
+
+    fn del(&self, game_id: &GameId) -> Result<(), WriteErr> {
+        let mut conn = self.get_connection().map_err(|_| WriteErr)?;
+        conn.del(redis_key(game_id)).map_err(|_| WriteErr)
+    }
+}
+
+fn redis_key(game_id: &GameId) -> String {
+    format!("/BUGOUT/micro_sync/game_over/{}", game_id.0)
+}
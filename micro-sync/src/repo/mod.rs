@@ -1,8 +1,12 @@
+mod game_over;
 mod history;
 mod reply;
+mod snapshot;
 
+pub use game_over::*;
 pub use history::*;
 pub use reply::*;
+pub use snapshot::*;
 
 use log::error;
 use redis::{Commands, Connection, RedisError};
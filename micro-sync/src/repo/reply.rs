@@ -1,21 +1,22 @@
 use super::*;
 use crate::core_model::*;
+use crate::sync::CatchUp;
 use redis::Client;
 use std::rc::Rc;
-use sync_model::api::ReqSync;
 
 /// "Do we need to form a reply?"
 /// Used when client is ahead of the system.  Stores
-/// a requested sync event which can later be merged
-/// with a MOVE MADE event to form a sync reply.
+/// a client's catch up under the request ID of its move
+/// in flight, so that the MOVE MADE event answering that
+/// move can settle it.
 pub trait ReplyOnMoveRepo {
-    fn get(&self, game_id: &GameId, req_id: &ReqId) -> Result<Option<ReqSync>, FetchErr>;
-    fn put(&self, req: &ReqSync) -> Result<(), WriteErr>;
+    fn get(&self, game_id: &GameId, req_id: &ReqId) -> Result<Option<CatchUp>, FetchErr>;
+    fn put(&self, req_id: &ReqId, catch_up: &CatchUp) -> Result<(), WriteErr>;
     fn del(&self, game_id: &GameId, req_id: &ReqId) -> Result<(), WriteErr>;
 }
 
 impl ReplyOnMoveRepo for Rc<Client> {
-    fn get(&self, game_id: &GameId, req_id: &ReqId) -> Result<Option<ReqSync>, FetchErr> {
+    fn get(&self, game_id: &GameId, req_id: &ReqId) -> Result<Option<CatchUp>, FetchErr> {
         match self.get_connection() {
             Ok(mut conn) => {
                 let key = redis_key(game_id, req_id);
@@ -27,7 +28,7 @@ impl ReplyOnMoveRepo for Rc<Client> {
                 }
                 match data {
                     Ok(Some(bytes)) => {
-                        let deser: Result<ReqSync, _> = bincode::deserialize(&bytes);
+                        let deser: Result<CatchUp, _> = bincode::deserialize(&bytes);
                         deser.map(|hist| Some(hist)).map_err(|e| FetchErr::Deser(e))
                     }
                     Ok(None) => Ok(None),
@@ -38,9 +39,9 @@ impl ReplyOnMoveRepo for Rc<Client> {
         }
     }
 
-    fn put(&self, req: &ReqSync) -> Result<(), WriteErr> {
-        let key = redis_key(&req.game_id, &req.req_id);
-        if let (Ok(mut conn), Ok(bytes)) = (self.get_connection(), bincode::serialize(&req)) {
+    fn put(&self, req_id: &ReqId, catch_up: &CatchUp) -> Result<(), WriteErr> {
+        let key = redis_key(&catch_up.req_sync.game_id, req_id);
+        if let (Ok(mut conn), Ok(bytes)) = (self.get_connection(), bincode::serialize(catch_up)) {
             let done = conn.set(&key, bytes).map_err(|_| WriteErr)?;
             touch_ttl(&mut conn, &key);
             Ok(done)
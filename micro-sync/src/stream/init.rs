@@ -3,7 +3,17 @@ use log::warn;
 use redis::Commands;
 pub fn create_consumer_group(client: &redis::Client) {
     let mut conn = client.get_connection().expect("group create conn");
-    let to_create = vec![PROVIDE_HISTORY, GAME_STATES_CHANGELOG, REQ_SYNC, MOVE_MADE];
+    let to_create = vec![
+        PROVIDE_HISTORY,
+        GAME_STATES_CHANGELOG,
+        REQ_SYNC,
+        MOVE_MADE,
+        MOVE_REJECTED,
+        GAME_OVER,
+        SCORE_FINALIZED,
+        PLAY_RESUMED,
+        ARCHIVE_PROVIDED,
+    ];
     for topic in to_create {
         let created: Result<(), _> = conn.xgroup_create_mkstream(topic, GROUP_NAME, "$");
         if let Err(e) = created {
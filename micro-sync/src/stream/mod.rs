@@ -8,13 +8,12 @@ pub use xread::*;
 
 use crate::components::*;
 use crate::player::other_player;
-use crate::sync::is_client_ahead_by_one_turn;
+use crate::sync::{diverged, missing_moves, CatchUp};
 use crate::ToHistory;
 use core_model::*;
 use log::{error, info, trace, warn};
 use move_model::*;
 use sync_model::api::*;
-use sync_model::Move;
 use sync_model::*;
 
 const GROUP_NAME: &str = "micro-sync";
@@ -25,6 +24,11 @@ pub enum StreamInput {
     GS(GameState),
     RS(ReqSync),
     MM(MoveMade),
+    MR(MoveRejected),
+    GO(GameOver),
+    SF(ScoreFinalized),
+    PR(PlayResumed),
+    AP(ArchiveProvided),
 }
 
 pub fn process(components: &Components) {
@@ -50,6 +54,11 @@ fn process_event(event: &StreamInput, components: &Components) {
         StreamInput::PH(ph) => process_prov_hist(ph, components),
         StreamInput::GS(game_state) => process_game_state(game_state, components),
         StreamInput::MM(mm) => process_move_made(mm, components),
+        StreamInput::MR(mr) => process_move_rejected(mr, components),
+        StreamInput::GO(go) => process_game_over(go, components),
+        StreamInput::SF(sf) => process_score_finalized(sf, components),
+        StreamInput::PR(pr) => process_play_resumed(pr, components),
+        StreamInput::AP(ap) => process_archive_provided(ap, components),
     }
 }
 
@@ -59,50 +68,75 @@ fn process_req_sync(rs: &ReqSync, components: &Components) {
         Ok(maybe_history) => {
             let history = maybe_history.unwrap_or_default();
             let system_last_move = history.last();
+            let first_player = if history.is_empty() {
+                first_player(&rs.game_id, components)
+            } else {
+                Player::BLACK
+            };
             let system_player_up = system_last_move
                 .map(|m| other_player(m.player))
-                .unwrap_or(Player::BLACK);
+                .unwrap_or(first_player);
             let system_turn = system_last_move.map(|m| m.turn).unwrap_or(0) + 1;
-
-            if is_client_ahead_by_one_turn(rs, system_turn, system_player_up) {
-                // client is ahead of server by a single turn
-                // and their move needs to be processed
-                if let Some(missed_move) = &rs.last_move {
-                    let make_move = MakeMove {
-                        game_id: rs.game_id.clone(),
-                        req_id: rs.req_id.clone(),
-                        player: missed_move.player,
-                        coord: missed_move.coord,
-                    };
-
-                    if let Err(e) = components.xadd.add_make_move(make_move) {
-                        error!("xadd make move {:?}", e)
-                    }
-
-                    // Very important ... 😈
-                    // We need to remember this request, so that
-                    // when a move is finally made by changelog,
-                    // we don't forget to send a sync reply.
-
-                    // The reply will ultimately be processed in our
-                    // process loop's StreamInput::MM(move_made) branch !
-                    if let Err(_) = components.reply_repo.put(rs) {
-                        error!("fail to put req in reply repo")
-                    }
+            let game_over = match components.game_over_repo.get(&rs.game_id) {
+                Ok(go) => go.is_some(),
+                Err(e) => {
+                    error!("game over lookup for req sync : {:?}", e);
+                    false
                 }
+            };
+
+            let missing = if game_over {
+                vec![]
+            } else {
+                missing_moves(rs, &history, first_player)
+            };
+            if !missing.is_empty() {
+                // client is ahead of server, and their moves
+                // need to be processed one after another
+                let catch_up = CatchUp {
+                    req_sync: rs.clone(),
+                    moves: history,
+                    pending: missing,
+                    first_player,
+                };
+                // Very important ... 😈
+                // We need to remember this request, so that
+                // when a move is finally made by changelog,
+                // we don't forget to send the next move, or
+                // the sync reply once they're all settled.
+
+                // This will ultimately be processed in our
+                // process loop's StreamInput::MM(move_made) branch !
+                request_next_move(&rs.req_id, &catch_up, components)
             } else {
                 // in every other case, we should send the server's view:
                 // - no op: client is caught up
-                // - client is behind by one move
+                // - client is behind
                 // - client has a state which we cannot reconcile
                 //            ...(but maybe they can fix themselves)
+                // - the game is over, and no more moves may be made
+                let snapshot = match rs.board_checksum {
+                    Some(checksum) if rs.turn == system_turn => {
+                        board_mismatch(&rs.game_id, checksum, components)
+                    }
+                    _ => None,
+                };
+                // a client whose board or moves have drifted
+                // gets everything
+                let (moves, next_page) = if snapshot.is_some() || diverged(rs, &history) {
+                    (history, None)
+                } else {
+                    rs.page.select(&history)
+                };
                 let sync_reply = SyncReply {
-                    moves: history,
+                    moves,
+                    next_page,
                     game_id: rs.game_id.clone(),
                     reply_to: rs.req_id.clone(),
                     player_up: system_player_up,
                     turn: system_turn,
                     session_id: rs.session_id.clone(),
+                    snapshot,
                 };
                 if let Err(e) = components.xadd.add_sync_reply(sync_reply) {
                     error!("xadd sync reply {:?}", e)
@@ -113,27 +147,108 @@ fn process_req_sync(rs: &ReqSync, components: &Components) {
     }
 }
 
+/// Who moves first in a game with no moves yet.  White
+/// does, if black was given handicap stones.
+fn first_player(game_id: &GameId, components: &Components) -> Player {
+    match components.snapshot_repo.get(game_id) {
+        Ok(Some(snapshot)) => snapshot.player_up,
+        Ok(None) => Player::BLACK,
+        Err(e) => {
+            error!("snapshot lookup for first player : {:?}", e);
+            Player::BLACK
+        }
+    }
+}
+
+/// Our snapshot of the board, if the client's checksum
+/// disagrees with it
+fn board_mismatch(game_id: &GameId, checksum: u64, components: &Components) -> Option<Snapshot> {
+    match components.snapshot_repo.get(game_id) {
+        Ok(Some(snapshot)) if snapshot.checksum() != checksum => {
+            warn!("board checksum mismatch in game {:?}", game_id);
+            Some(snapshot)
+        }
+        Ok(_) => None,
+        Err(e) => {
+            error!("snapshot lookup for req sync : {:?}", e);
+            None
+        }
+    }
+}
+
 fn process_prov_hist(ph: &ProvideHistory, components: &Components) {
     info!("Stream: Provide History {:?}", ph);
     let maybe_hist_r = components.history_repo.get(&ph.game_id);
     match maybe_hist_r {
         Ok(Some(moves)) => {
+            let (score, result) = match components.game_over_repo.get(&ph.game_id) {
+                Ok(Some(game_over)) => (game_over.score, Some(game_over.result)),
+                Ok(None) => (None, None),
+                Err(e) => {
+                    error!("game over lookup error in prov hist: {:?}", e);
+                    (None, None)
+                }
+            };
+            let (moves, next_page) = ph.page.select(&moves);
             let hp = HistoryProvided {
                 moves,
+                next_page,
                 event_id: EventId::new(),
                 epoch_millis: crate::time::now_millis() as u64,
                 game_id: ph.game_id.clone(),
                 reply_to: ph.req_id.clone(),
+                score,
+                result,
             };
             if let Err(e) = components.xadd.add_history_provided(hp) {
                 error!("error in xadd {:?}", e)
             }
         }
-        Ok(None) => warn!("no history for game {:?}", ph.game_id),
+        Ok(None) => {
+            // redis forgets games after a day, but
+            // the archive keeps the finished ones
+            info!("no history for game {:?}, asking archive", ph.game_id);
+            if let Err(e) = components.xadd.add_provide_archive(ProvideArchive {
+                game_id: ph.game_id.clone(),
+                req_id: ph.req_id.clone(),
+                page: ph.page,
+            }) {
+                error!("error in xadd {:?}", e)
+            }
+        }
         Err(e) => error!("history lookup error in prov hist: {:?}", e),
     }
 }
 
+/// Answers a history request which redis could not.  A
+/// game which the archive never saw has no moves.
+fn process_archive_provided(ap: &ArchiveProvided, components: &Components) {
+    info!("Stream: Archive Provided {:?}", ap.game_id);
+    let (moves, next_page, result) = match &ap.game_state {
+        Some(game_state) => {
+            let (moves, next_page) = ap.page.select(&game_state.to_history());
+            (moves, next_page, game_state.result)
+        }
+        None => {
+            warn!("no history for game {:?}", ap.game_id);
+            (vec![], None, None)
+        }
+    };
+    let hp = HistoryProvided {
+        moves,
+        next_page,
+        event_id: EventId::new(),
+        epoch_millis: crate::time::now_millis() as u64,
+        game_id: ap.game_id.clone(),
+        reply_to: ap.reply_to.clone(),
+        score: ap.score,
+        result,
+    };
+    if let Err(e) = components.xadd.add_history_provided(hp) {
+        error!("error in xadd {:?}", e)
+    }
+}
+
 fn process_game_state(game_state: &GameState, components: &Components) {
     info!("Stream: Game State   {:?}", game_state.game_id);
     trace!("Full game state: {:?}", game_state);
@@ -143,47 +258,68 @@ fn process_game_state(game_state: &GameState, components: &Components) {
     {
         error!("write to history repo")
     }
+    let snapshot = Snapshot {
+        board: game_state.board.clone(),
+        captures: game_state.captures.clone(),
+        player_up: game_state.player_up,
+    };
+    if let Err(_e) = components.snapshot_repo.put(&game_state.game_id, &snapshot) {
+        error!("write to snapshot repo")
+    }
+}
+
+fn process_game_over(game_over: &GameOver, components: &Components) {
+    info!("Stream: Game Over {:?}", game_over);
+    if let Err(_e) = components.game_over_repo.put(game_over) {
+        error!("write to game over repo")
+    }
+}
+
+/// Keeps the agreed score, which accounts for dead stones
+fn process_score_finalized(score_finalized: &ScoreFinalized, components: &Components) {
+    info!("Stream: Score Finalized {:?}", score_finalized);
+    match components.game_over_repo.get(&score_finalized.game_id) {
+        Ok(Some(game_over)) => {
+            if let Err(_e) = components.game_over_repo.put(&GameOver {
+                score: Some(score_finalized.score),
+                result: score_finalized.result,
+                ..game_over
+            }) {
+                error!("write to game over repo")
+            }
+        }
+        Ok(None) => warn!("no game over for {:?}", score_finalized.game_id),
+        Err(e) => error!("game over lookup for score finalized : {:?}", e),
+    }
+}
+
+fn process_play_resumed(play_resumed: &PlayResumed, components: &Components) {
+    info!("Stream: Play Resumed {:?}", play_resumed);
+    if let Err(_e) = components.game_over_repo.del(&play_resumed.game_id) {
+        error!("delete from game over repo")
+    }
 }
 
 fn process_move_made(move_made: &MoveMade, components: &Components) {
     info!("Stream: Move Made {:?}", move_made);
-    // Check ReplyOnMove repo to see if we have a req_sync associated with this
-    // game_id & req_id combination.
+    // Check ReplyOnMove repo to see if we have a catch up waiting
+    // on this game_id & req_id combination.
     match components
         .reply_repo
         .get(&move_made.game_id, &move_made.reply_to)
     {
-        Ok(Some(req_sync)) => {
+        Ok(Some(mut catch_up)) => {
             // We were waiting to hear about this move being made.
-            //  we need to create a
-            // sync reply based on this move.  This branch executes in the
-            // case where a client was previously ahead of the backend and we
-            // emitted a MakeMove request.  This MoveMade is the result
-            // of changelog recording our move
-            match components.history_repo.get(&move_made.game_id) {
-                Ok(history_nested) => {
-                    let history = history_nested.unwrap_or_default();
-                    let mut all_moves = history.clone();
-                    let the_turn = (history.last().map(|l| l.turn)).unwrap_or_default() + 1;
-                    all_moves.push(Move {
-                        player: move_made.player,
-                        coord: move_made.coord,
-                        turn: the_turn,
-                    });
-
-                    let sync_reply = SyncReply {
-                        session_id: req_sync.session_id,
-                        game_id: req_sync.game_id,
-                        reply_to: req_sync.req_id,
-                        moves: all_moves,
-                        turn: the_turn + 1,
-                        player_up: other_player(move_made.player),
-                    };
-                    if let Err(_) = components.xadd.add_sync_reply(sync_reply) {
-                        error!("xadd sync reply")
-                    }
-                }
-                Err(e) => error!("history fetch err in move made processor: {:?}", e),
+            // This branch executes in the case where a client was
+            // previously ahead of the backend and we emitted a
+            // MakeMove request.  This MoveMade is the result of
+            // changelog recording our move.
+            forget_reply(&move_made.game_id, &move_made.reply_to, components);
+            catch_up.made(move_made);
+            if catch_up.pending.is_empty() {
+                send_catch_up_reply(&catch_up, components)
+            } else {
+                request_next_move(&ReqId::new(), &catch_up, components)
             }
         }
         Ok(None) => (),
@@ -191,6 +327,47 @@ fn process_move_made(move_made: &MoveMade, components: &Components) {
     }
 }
 
+/// The judge refused one of the client's moves, so the
+/// rest of them cannot be played either.  The client hears
+/// the backend's view instead.
+fn process_move_rejected(move_rejected: &MoveRejected, components: &Components) {
+    match components
+        .reply_repo
+        .get(&move_rejected.game_id, &move_rejected.reply_to)
+    {
+        Ok(Some(catch_up)) => {
+            info!("Stream: Move Rejected {:?}", move_rejected);
+            forget_reply(&move_rejected.game_id, &move_rejected.reply_to, components);
+            send_catch_up_reply(&catch_up, components)
+        }
+        Ok(None) => (),
+        Err(e) => error!("error fetching from reply repo: {:?}", e),
+    }
+}
+
+fn request_next_move(req_id: &ReqId, catch_up: &CatchUp, components: &Components) {
+    if let Some(make_move) = catch_up.make_move(req_id) {
+        if components.reply_repo.put(req_id, catch_up).is_err() {
+            error!("fail to put catch up in reply repo")
+        }
+        if let Err(e) = components.xadd.add_make_move(make_move) {
+            error!("xadd make move {:?}", e)
+        }
+    }
+}
+
+fn forget_reply(game_id: &GameId, req_id: &ReqId, components: &Components) {
+    if components.reply_repo.del(game_id, req_id).is_err() {
+        error!("fail to delete from reply repo")
+    }
+}
+
+fn send_catch_up_reply(catch_up: &CatchUp, components: &Components) {
+    if components.xadd.add_sync_reply(catch_up.reply()).is_err() {
+        error!("xadd sync reply")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -220,16 +397,16 @@ mod test {
     }
 
     struct FakeReplyRepo {
-        pub contents: Arc<Mutex<Option<ReqSync>>>,
+        pub contents: Arc<Mutex<Option<CatchUp>>>,
     }
     impl ReplyOnMoveRepo for FakeReplyRepo {
-        fn get(&self, _game_id: &GameId, _req_id: &ReqId) -> Result<Option<ReqSync>, FetchErr> {
+        fn get(&self, _game_id: &GameId, _req_id: &ReqId) -> Result<Option<CatchUp>, FetchErr> {
             Ok(self.contents.lock().expect("mutex").clone())
         }
 
-        fn put(&self, req: &ReqSync) -> Result<(), WriteErr> {
+        fn put(&self, _req_id: &ReqId, catch_up: &CatchUp) -> Result<(), WriteErr> {
             let mut data = self.contents.lock().expect("mutex");
-            *data = Some(req.clone());
+            *data = Some(catch_up.clone());
             Ok(())
         }
 
@@ -240,20 +417,66 @@ mod test {
         }
     }
 
+    struct FakeGameOverRepo {
+        pub contents: Arc<Mutex<Option<GameOver>>>,
+    }
+    impl GameOverRepo for FakeGameOverRepo {
+        fn get(&self, _game_id: &GameId) -> Result<Option<GameOver>, FetchErr> {
+            Ok(self.contents.lock().expect("mutex").clone())
+        }
+
+        fn put(&self, game_over: &GameOver) -> Result<(), WriteErr> {
+            let mut data = self.contents.lock().expect("mutex");
+            *data = Some(game_over.clone());
+            Ok(())
+        }
+
+        fn del(&self, _game_id: &GameId) -> Result<(), WriteErr> {
+            let mut data = self.contents.lock().expect("mutex");
+            *data = None;
+            Ok(())
+        }
+    }
+
+    struct FakeSnapshotRepo {
+        pub contents: Arc<Mutex<Option<Snapshot>>>,
+    }
+    impl SnapshotRepo for FakeSnapshotRepo {
+        fn get(&self, _game_id: &GameId) -> Result<Option<Snapshot>, FetchErr> {
+            Ok(self.contents.lock().expect("mutex").clone())
+        }
+
+        fn put(&self, _game_id: &GameId, snapshot: &Snapshot) -> Result<(), WriteErr> {
+            let mut data = self.contents.lock().expect("mutex");
+            *data = Some(snapshot.clone());
+            Ok(())
+        }
+    }
+
     struct FakeAcks {
         last_mm_ack_ms: AtomicU64,
+        last_mr_ack_ms: AtomicU64,
         last_rs_ack_ms: AtomicU64,
         last_ph_ack_ms: AtomicU64,
         last_gs_ack_ms: AtomicU64,
+        last_go_ack_ms: AtomicU64,
+        last_sf_ack_ms: AtomicU64,
+        last_pr_ack_ms: AtomicU64,
+        last_ap_ack_ms: AtomicU64,
         max_read_xid_ms: AtomicU64,
     }
     impl FakeAcks {
         pub fn new() -> Self {
             Self {
                 last_mm_ack_ms: AtomicU64::new(0),
+                last_mr_ack_ms: AtomicU64::new(0),
                 last_rs_ack_ms: AtomicU64::new(0),
                 last_ph_ack_ms: AtomicU64::new(0),
                 last_gs_ack_ms: AtomicU64::new(0),
+                last_go_ack_ms: AtomicU64::new(0),
+                last_sf_ack_ms: AtomicU64::new(0),
+                last_pr_ack_ms: AtomicU64::new(0),
+                last_ap_ack_ms: AtomicU64::new(0),
                 max_read_xid_ms: AtomicU64::new(0),
             }
         }
@@ -305,6 +528,28 @@ mod test {
         fn ack_move_made(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
             Ok(self.update_max_id(&self.fake_acks.last_mm_ack_ms, ids))
         }
+
+        fn ack_move_rejected(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            self.update_max_id(&self.fake_acks.last_mr_ack_ms, ids);
+            Ok(())
+        }
+
+        fn ack_game_over(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(self.update_max_id(&self.fake_acks.last_go_ack_ms, ids))
+        }
+
+        fn ack_score_finalized(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(self.update_max_id(&self.fake_acks.last_sf_ack_ms, ids))
+        }
+
+        fn ack_play_resumed(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(self.update_max_id(&self.fake_acks.last_pr_ack_ms, ids))
+        }
+
+        fn ack_archive_provided(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            self.update_max_id(&self.fake_acks.last_ap_ack_ms, ids);
+            Ok(())
+        }
     }
     impl FakeXRead {
         fn update_max_id(&self, some: &AtomicU64, ids: &[XReadEntryId]) {
@@ -318,6 +563,7 @@ mod test {
         hist_prov_in: Sender<HistoryProvided>,
         sync_reply_in: Sender<SyncReply>,
         make_move_in: Sender<MakeMove>,
+        provide_archive_in: Sender<ProvideArchive>,
     }
     impl XAdd for FakeXAdd {
         fn add_history_provided(&self, data: HistoryProvided) -> Result<(), XAddErr> {
@@ -331,6 +577,11 @@ mod test {
         fn add_make_move(&self, data: MakeMove) -> Result<(), XAddErr> {
             Ok(self.make_move_in.send(data).expect("send"))
         }
+
+        fn add_provide_archive(&self, data: ProvideArchive) -> Result<(), XAddErr> {
+            self.provide_archive_in.send(data).expect("send");
+            Ok(())
+        }
     }
 
     fn quick_xid(ms: u64) -> XReadEntryId {
@@ -343,11 +594,14 @@ mod test {
     const SLEEP_WAIT_MS: u64 = 100;
     struct TestFakes {
         history_contents: Arc<Mutex<Option<Vec<Move>>>>,
-        reply_contents: Arc<Mutex<Option<ReqSync>>>,
+        reply_contents: Arc<Mutex<Option<CatchUp>>>,
+        game_over_contents: Arc<Mutex<Option<GameOver>>>,
+        snapshot_contents: Arc<Mutex<Option<Snapshot>>>,
         sorted_stream: Arc<Mutex<Vec<(XReadEntryId, StreamInput)>>>,
         sync_reply_xadd_out: Receiver<SyncReply>,
         hist_prov_xadd_out: Receiver<HistoryProvided>,
         make_move_xadd_out: Receiver<MakeMove>,
+        provide_archive_xadd_out: Receiver<ProvideArchive>,
         acks: Arc<FakeAcks>,
         time_ms: u64,
     }
@@ -371,9 +625,13 @@ mod test {
         let (hist_prov_xadd_in, hist_prov_xadd_out): (Sender<HistoryProvided>, _) = unbounded();
         let (sync_reply_xadd_in, sync_reply_xadd_out): (Sender<SyncReply>, _) = unbounded();
         let (make_move_xadd_in, make_move_xadd_out): (Sender<MakeMove>, _) = unbounded();
+        let (provide_archive_xadd_in, provide_archive_xadd_out): (Sender<ProvideArchive>, _) =
+            unbounded();
 
         let history_contents: Arc<Mutex<Option<Vec<Move>>>> = Arc::new(Mutex::new(None));
-        let reply_contents: Arc<Mutex<Option<ReqSync>>> = Arc::new(Mutex::new(None));
+        let reply_contents: Arc<Mutex<Option<CatchUp>>> = Arc::new(Mutex::new(None));
+        let game_over_contents: Arc<Mutex<Option<GameOver>>> = Arc::new(Mutex::new(None));
+        let snapshot_contents: Arc<Mutex<Option<Snapshot>>> = Arc::new(Mutex::new(None));
 
         let sorted_stream: Arc<Mutex<Vec<(XReadEntryId, StreamInput)>>> =
             Arc::new(Mutex::new(vec![]));
@@ -381,6 +639,8 @@ mod test {
         let sfs = sorted_stream.clone();
         let fh = history_contents.clone();
         let fr = reply_contents.clone();
+        let fg = game_over_contents.clone();
+        let fs = snapshot_contents.clone();
 
         let acks = Arc::new(FakeAcks::new());
         let ackss = acks.clone();
@@ -388,6 +648,8 @@ mod test {
             let components = Components {
                 history_repo: Box::new(FakeHistoryRepo { contents: fh }),
                 reply_repo: Box::new(FakeReplyRepo { contents: fr }),
+                game_over_repo: Box::new(FakeGameOverRepo { contents: fg }),
+                snapshot_repo: Box::new(FakeSnapshotRepo { contents: fs }),
                 xread: Box::new(FakeXRead {
                     sorted_data: sfs.clone(),
                     fake_acks: ackss,
@@ -396,6 +658,7 @@ mod test {
                     hist_prov_in: hist_prov_xadd_in,
                     sync_reply_in: sync_reply_xadd_in,
                     make_move_in: make_move_xadd_in,
+                    provide_archive_in: provide_archive_xadd_in,
                 }),
             };
             process(&components);
@@ -404,10 +667,13 @@ mod test {
         TestFakes {
             history_contents,
             reply_contents,
+            game_over_contents,
+            snapshot_contents,
             sorted_stream,
             hist_prov_xadd_out,
             sync_reply_xadd_out,
             make_move_xadd_out,
+            provide_archive_xadd_out,
             acks,
             time_ms: 100, // be careful, this needs to be non-zero or everything will hang 😹
         }
@@ -445,8 +711,11 @@ mod test {
             req_id: req_id.clone(),
             game_id: game_id.clone(),
             last_move,
+            recent_moves: vec![],
+            page: Page::default(),
             player_up,
             turn,
+            board_checksum: None,
         };
 
         // force fake history repo to respond as we expect
@@ -466,6 +735,8 @@ mod test {
             game_id,
             player_up,
             turn,
+            next_page: None,
+            snapshot: None,
         };
 
         let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
@@ -505,8 +776,11 @@ mod test {
             req_id: req_id.clone(),
             game_id: game_id.clone(),
             last_move: Some(client_last_move_behind_by_one),
+            recent_moves: vec![],
+            page: Page::default(),
             player_up: Player::WHITE, // behind by one
             turn: turn - 1,           // behind by one
+            board_checksum: None,
         };
 
         // set contents of fake history repo.
@@ -529,6 +803,8 @@ mod test {
             game_id,
             player_up,
             turn,
+            next_page: None,
+            snapshot: None,
         };
 
         let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
@@ -578,6 +854,9 @@ mod test {
             player_up: Player::BLACK,
             turn: bogus_client_turn,
             last_move: Some(bogus_client_move),
+            recent_moves: vec![],
+            page: Page::default(),
+            board_checksum: None,
         };
 
         // make sure fake history repo is configured
@@ -597,6 +876,8 @@ mod test {
             game_id,
             player_up,
             turn,
+            next_page: None,
+            snapshot: None,
         };
         let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
         assert_eq!(actual, expected)
@@ -646,8 +927,11 @@ mod test {
             session_id: session_id.clone(),
             req_id: req_id.clone(),
             last_move: client_last_move.clone(),
+            recent_moves: vec![],
+            page: Page::default(),
             turn: client_turn,
             player_up: client_player_up,
+            board_checksum: None,
         };
 
         let mut fakes: TestFakes = spawn_process_thread();
@@ -669,7 +953,7 @@ mod test {
             .as_ref()
             .expect("some")
             .clone();
-        assert_eq!(actual_req_saved, req_sync);
+        assert_eq!(actual_req_saved.req_sync, req_sync);
 
         // There should be an xadd to make-move-ev
         let actual_make_move = fakes.make_move_xadd_out.recv().expect("recv xadd");
@@ -704,11 +988,541 @@ mod test {
             game_id,
             player_up: client_player_up,
             turn: client_turn,
+            next_page: None,
+            snapshot: None,
         };
         let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
         assert_eq!(actual, expected)
     }
 
+    fn move_at(turn: u32, x: u16) -> Move {
+        Move {
+            player: if turn % 2 == 1 {
+                Player::BLACK
+            } else {
+                Player::WHITE
+            },
+            coord: Some(Coord { x, y: x }),
+            turn,
+        }
+    }
+
+    fn made_for(make_move: &MakeMove) -> MoveMade {
+        MoveMade {
+            game_id: make_move.game_id.clone(),
+            reply_to: make_move.req_id.clone(),
+            coord: make_move.coord,
+            player: make_move.player,
+            event_id: EventId::new(),
+            captured: Vec::new(),
+        }
+    }
+
+    /// Client is several moves ahead.  Its moves are made
+    /// one at a time, and it hears back once all are made.
+    #[test]
+    fn test_req_sync_server_catch_up_several() {
+        let client_moves: Vec<Move> = (1..=4).map(|t| move_at(t, t as u16)).collect();
+        let server_moves = client_moves[..1].to_vec();
+
+        let game_id = GameId::new();
+        let session_id = SessionId::new();
+        let req_id = ReqId::new();
+        let req_sync = ReqSync {
+            game_id: game_id.clone(),
+            session_id: session_id.clone(),
+            req_id: req_id.clone(),
+            last_move: client_moves.last().cloned(),
+            recent_moves: client_moves.clone(),
+            page: Page::default(),
+            turn: 5,
+            player_up: Player::BLACK,
+            board_checksum: None,
+        };
+
+        let mut fakes: TestFakes = spawn_process_thread();
+        *fakes.history_contents.lock().expect("lock") = Some(server_moves);
+        fakes.emit_sleep(StreamInput::RS(req_sync));
+
+        let mut req_ids = vec![];
+        for expected in &client_moves[1..] {
+            let make_move = fakes.make_move_xadd_out.recv().expect("recv xadd");
+            assert_eq!(make_move.player, expected.player);
+            assert_eq!(make_move.coord, expected.coord);
+            // nobody hears back while moves are in flight
+            assert!(fakes.sync_reply_xadd_out.try_recv().is_err());
+            req_ids.push(make_move.req_id.clone());
+            fakes.emit_sleep(StreamInput::MM(made_for(&make_move)));
+        }
+        assert_eq!(req_ids[0], req_id);
+        assert_ne!(req_ids[1], req_id);
+
+        let expected: SyncReply = SyncReply {
+            session_id,
+            reply_to: req_id,
+            moves: client_moves,
+            game_id,
+            player_up: Player::BLACK,
+            turn: 5,
+            next_page: None,
+            snapshot: None,
+        };
+        let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert_eq!(actual, expected);
+        assert!(fakes.make_move_xadd_out.try_recv().is_err());
+        assert!(fakes.reply_contents.lock().expect("lock").is_none());
+    }
+
+    /// The judge refuses one of the client's moves, so
+    /// the client gets the server view as it then stands.
+    #[test]
+    fn test_req_sync_server_catch_up_rejected() {
+        let client_moves: Vec<Move> = (1..=3).map(|t| move_at(t, t as u16)).collect();
+        let game_id = GameId::new();
+        let req_id = ReqId::new();
+        let req_sync = ReqSync {
+            game_id: game_id.clone(),
+            session_id: SessionId::new(),
+            req_id: req_id.clone(),
+            last_move: client_moves.last().cloned(),
+            recent_moves: client_moves.clone(),
+            page: Page::default(),
+            turn: 4,
+            player_up: Player::WHITE,
+            board_checksum: None,
+        };
+
+        let mut fakes: TestFakes = spawn_process_thread();
+        *fakes.history_contents.lock().expect("lock") = Some(vec![]);
+        fakes.emit_sleep(StreamInput::RS(req_sync));
+
+        let first = fakes.make_move_xadd_out.recv().expect("recv xadd");
+        fakes.emit_sleep(StreamInput::MM(made_for(&first)));
+        let second = fakes.make_move_xadd_out.recv().expect("recv xadd");
+        let xid_mr = fakes.emit_sleep(StreamInput::MR(MoveRejected {
+            game_id: game_id.clone(),
+            reply_to: second.req_id.clone(),
+            player: second.player,
+            coord: second.coord,
+            reason: RejectReason::Occupied,
+            event_id: EventId::new(),
+        }));
+        let mr_ack = fakes.acks.last_mr_ack_ms.load(Ordering::Relaxed);
+        assert_eq!(mr_ack, xid_mr.millis_time);
+
+        let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert_eq!(actual.reply_to, req_id);
+        assert_eq!(actual.moves, client_moves[..1].to_vec());
+        assert_eq!(actual.turn, 2);
+        assert!(fakes.make_move_xadd_out.try_recv().is_err())
+    }
+
+    /// Once the game is over, a client which is ahead
+    /// of the server only receives the server view.
+    /// No move is requested on its behalf.
+    #[test]
+    fn test_req_sync_after_game_over() {
+        let mut fakes = spawn_process_thread();
+
+        let server_moves = vec![
+            Move {
+                player: Player::BLACK,
+                coord: None,
+                turn: 1,
+            },
+            Move {
+                player: Player::WHITE,
+                coord: None,
+                turn: 2,
+            },
+        ];
+        let game_id = GameId::new();
+        let session_id = SessionId::new();
+        let req_id = ReqId::new();
+
+        *fakes.history_contents.lock().expect("lock") = Some(server_moves.clone());
+
+        let xid_go = fakes.emit_sleep(StreamInput::GO(GameOver {
+            game_id: game_id.clone(),
+            event_id: EventId::new(),
+            result: GameResult {
+                winner: Some(Player::WHITE),
+                reason: EndReason::Score,
+            },
+            score: Some(score(
+                &Board::default(),
+                &Captures::default(),
+                &Default::default(),
+                DEFAULT_KOMI,
+            )),
+        }));
+        let go_ack = fakes.acks.last_go_ack_ms.load(Ordering::Relaxed);
+        assert_eq!(go_ack, xid_go.millis_time);
+        assert!(fakes.game_over_contents.lock().expect("lock").is_some());
+
+        let req_sync = ReqSync {
+            game_id: game_id.clone(),
+            session_id: session_id.clone(),
+            req_id: req_id.clone(),
+            last_move: Some(Move {
+                player: Player::BLACK,
+                coord: Some(Coord { x: 4, y: 4 }),
+                turn: 3,
+            }),
+            recent_moves: vec![],
+            page: Page::default(),
+            turn: 4,
+            player_up: Player::WHITE,
+            board_checksum: None,
+        };
+        fakes.emit_sleep(StreamInput::RS(req_sync));
+
+        let expected = SyncReply {
+            session_id,
+            reply_to: req_id,
+            moves: server_moves,
+            game_id: game_id.clone(),
+            player_up: Player::BLACK,
+            turn: 3,
+            next_page: None,
+            snapshot: None,
+        };
+        let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert_eq!(actual, expected);
+        assert!(fakes.make_move_xadd_out.try_recv().is_err());
+        assert!(fakes.reply_contents.lock().expect("lock").is_none());
+
+        // history includes the final score
+        fakes.emit_sleep(StreamInput::PH(ProvideHistory {
+            game_id,
+            req_id: ReqId::new(),
+            page: Page::default(),
+        }));
+        let hp = fakes.hist_prov_xadd_out.recv().expect("recv");
+        assert_eq!(hp.score.map(|s| s.area.winner), Some(Some(Player::WHITE)));
+        assert_eq!(hp.result.and_then(|r| r.winner), Some(Player::WHITE))
+    }
+
+    #[test]
+    fn test_resigned_history_has_no_score() {
+        let mut fakes = spawn_process_thread();
+        let game_id = GameId::new();
+        *fakes.history_contents.lock().expect("lock") = Some(vec![]);
+        fakes.emit_sleep(StreamInput::GO(GameOver {
+            game_id: game_id.clone(),
+            event_id: EventId::new(),
+            result: GameResult {
+                winner: Some(Player::BLACK),
+                reason: EndReason::Resignation,
+            },
+            score: None,
+        }));
+        fakes.emit_sleep(StreamInput::PH(ProvideHistory {
+            game_id,
+            req_id: ReqId::new(),
+            page: Page::default(),
+        }));
+        let hp = fakes.hist_prov_xadd_out.recv().expect("recv");
+        assert!(hp.score.is_none());
+        assert_eq!(hp.result.map(|r| r.reason), Some(EndReason::Resignation))
+    }
+
+    /// Clients ask for a few moves at a time, and
+    /// follow the next page until there is none
+    #[test]
+    fn test_provide_history_paged() {
+        let mut fakes = spawn_process_thread();
+        let game_id = GameId::new();
+        let moves: Vec<Move> = (1..=5).map(|t| move_at(t, t as u16)).collect();
+        *fakes.history_contents.lock().expect("lock") = Some(moves.clone());
+
+        let mut page = Page {
+            since_turn: 1,
+            size: Some(3),
+        };
+        let mut received = vec![];
+        loop {
+            fakes.emit_sleep(StreamInput::PH(ProvideHistory {
+                game_id: game_id.clone(),
+                req_id: ReqId::new(),
+                page,
+            }));
+            let hp = fakes.hist_prov_xadd_out.recv().expect("recv");
+            assert!(hp.moves.len() <= 3);
+            received.extend(hp.moves);
+            match hp.next_page {
+                Some(next) => page = next,
+                None => break,
+            }
+        }
+        assert_eq!(received, moves[1..].to_vec());
+    }
+
+    /// A client which is behind hears only what it missed
+    #[test]
+    fn test_req_sync_delta() {
+        let mut fakes = spawn_process_thread();
+        let moves: Vec<Move> = (1..=4).map(|t| move_at(t, t as u16)).collect();
+        *fakes.history_contents.lock().expect("lock") = Some(moves.clone());
+        fakes.emit_sleep(StreamInput::RS(ReqSync {
+            game_id: GameId::new(),
+            session_id: SessionId::new(),
+            req_id: ReqId::new(),
+            last_move: Some(moves[1].clone()),
+            recent_moves: moves[..2].to_vec(),
+            page: Page {
+                since_turn: 2,
+                size: None,
+            },
+            turn: 3,
+            player_up: Player::BLACK,
+            board_checksum: None,
+        }));
+        let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert_eq!(actual.moves, moves[2..].to_vec());
+        assert_eq!(actual.next_page, None);
+        assert_eq!(actual.turn, 5);
+        assert!(fakes.make_move_xadd_out.try_recv().is_err())
+    }
+
+    /// A client whose moves disagree with ours hears the
+    /// whole game, whatever page it asked for
+    #[test]
+    fn test_req_sync_diverged() {
+        let mut fakes = spawn_process_thread();
+        let moves: Vec<Move> = (1..=4).map(|t| move_at(t, t as u16)).collect();
+        *fakes.history_contents.lock().expect("lock") = Some(moves.clone());
+        let mut client_moves = moves[..3].to_vec();
+        client_moves[1] = move_at(2, 9);
+        fakes.emit_sleep(StreamInput::RS(ReqSync {
+            game_id: GameId::new(),
+            session_id: SessionId::new(),
+            req_id: ReqId::new(),
+            last_move: client_moves.last().cloned(),
+            recent_moves: client_moves,
+            page: Page {
+                since_turn: 3,
+                size: None,
+            },
+            turn: 4,
+            player_up: Player::WHITE,
+            board_checksum: None,
+        }));
+        let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert_eq!(actual.moves, moves);
+        assert_eq!(actual.next_page, None);
+        assert_eq!(actual.turn, 5);
+        assert!(fakes.make_move_xadd_out.try_recv().is_err())
+    }
+
+    /// A client whose board has drifted from ours hears
+    /// the whole game and our board, whatever page it asked for
+    #[test]
+    fn test_req_sync_board_mismatch() {
+        let mut fakes = spawn_process_thread();
+        let moves: Vec<Move> = (1..=2).map(|t| move_at(t, t as u16)).collect();
+        let mut board = Board::default();
+        for m in &moves {
+            board.place(m.coord.unwrap(), m.player)
+        }
+        let snapshot = Snapshot {
+            board: board.clone(),
+            captures: Captures::default(),
+            player_up: Player::BLACK,
+        };
+        *fakes.history_contents.lock().expect("lock") = Some(moves.clone());
+        *fakes.snapshot_contents.lock().expect("lock") = Some(snapshot.clone());
+
+        let req_sync = |board_checksum| ReqSync {
+            game_id: GameId::new(),
+            session_id: SessionId::new(),
+            req_id: ReqId::new(),
+            last_move: Some(moves[1].clone()),
+            recent_moves: moves.clone(),
+            page: Page {
+                since_turn: 2,
+                size: None,
+            },
+            turn: 3,
+            player_up: Player::BLACK,
+            board_checksum,
+        };
+
+        fakes.emit_sleep(StreamInput::RS(req_sync(Some(snapshot.checksum()))));
+        let agreed = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert!(agreed.moves.is_empty());
+        assert_eq!(agreed.snapshot, None);
+
+        // the client lost track of a capture
+        let drifted = board.checksum(&Captures { black: 1, white: 0 });
+        fakes.emit_sleep(StreamInput::RS(req_sync(Some(drifted))));
+        let corrected = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert_eq!(corrected.moves, moves);
+        assert_eq!(corrected.next_page, None);
+        assert_eq!(corrected.snapshot, Some(snapshot));
+    }
+
+    /// White moves first once black's handicap stones are down
+    #[test]
+    fn test_req_sync_handicap_white_first() {
+        let mut fakes = spawn_process_thread();
+        let mut board = Board::default();
+        board.place(Coord { x: 3, y: 3 }, Player::BLACK);
+        board.place(Coord { x: 15, y: 15 }, Player::BLACK);
+        *fakes.snapshot_contents.lock().expect("lock") = Some(Snapshot {
+            board,
+            captures: Captures::default(),
+            player_up: Player::WHITE,
+        });
+
+        let req_sync = |recent_moves: Vec<Move>| ReqSync {
+            game_id: GameId::new(),
+            session_id: SessionId::new(),
+            req_id: ReqId::new(),
+            last_move: recent_moves.last().cloned(),
+            turn: recent_moves.len() as u32 + 1,
+            player_up: if recent_moves.is_empty() {
+                Player::WHITE
+            } else {
+                Player::BLACK
+            },
+            recent_moves,
+            page: Page::default(),
+            board_checksum: None,
+        };
+
+        fakes.emit_sleep(StreamInput::RS(req_sync(vec![])));
+        let actual = fakes.sync_reply_xadd_out.recv().expect("recv");
+        assert_eq!(actual.player_up, Player::WHITE);
+        assert_eq!(actual.turn, 1);
+
+        // the backend missed white's first move
+        let first = Move {
+            player: Player::WHITE,
+            coord: Some(Coord { x: 15, y: 3 }),
+            turn: 1,
+        };
+        fakes.emit_sleep(StreamInput::RS(req_sync(vec![first.clone()])));
+        let make_move = fakes.make_move_xadd_out.recv().expect("recv");
+        assert_eq!(make_move.player, Player::WHITE);
+        assert_eq!(make_move.coord, first.coord);
+    }
+
+    #[test]
+    fn test_play_resumed_clears_game_over() {
+        let mut fakes = spawn_process_thread();
+        let game_id = GameId::new();
+        let score = score(
+            &Board::default(),
+            &Captures::default(),
+            &Default::default(),
+            DEFAULT_KOMI,
+        );
+        fakes.emit_sleep(StreamInput::GO(GameOver {
+            game_id: game_id.clone(),
+            event_id: EventId::new(),
+            result: GameResult {
+                winner: score.area.winner,
+                reason: EndReason::Score,
+            },
+            score: Some(score),
+        }));
+        let xid_sf = fakes.emit_sleep(StreamInput::SF(ScoreFinalized {
+            game_id: game_id.clone(),
+            event_id: EventId::new(),
+            dead: vec![],
+            result: GameResult {
+                winner: score.area.winner,
+                reason: EndReason::Score,
+            },
+            score: FinalScore { komi: 0.5, ..score },
+        }));
+        assert_eq!(
+            fakes.acks.last_sf_ack_ms.load(Ordering::Relaxed),
+            xid_sf.millis_time
+        );
+        assert_eq!(
+            fakes
+                .game_over_contents
+                .lock()
+                .expect("lock")
+                .as_ref()
+                .and_then(|go| go.score.map(|s| s.komi)),
+            Some(0.5)
+        );
+
+        let xid_pr = fakes.emit_sleep(StreamInput::PR(PlayResumed {
+            game_id,
+            event_id: EventId::new(),
+            player: Player::BLACK,
+        }));
+        assert_eq!(
+            fakes.acks.last_pr_ack_ms.load(Ordering::Relaxed),
+            xid_pr.millis_time
+        );
+        assert!(fakes.game_over_contents.lock().expect("lock").is_none());
+    }
+
+    #[test]
+    fn test_provide_history_from_archive() {
+        let mut fakes = spawn_process_thread();
+        let game_id = GameId::new();
+        let req_id = ReqId::new();
+        fakes.emit_sleep(StreamInput::PH(ProvideHistory {
+            game_id: game_id.clone(),
+            req_id: req_id.clone(),
+            page: Page::default(),
+        }));
+        let pa = fakes.provide_archive_xadd_out.recv().expect("recv");
+        assert_eq!(pa.req_id, req_id);
+
+        let mut game_state =
+            GameState::new(game_id.clone(), BoardSize::default(), Rules::default(), 0);
+        game_state.moves.push(MoveMade {
+            game_id: game_id.clone(),
+            reply_to: ReqId::new(),
+            event_id: EventId::new(),
+            player: Player::BLACK,
+            coord: None,
+            captured: vec![],
+        });
+        let xid_ap = fakes.emit_sleep(StreamInput::AP(ArchiveProvided {
+            game_id,
+            reply_to: req_id.clone(),
+            game_state: Some(game_state),
+            score: None,
+            page: Page::default(),
+        }));
+        assert_eq!(
+            fakes.acks.last_ap_ack_ms.load(Ordering::Relaxed),
+            xid_ap.millis_time
+        );
+        let hp = fakes.hist_prov_xadd_out.recv().expect("recv");
+        assert_eq!(hp.reply_to, req_id);
+        assert_eq!(hp.moves.len(), 1)
+    }
+
+    /// A game which nobody knows of still gets an answer
+    #[test]
+    fn test_provide_history_unknown_game() {
+        let mut fakes = spawn_process_thread();
+        let game_id = GameId::new();
+        let req_id = ReqId::new();
+        fakes.emit_sleep(StreamInput::AP(ArchiveProvided {
+            game_id: game_id.clone(),
+            reply_to: req_id.clone(),
+            game_state: None,
+            score: None,
+            page: Page::default(),
+        }));
+        let hp = fakes.hist_prov_xadd_out.recv().expect("recv");
+        assert_eq!(hp.game_id, game_id);
+        assert_eq!(hp.reply_to, req_id);
+        assert!(hp.moves.is_empty());
+        assert_eq!(hp.next_page, None);
+        assert_eq!(hp.result, None)
+    }
+
     /// Test the ProvideHistory API
     #[test]
     fn test_provide_history() {
@@ -743,6 +1557,12 @@ mod test {
             captures: Captures::default(),
             game_id: fake_game_id.clone(),
             turn: 1,
+            ko: None,
+            rules: Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         }));
 
         // history repo should now contain the moves from that game
@@ -767,6 +1587,8 @@ mod test {
             },
         ];
         assert_eq!(actual_moves, expected_moves);
+        let snapshot = fakes.snapshot_contents.lock().expect("sr").clone();
+        assert_eq!(snapshot.map(|s| s.board), Some(Board::default()));
         // check ack for game_states stream
         let gs_ack = fakes.acks.last_gs_ack_ms.load(Ordering::Relaxed);
         assert_eq!(gs_ack, xid_gs.millis_time);
@@ -776,14 +1598,18 @@ mod test {
         let xid_ph = fakes.emit_sleep(StreamInput::PH(ProvideHistory {
             game_id: fake_game_id.clone(),
             req_id: fake_req_id.clone(),
+            page: Page::default(),
         }));
 
         // There should be an XADD triggered on history-provided stream
         select! {
             recv(fakes.hist_prov_xadd_out) -> msg => match msg {
-                Ok(HistoryProvided { game_id, reply_to, moves, event_id: _, epoch_millis: _, }) => {
+                Ok(HistoryProvided { game_id, reply_to, moves, next_page, event_id: _, epoch_millis: _, score, result }) => {
                     assert_eq!(game_id, fake_game_id);
                     assert_eq!(moves, expected_moves);
+                    assert_eq!(next_page, None);
+                    assert_eq!(score, None);
+                    assert_eq!(result, None);
                     assert_eq!(reply_to, fake_req_id);
                     // check ack for provide_history stream
                     let ph_ack = fakes.acks.last_ph_ack_ms.load(Ordering::Relaxed);
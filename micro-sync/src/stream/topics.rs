@@ -4,4 +4,10 @@ pub const HISTORY_PROVIDED: &str = "bugout-history-provided-ev";
 pub const REQ_SYNC: &str = "bugout-req-sync-cmd";
 pub const MAKE_MOVE: &str = "bugout-make-move-cmd";
 pub const MOVE_MADE: &str = "bugout-move-made-ev";
+pub const MOVE_REJECTED: &str = "bugout-move-rejected-ev";
+pub const GAME_OVER: &str = "bugout-game-over-ev";
+pub const SCORE_FINALIZED: &str = "bugout-score-finalized-ev";
+pub const PLAY_RESUMED: &str = "bugout-play-resumed-ev";
 pub const SYNC_REPLY: &str = "bugout-sync-reply-ev";
+pub const PROVIDE_ARCHIVE: &str = "bugout-provide-archive-cmd";
+pub const ARCHIVE_PROVIDED: &str = "bugout-archive-provided-ev";
@@ -3,12 +3,13 @@ use crate::move_model::{Coord, MakeMove};
 use redis::{streams::StreamMaxlen, Client, Commands};
 use std::collections::BTreeMap;
 use std::rc::Rc;
-use sync_model::api::{HistoryProvided, SyncReply};
+use sync_model::api::{HistoryProvided, ProvideArchive, SyncReply};
 
 pub trait XAdd {
     fn add_history_provided(&self, data: HistoryProvided) -> Result<(), XAddErr>;
     fn add_sync_reply(&self, data: SyncReply) -> Result<(), XAddErr>;
     fn add_make_move(&self, data: MakeMove) -> Result<(), XAddErr>;
+    fn add_provide_archive(&self, data: ProvideArchive) -> Result<(), XAddErr>;
 }
 
 #[derive(Debug)]
@@ -36,7 +37,7 @@ impl XAdd for Rc<Client> {
                     AUTO_ID,
                     m,
                 )
-                .map_err(|e| XAddErr::Redis(e))
+                .map_err(XAddErr::Redis)
             } else {
                 Err(XAddErr::Conn)
             }
@@ -58,7 +59,29 @@ impl XAdd for Rc<Client> {
                     AUTO_ID,
                     m,
                 )
-                .map_err(|e| XAddErr::Redis(e))
+                .map_err(XAddErr::Redis)
+            } else {
+                Err(XAddErr::Conn)
+            }
+        } else {
+            Err(XAddErr::Ser)
+        }
+    }
+
+    fn add_provide_archive(&self, data: ProvideArchive) -> Result<(), XAddErr> {
+        let ser_bytes_result = bincode::serialize(&data);
+
+        if let Ok(bytes) = ser_bytes_result {
+            let mut m: BTreeMap<&str, &[u8]> = BTreeMap::new();
+            m.insert(MAP_KEY, &bytes);
+            if let Ok(mut conn) = self.get_connection() {
+                conn.xadd_maxlen_map(
+                    topics::PROVIDE_ARCHIVE,
+                    StreamMaxlen::Approx(MAX_LEN),
+                    AUTO_ID,
+                    m,
+                )
+                .map_err(XAddErr::Redis)
             } else {
                 Err(XAddErr::Conn)
             }
@@ -17,6 +17,11 @@ pub trait XRead {
     fn ack_prov_hist(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_game_states(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_move_made(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_move_rejected(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_game_over(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_score_finalized(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_play_resumed(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_archive_provided(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
 }
 
 impl XRead for Rc<Client> {
@@ -31,8 +36,13 @@ impl XRead for Rc<Client> {
                     topics::PROVIDE_HISTORY,
                     topics::REQ_SYNC,
                     topics::MOVE_MADE,
+                    topics::MOVE_REJECTED,
+                    topics::GAME_OVER,
+                    topics::SCORE_FINALIZED,
+                    topics::PLAY_RESUMED,
+                    topics::ARCHIVE_PROVIDED,
                 ],
-                &[">", ">", ">", ">"],
+                &[">", ">", ">", ">", ">", ">", ">", ">", ">"],
                 opts,
             )?;
 
@@ -68,6 +78,21 @@ impl XRead for Rc<Client> {
     fn ack_move_made(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
         ack(&self, topics::MOVE_MADE, ids)
     }
+    fn ack_move_rejected(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::MOVE_REJECTED, ids)
+    }
+    fn ack_game_over(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(&self, topics::GAME_OVER, ids)
+    }
+    fn ack_score_finalized(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(&self, topics::SCORE_FINALIZED, ids)
+    }
+    fn ack_play_resumed(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(&self, topics::PLAY_RESUMED, ids)
+    }
+    fn ack_archive_provided(&self, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::ARCHIVE_PROVIDED, ids)
+    }
 }
 #[derive(Debug)]
 pub enum StreamReadErr {
@@ -88,6 +113,11 @@ pub struct Unacknowledged {
     prov_hist: Vec<XReadEntryId>,
     game_states: Vec<XReadEntryId>,
     move_made: Vec<XReadEntryId>,
+    move_rejected: Vec<XReadEntryId>,
+    game_over: Vec<XReadEntryId>,
+    score_finalized: Vec<XReadEntryId>,
+    play_resumed: Vec<XReadEntryId>,
+    archive_provided: Vec<XReadEntryId>,
 }
 
 impl Unacknowledged {
@@ -121,13 +151,56 @@ impl Unacknowledged {
                 self.move_made.clear();
             }
         }
+        if !self.move_rejected.is_empty() {
+            if let Err(_e) = components.xread.ack_move_rejected(&self.move_rejected) {
+                error!("ack for move rejected failed")
+            } else {
+                self.move_rejected.clear();
+            }
+        }
+        if !self.game_over.is_empty() {
+            if let Err(_e) = components.xread.ack_game_over(&self.game_over) {
+                error!("ack for game over failed")
+            } else {
+                self.game_over.clear();
+            }
+        }
+        if !self.score_finalized.is_empty() {
+            if let Err(_e) = components.xread.ack_score_finalized(&self.score_finalized) {
+                error!("ack for score finalized failed")
+            } else {
+                self.score_finalized.clear();
+            }
+        }
+        if !self.play_resumed.is_empty() {
+            if let Err(_e) = components.xread.ack_play_resumed(&self.play_resumed) {
+                error!("ack for play resumed failed")
+            } else {
+                self.play_resumed.clear();
+            }
+        }
+        if !self.archive_provided.is_empty() {
+            if let Err(_e) = components
+                .xread
+                .ack_archive_provided(&self.archive_provided)
+            {
+                error!("ack for archive provided failed")
+            } else {
+                self.archive_provided.clear();
+            }
+        }
     }
     pub fn push(&mut self, xid: XReadEntryId, event: StreamInput) {
         match event {
             StreamInput::GS(_) => self.game_states.push(xid),
             StreamInput::MM(_) => self.move_made.push(xid),
+            StreamInput::MR(_) => self.move_rejected.push(xid),
             StreamInput::PH(_) => self.prov_hist.push(xid),
             StreamInput::RS(_) => self.req_sync.push(xid),
+            StreamInput::GO(_) => self.game_over.push(xid),
+            StreamInput::SF(_) => self.score_finalized.push(xid),
+            StreamInput::PR(_) => self.play_resumed.push(xid),
+            StreamInput::AP(_) => self.archive_provided.push(xid),
         }
     }
 }
@@ -139,6 +212,11 @@ impl Default for Unacknowledged {
             req_sync: Vec::with_capacity(INIT_ACK_CAPACITY),
             game_states: Vec::with_capacity(INIT_ACK_CAPACITY),
             move_made: Vec::with_capacity(INIT_ACK_CAPACITY),
+            move_rejected: Vec::with_capacity(INIT_ACK_CAPACITY),
+            game_over: Vec::with_capacity(INIT_ACK_CAPACITY),
+            score_finalized: Vec::with_capacity(INIT_ACK_CAPACITY),
+            play_resumed: Vec::with_capacity(INIT_ACK_CAPACITY),
+            archive_provided: Vec::with_capacity(INIT_ACK_CAPACITY),
         }
     }
 }
@@ -159,7 +237,7 @@ fn deser(srr: StreamReadReply) -> Result<HashMap<XReadEntryId, StreamInput>, Str
                 let maybe_data: Option<Vec<u8>> = e.get("data");
                 if let Some(data) = maybe_data {
                     let sd: Option<StreamInput> = if key == topics::GAME_STATES_CHANGELOG {
-                        bincode::deserialize(&data)
+                        crate::move_model::GameState::from(&data)
                             .map(|gs| StreamInput::GS(gs))
                             .ok()
                     } else if key == topics::REQ_SYNC {
@@ -174,6 +252,22 @@ fn deser(srr: StreamReadReply) -> Result<HashMap<XReadEntryId, StreamInput>, Str
                         bincode::deserialize(&data)
                             .map(|mm| StreamInput::MM(mm))
                             .ok()
+                    } else if key == topics::MOVE_REJECTED {
+                        bincode::deserialize(&data).map(StreamInput::MR).ok()
+                    } else if key == topics::GAME_OVER {
+                        bincode::deserialize(&data)
+                            .map(|go| StreamInput::GO(go))
+                            .ok()
+                    } else if key == topics::SCORE_FINALIZED {
+                        bincode::deserialize(&data)
+                            .map(|sf| StreamInput::SF(sf))
+                            .ok()
+                    } else if key == topics::PLAY_RESUMED {
+                        bincode::deserialize(&data)
+                            .map(|pr| StreamInput::PR(pr))
+                            .ok()
+                    } else if key == topics::ARCHIVE_PROVIDED {
+                        bincode::deserialize(&data).map(StreamInput::AP).ok()
                     } else {
                         error!("Unknown key {}", key);
                         return Err(StreamDeserErr::DataDeser);
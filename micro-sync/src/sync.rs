@@ -1,6 +1,9 @@
-use crate::move_model::Player;
+use crate::core_model::ReqId;
+use crate::move_model::{MakeMove, MoveMade, Player};
 use crate::player::other_player;
-use sync_model::api::ReqSync;
+use serde_derive::{Deserialize, Serialize};
+use sync_model::api::{ReqSync, SyncReply};
+use sync_model::Move;
 
 /// Note that the check for last_move.is_some() makes
 /// sense.  If the player passed, we'll still see a `Move`,
@@ -15,9 +18,132 @@ pub fn is_client_ahead_by_one_turn(
         && req_sync.last_move.is_some()
 }
 
+/// The client's moves which the backend has not heard of,
+/// oldest first.  Empty unless the client is ahead and its
+/// recent moves agree with the history up to the backend's
+/// last move.  `first_player` moves first when the history
+/// is empty.
+pub fn missing_moves(req_sync: &ReqSync, history: &[Move], first_player: Player) -> Vec<Move> {
+    let system_last_move = history.last();
+    let system_turn = system_last_move.map(|m| m.turn).unwrap_or(0) + 1;
+    if req_sync.recent_moves.is_empty() {
+        // older clients only tell us their last move
+        let system_player_up = system_last_move
+            .map(|m| other_player(m.player))
+            .unwrap_or(first_player);
+        return if is_client_ahead_by_one_turn(req_sync, system_turn, system_player_up) {
+            req_sync.last_move.iter().cloned().collect()
+        } else {
+            vec![]
+        };
+    }
+
+    let recent = &req_sync.recent_moves;
+    let overlaps = history.is_empty() || recent.iter().any(|m| m.turn + 1 == system_turn);
+    let agrees = recent.iter().filter(|m| m.turn < system_turn).all(|m| {
+        match m.turn.checked_sub(1).map(|i| history.get(i as usize)) {
+            Some(Some(h)) => h.player == m.player && h.coord == m.coord,
+            _ => false,
+        }
+    });
+    let missing: Vec<Move> = recent
+        .iter()
+        .filter(|m| m.turn >= system_turn)
+        .cloned()
+        .collect();
+    let in_order = missing
+        .iter()
+        .enumerate()
+        .all(|(i, m)| m.turn == system_turn + i as u32);
+    if overlaps && agrees && in_order {
+        missing
+    } else {
+        vec![]
+    }
+}
+
+/// Whether a client which is missing none of the backend's
+/// moves cannot be brought back in line by them alone: it
+/// has a different move at a turn which both know, or moves
+/// beyond ours which could not be caught up.  Such a client
+/// needs the whole history, whatever page it asked for.
+pub fn diverged(req_sync: &ReqSync, history: &[Move]) -> bool {
+    req_sync
+        .recent_moves
+        .iter()
+        .chain(req_sync.last_move.iter())
+        .any(
+            |m| match m.turn.checked_sub(1).map(|i| history.get(i as usize)) {
+                Some(Some(h)) => h.player != m.player || h.coord != m.coord,
+                Some(None) => true,
+                None => false,
+            },
+        )
+}
+
+/// A client's moves which the backend missed, sent to the
+/// judge one at a time.  Each move must be made before the
+/// next one can be judged.  Kept in the reply repo under
+/// the request ID of the move in flight.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CatchUp {
+    /// Answered once every move is settled
+    pub req_sync: ReqSync,
+    /// The backend's history, and the moves made since
+    pub moves: Vec<Move>,
+    /// The client's moves still to be made, oldest first
+    pub pending: Vec<Move>,
+    /// Who moves first, if no one has moved yet
+    pub first_player: Player,
+}
+
+impl CatchUp {
+    /// Asks for the next pending move
+    pub fn make_move(&self, req_id: &ReqId) -> Option<MakeMove> {
+        self.pending.first().map(|m| MakeMove {
+            game_id: self.req_sync.game_id.clone(),
+            req_id: req_id.clone(),
+            player: m.player,
+            coord: m.coord,
+        })
+    }
+
+    /// Settles the move in flight
+    pub fn made(&mut self, move_made: &MoveMade) {
+        if !self.pending.is_empty() {
+            self.pending.remove(0);
+        }
+        let turn = self.moves.last().map(|m| m.turn).unwrap_or(0) + 1;
+        self.moves.push(Move {
+            player: move_made.player,
+            coord: move_made.coord,
+            turn,
+        })
+    }
+
+    /// The backend's view, once nothing is left in flight
+    pub fn reply(&self) -> SyncReply {
+        let last_move = self.moves.last();
+        let (moves, next_page) = self.req_sync.page.select(&self.moves);
+        SyncReply {
+            session_id: self.req_sync.session_id.clone(),
+            reply_to: self.req_sync.req_id.clone(),
+            game_id: self.req_sync.game_id.clone(),
+            player_up: last_move
+                .map(|m| other_player(m.player))
+                .unwrap_or(self.first_player),
+            turn: last_move.map(|m| m.turn).unwrap_or(0) + 1,
+            moves,
+            next_page,
+            snapshot: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::move_model::Coord;
     use core_model::*;
     use sync_model::*;
     #[test]
@@ -36,9 +162,12 @@ mod tests {
                 coord: None,
                 turn: 1,
             }),
+            recent_moves: vec![],
+            page: Page::default(),
             game_id: game_id.clone(),
             session_id: session_id.clone(),
             req_id: req_id.clone(),
+            board_checksum: None,
         };
         assert!(is_client_ahead_by_one_turn(
             &req,
@@ -63,11 +192,84 @@ mod tests {
                 coord: None,
                 turn: 1,
             }),
+            recent_moves: vec![],
+            page: Page::default(),
             game_id: game_id.clone(),
             session_id: session_id.clone(),
             req_id: req_id.clone(),
+            board_checksum: None,
         };
         let no = !is_client_ahead_by_one_turn(&req, system_turn, system_player_up);
         assert!(no)
     }
+
+    fn req_sync(recent_moves: Vec<Move>) -> ReqSync {
+        ReqSync {
+            player_up: Player::BLACK,
+            turn: 1,
+            last_move: recent_moves.last().cloned(),
+            recent_moves,
+            page: Page::default(),
+            game_id: GameId::new(),
+            session_id: SessionId::new(),
+            req_id: ReqId::new(),
+            board_checksum: None,
+        }
+    }
+
+    fn at(turn: u32, x: u16) -> Move {
+        Move {
+            player: if turn % 2 == 1 {
+                Player::BLACK
+            } else {
+                Player::WHITE
+            },
+            coord: Some(Coord { x, y: 0 }),
+            turn,
+        }
+    }
+
+    #[test]
+    fn missing_moves_after_common_prefix() {
+        let history = vec![at(1, 1), at(2, 2)];
+        let client = vec![at(2, 2), at(3, 3), at(4, 4)];
+        assert_eq!(
+            missing_moves(&req_sync(client), &history, Player::BLACK),
+            vec![at(3, 3), at(4, 4)]
+        );
+        assert_eq!(
+            missing_moves(&req_sync(vec![at(1, 1), at(2, 2)]), &history, Player::BLACK),
+            vec![]
+        );
+        assert_eq!(
+            missing_moves(&req_sync(vec![at(1, 1)]), &[], Player::BLACK),
+            vec![at(1, 1)]
+        );
+    }
+
+    #[test]
+    fn diverged_from_history() {
+        let history = vec![at(1, 1), at(2, 2)];
+        assert!(!diverged(&req_sync(vec![at(1, 1), at(2, 2)]), &history));
+        assert!(!diverged(&req_sync(vec![at(1, 1)]), &history));
+        assert!(!diverged(&req_sync(vec![]), &history));
+        // a different move at turn 2
+        assert!(diverged(&req_sync(vec![at(1, 1), at(2, 5)]), &history));
+        // a move we never heard of, which was not caught up
+        assert!(diverged(&req_sync(vec![at(2, 2), at(3, 3)]), &history));
+    }
+
+    #[test]
+    fn missing_moves_which_disagree() {
+        let history = vec![at(1, 1), at(2, 2)];
+        // a different move at turn 2
+        let client = vec![at(2, 5), at(3, 3)];
+        assert!(missing_moves(&req_sync(client), &history, Player::BLACK).is_empty());
+        // nothing to compare with the backend's last move
+        let client = vec![at(3, 3), at(4, 4)];
+        assert!(missing_moves(&req_sync(client), &history, Player::BLACK).is_empty());
+        // a gap in the client's moves
+        let client = vec![at(2, 2), at(3, 3), at(5, 5)];
+        assert!(missing_moves(&req_sync(client), &history, Player::BLACK).is_empty());
+    }
 }
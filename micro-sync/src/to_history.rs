@@ -54,6 +54,12 @@ mod tests {
             game_id: fake_game_id,
             board: Board::default(),
             turn: 1,
+            ko: None,
+            rules: Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         };
 
         let actual = game_state.to_history();
@@ -11,6 +11,15 @@ pub struct ReqSync {
     pub player_up: Player,
     pub turn: u32,
     pub last_move: Option<Move>,
+    /// The client's latest moves, oldest first, ending
+    /// with `last_move`
+    pub recent_moves: Vec<Move>,
+    /// Which moves to send back
+    pub page: Page,
+    /// The client's board and captures, as `Board::checksum`
+    /// computes them.  When it disagrees with ours, the reply
+    /// carries every move and a snapshot of the board.
+    pub board_checksum: Option<u64>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -20,13 +29,20 @@ pub struct SyncReply {
     pub game_id: GameId,
     pub player_up: Player,
     pub turn: u32,
+    /// Only the moves on the requested page
     pub moves: Vec<Move>,
+    /// Present when moves are left after this page
+    pub next_page: Option<Page>,
+    /// Present when the client's board checksum did not
+    /// match ours
+    pub snapshot: Option<Snapshot>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ProvideHistory {
     pub game_id: GameId,
     pub req_id: ReqId,
+    pub page: Page,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -34,6 +50,35 @@ pub struct HistoryProvided {
     pub game_id: GameId,
     pub reply_to: ReqId,
     pub event_id: EventId,
+    /// Only the moves on the requested page
     pub moves: Vec<Move>,
+    /// Present when moves are left after this page
+    pub next_page: Option<Page>,
     pub epoch_millis: u64,
+    /// Present once the game is over, unless it ended by resignation
+    pub score: Option<FinalScore>,
+    /// Present once the game is over
+    pub result: Option<GameResult>,
+}
+
+/// Asks the archive for a finished game whose history
+/// has already expired from redis.  Emitted by micro-sync.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProvideArchive {
+    pub game_id: GameId,
+    pub req_id: ReqId,
+    /// The page of history which was asked for
+    pub page: Page,
+}
+
+/// The archive's answer, emitted by the archive service
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArchiveProvided {
+    pub game_id: GameId,
+    pub reply_to: ReqId,
+    /// Absent when the archive has never seen the game finish
+    pub game_state: Option<GameState>,
+    pub score: Option<FinalScore>,
+    /// As given in the request
+    pub page: Page,
 }
@@ -4,11 +4,140 @@ pub use move_model;
 pub mod api;
 
 use serde_derive::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
-use move_model::{Coord, Player};
+use move_model::{Board, Captures, Coord, Player};
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct Move {
     pub player: Player,
     pub coord: Option<Coord>,
     pub turn: u32,
 }
+
+/// Which of a game's moves to send.  The default asks
+/// for all of them.  A page which holds no moves is
+/// refused when read.
+#[derive(Clone, Copy, Default, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(try_from = "UncheckedPage")]
+pub struct Page {
+    /// Only moves after this turn
+    pub since_turn: u32,
+    /// At most this many moves, if given
+    pub size: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct UncheckedPage {
+    since_turn: u32,
+    size: Option<u32>,
+}
+
+impl TryFrom<UncheckedPage> for Page {
+    type Error = String;
+    fn try_from(page: UncheckedPage) -> Result<Self, Self::Error> {
+        match page.size {
+            Some(0) => Err("a page must hold at least one move".to_string()),
+            size => Ok(Page {
+                since_turn: page.since_turn,
+                size,
+            }),
+        }
+    }
+}
+
+impl Page {
+    /// The moves on this page, and the page to ask for
+    /// next if any moves are left over
+    pub fn select(&self, moves: &[Move]) -> (Vec<Move>, Option<Page>) {
+        let after: Vec<&Move> = moves.iter().filter(|m| m.turn > self.since_turn).collect();
+        let take = self
+            .size
+            .map(|s| s as usize)
+            .unwrap_or(after.len())
+            .min(after.len());
+        let selected: Vec<Move> = after[..take].iter().map(|m| (*m).clone()).collect();
+        let next = match selected.last() {
+            Some(last) if take < after.len() => Some(Page {
+                since_turn: last.turn,
+                size: self.size,
+            }),
+            _ => None,
+        };
+        (selected, next)
+    }
+}
+
+/// The backend's board, sent to a client whose own
+/// board does not agree with it
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct Snapshot {
+    pub board: Board,
+    pub captures: Captures,
+    /// Whose turn it is.  White moves first in a
+    /// handicap game.
+    pub player_up: Player,
+}
+
+impl Snapshot {
+    pub fn checksum(&self) -> u64 {
+        self.board.checksum(&self.captures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_pages() {
+        let moves: Vec<Move> = (1..=5)
+            .map(|turn| Move {
+                player: if turn % 2 == 1 {
+                    Player::BLACK
+                } else {
+                    Player::WHITE
+                },
+                coord: None,
+                turn,
+            })
+            .collect();
+
+        assert_eq!(Page::default().select(&moves), (moves.clone(), None));
+
+        let first = Page {
+            since_turn: 1,
+            size: Some(2),
+        };
+        let (selected, next) = first.select(&moves);
+        assert_eq!(selected, moves[1..3].to_vec());
+        let next = next.expect("more moves");
+        assert_eq!(next.since_turn, 3);
+        assert_eq!(next.select(&moves), (moves[3..].to_vec(), None));
+
+        let caught_up = Page {
+            since_turn: 5,
+            size: None,
+        };
+        assert_eq!(caught_up.select(&moves), (vec![], None));
+    }
+
+    #[test]
+    fn refuse_empty_pages() {
+        let empty = UncheckedPage {
+            since_turn: 3,
+            size: Some(0),
+        };
+        assert!(Page::try_from(empty).is_err());
+        let one = UncheckedPage {
+            since_turn: 3,
+            size: Some(1),
+        };
+        assert_eq!(
+            Page::try_from(one),
+            Ok(Page {
+                since_turn: 3,
+                size: Some(1)
+            })
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use super::*;
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+
+const DEFAULT_BOARD_SIZE: u16 = 19;
+
+/// The dimensions of a board.  Boards are usually
+/// square, but small rectangular boards are handy
+/// for teaching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BoardSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl BoardSize {
+    pub fn square(size: u16) -> Self {
+        BoardSize {
+            width: size,
+            height: size,
+        }
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    fn points(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+}
+
+impl Default for BoardSize {
+    fn default() -> Self {
+        BoardSize::square(DEFAULT_BOARD_SIZE)
+    }
+}
+
+/// The stones on the board, stored one point per cell
+/// in row-major order.  A Zobrist hash of the position is
+/// kept up to date as stones are placed and removed, so
+/// that positions can be compared cheaply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "PackedBoard", into = "PackedBoard")]
+pub struct Board {
+    pub size: BoardSize,
+    points: Vec<Option<Player>>,
+    zobrist: u64,
+}
+
+impl Board {
+    pub fn new(size: BoardSize) -> Self {
+        Board {
+            size,
+            points: vec![None; size.points()],
+            zobrist: 0,
+        }
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<Player> {
+        self.index(coord).and_then(|i| self.points[i])
+    }
+
+    /// Puts a stone on the board, replacing whatever was
+    /// there.  Points off the board are ignored.
+    pub fn place(&mut self, coord: Coord, player: Player) {
+        if let Some(i) = self.index(coord) {
+            if let Some(old) = self.points[i] {
+                self.zobrist ^= zobrist_key(coord, old)
+            }
+            self.points[i] = Some(player);
+            self.zobrist ^= zobrist_key(coord, player)
+        }
+    }
+
+    /// Takes a stone off the board, returning its color
+    pub fn remove(&mut self, coord: Coord) -> Option<Player> {
+        let i = self.index(coord)?;
+        let old = self.points[i].take()?;
+        self.zobrist ^= zobrist_key(coord, old);
+        Some(old)
+    }
+
+    /// All stones on the board, in row-major order
+    pub fn pieces(&self) -> impl Iterator<Item = (Coord, Player)> + '_ {
+        let width = self.size.width as usize;
+        self.points
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, p)| p.map(|p| (point(i, width), p)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.iter().all(Option::is_none)
+    }
+
+    /// Identifies the position.  Equal positions always have
+    /// equal hashes, regardless of the order of play.
+    pub fn zobrist(&self) -> u64 {
+        self.zobrist
+    }
+
+    /// Identifies the position together with the prisoners
+    /// each player holds.  Clients compute the same value
+    /// from their own board to check that it agrees with ours.
+    pub fn checksum(&self, captures: &Captures) -> u64 {
+        self.zobrist ^ splitmix64(1 << 63 | (captures.black as u64) << 16 | captures.white as u64)
+    }
+
+    fn index(&self, coord: Coord) -> Option<usize> {
+        if self.size.contains(coord) {
+            Some(coord.y as usize * self.size.width as usize + coord.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new(BoardSize::default())
+    }
+}
+
+/// Collects stones onto a board of the default size
+impl FromIterator<(Coord, Player)> for Board {
+    fn from_iter<I: IntoIterator<Item = (Coord, Player)>>(iter: I) -> Self {
+        let mut board = Board::default();
+        for (c, p) in iter {
+            board.place(c, p)
+        }
+        board
+    }
+}
+
+fn point(index: usize, width: usize) -> Coord {
+    Coord::of((index % width) as u16, (index / width) as u16)
+}
+
+/// A fixed pseudorandom key for each point and color,
+/// derived with splitmix64 so that every service agrees
+/// on the hash of a position without sharing a table
+fn zobrist_key(coord: Coord, player: Player) -> u64 {
+    let color = match player {
+        Player::BLACK => 0,
+        Player::WHITE => 1,
+    };
+    splitmix64((coord.x as u64) << 32 | (coord.y as u64) << 1 | color)
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const EMPTY: u8 = 0;
+const BLACK: u8 = 1;
+const WHITE: u8 = 2;
+
+/// The serialized form of a board.  Each point takes two
+/// bits, so a full 19x19 board fits in 91 bytes.
+#[derive(Serialize, Deserialize)]
+struct PackedBoard {
+    size: BoardSize,
+    stones: Vec<u8>,
+}
+
+impl From<Board> for PackedBoard {
+    fn from(board: Board) -> Self {
+        let mut stones = vec![0; board.points.len().div_ceil(4)];
+        for (i, p) in board.points.iter().enumerate() {
+            let bits = match p {
+                None => EMPTY,
+                Some(Player::BLACK) => BLACK,
+                Some(Player::WHITE) => WHITE,
+            };
+            stones[i / 4] |= bits << (2 * (i % 4))
+        }
+        PackedBoard {
+            size: board.size,
+            stones,
+        }
+    }
+}
+
+impl TryFrom<PackedBoard> for Board {
+    type Error = String;
+    fn try_from(packed: PackedBoard) -> Result<Self, Self::Error> {
+        let points = packed.size.points();
+        if packed.stones.len() != points.div_ceil(4) {
+            return Err(format!(
+                "{} bytes cannot hold a board of size {:?}",
+                packed.stones.len(),
+                packed.size
+            ));
+        }
+        let mut board = Board::new(packed.size);
+        for i in 0..points {
+            let coord = point(i, packed.size.width as usize);
+            match (packed.stones[i / 4] >> (2 * (i % 4))) & 0b11 {
+                EMPTY => (),
+                BLACK => board.place(coord, Player::BLACK),
+                WHITE => board.place(coord, Player::WHITE),
+                _ => return Err(format!("invalid point at {:?}", coord)),
+            }
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_follows_position() {
+        let mut a = Board::default();
+        let mut b = Board::default();
+        assert_eq!(a.zobrist(), 0);
+        a.place(Coord::of(3, 3), Player::BLACK);
+        a.place(Coord::of(15, 15), Player::WHITE);
+        b.place(Coord::of(15, 15), Player::WHITE);
+        b.place(Coord::of(3, 3), Player::BLACK);
+        assert_eq!(a.zobrist(), b.zobrist());
+
+        a.place(Coord::of(4, 4), Player::BLACK);
+        assert_ne!(a.zobrist(), b.zobrist());
+        assert_eq!(a.remove(Coord::of(4, 4)), Some(Player::BLACK));
+        assert_eq!(a.zobrist(), b.zobrist());
+
+        a.place(Coord::of(3, 3), Player::WHITE);
+        b.remove(Coord::of(3, 3));
+        b.place(Coord::of(3, 3), Player::WHITE);
+        assert_eq!(a, b)
+    }
+
+    #[test]
+    fn checksum_counts_captures() {
+        let mut board = Board::default();
+        board.place(Coord::of(3, 3), Player::BLACK);
+        let none = Captures::default();
+        let one = Captures { black: 1, white: 0 };
+        assert_ne!(board.checksum(&none), board.checksum(&one));
+        assert_ne!(
+            board.checksum(&one),
+            board.checksum(&Captures { black: 0, white: 1 })
+        );
+        // browser clients compute the same value; see gtp.js
+        assert_eq!(format!("{:x}", board.checksum(&one)), "54b3fe25d730eb05")
+    }
+
+    #[test]
+    fn off_board_ignored() {
+        let mut board = Board::new(BoardSize::square(9));
+        board.place(Coord::of(9, 0), Player::BLACK);
+        assert!(board.is_empty());
+        assert_eq!(board.get(Coord::of(9, 0)), None);
+        assert_eq!(board.remove(Coord::of(0, 9)), None)
+    }
+
+    #[test]
+    fn pieces_roundtrip() {
+        let stones = vec![
+            (Coord::of(0, 0), Player::BLACK),
+            (Coord::of(18, 0), Player::WHITE),
+            (Coord::of(5, 7), Player::BLACK),
+            (Coord::of(18, 18), Player::WHITE),
+        ];
+        let board: Board = stones.iter().cloned().collect();
+        assert_eq!(board.pieces().collect::<Vec<_>>(), stones);
+        assert_eq!(board.get(Coord::of(5, 7)), Some(Player::BLACK))
+    }
+
+    #[test]
+    fn rectangular_points() {
+        let mut board = Board::new(BoardSize {
+            width: 7,
+            height: 5,
+        });
+        board.place(Coord::of(6, 4), Player::WHITE);
+        board.place(Coord::of(4, 6), Player::BLACK);
+        assert_eq!(
+            board.pieces().collect::<Vec<_>>(),
+            vec![(Coord::of(6, 4), Player::WHITE)]
+        )
+    }
+
+    #[test]
+    fn packed_serde_roundtrip() {
+        let mut board = Board::new(BoardSize {
+            width: 9,
+            height: 7,
+        });
+        board.place(Coord::of(8, 6), Player::WHITE);
+        board.place(Coord::of(2, 6), Player::BLACK);
+        let bytes = bincode::serialize(&board).unwrap();
+        assert!(bytes.len() < 32);
+        let back: Board = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back, board);
+        assert_eq!(back.zobrist(), board.zobrist())
+    }
+}
@@ -1,138 +1,114 @@
-use move_model::*;
+use super::*;
 use std::collections::HashSet;
 
 pub fn captures_for(player: Player, placement: Coord, board: &Board) -> HashSet<Coord> {
-    let enemy_neighbors: HashSet<(Coord, Player)> = neighbor_pieces(placement, board)
-        .iter()
-        .filter(|(_, pp)| player != *pp)
-        .cloned()
-        .collect();
     let mut h = HashSet::new();
-    for (target, _) in enemy_neighbors {
-        if dead_from(target, placement, board) {
-            for i in connected(target, board) {
-                h.insert(i);
-            }
+    for (target, _) in neighbor_pieces(placement, board)
+        .into_iter()
+        .filter(|(_, pp)| player != *pp)
+    {
+        if !h.contains(&target) && dead_from(target, placement, board) {
+            h.extend(connected(target, board))
         }
     }
     h
 }
 
+/// Return the player's own stones, including the placement,
+/// which would be left without liberties by a move that
+/// captures nothing.  Empty unless the move is suicide.
+pub fn suicide_for(player: Player, placement: Coord, board: &Board) -> HashSet<Coord> {
+    if !captures_for(player, placement, board).is_empty() {
+        return HashSet::new();
+    }
+    let mut after = board.clone();
+    after.place(placement, player);
+    if liberties(placement, &after).is_empty() {
+        connected(placement, &after)
+    } else {
+        HashSet::new()
+    }
+}
+
+/// Return all stones removed from the board by the placement,
+/// whether they are the opponent's captures or the player's
+/// own suicided group
+pub fn removed_by(player: Player, placement: Coord, board: &Board) -> HashSet<Coord> {
+    let captures = captures_for(player, placement, board);
+    if captures.is_empty() {
+        suicide_for(player, placement, board)
+    } else {
+        captures
+    }
+}
+
 /// Return all open spaces connected to the target piece's formation
 fn liberties(target: Coord, board: &Board) -> HashSet<Coord> {
     let mut h = HashSet::new();
-    for c in connected(target, &board) {
-        for s in neighbor_spaces(c, board) {
-            h.insert(s);
-        }
+    for c in connected(target, board) {
+        h.extend(neighbor_spaces(c, board))
     }
     h
 }
 
-fn neighbors(target: Coord, board: &Board) -> HashSet<(Coord, Option<Player>)> {
-    const OFFSETS: [(i16, i16); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    let mut h = HashSet::new();
-    let coords = OFFSETS
-        .iter()
-        .map(|(x, y)| (x + target.x as i16, y + target.y as i16))
-        .filter(|(x, y)| {
-            !(x < &0 || x >= &(board.size as i16) || y < &0 || y >= &(board.size as i16))
-        })
-        .map(|(x, y)| Coord {
-            x: x as u16,
-            y: y as u16,
-        });
-    for coord in coords {
-        h.insert((coord, board.pieces.get(&coord).map(|p| *p)));
-    }
-    h
+fn neighbors(target: Coord, board: &Board) -> Vec<(Coord, Option<Player>)> {
+    super::neighbors(target, board.size)
+        .into_iter()
+        .map(|c| (c, board.get(c)))
+        .collect()
 }
 
 /// Return neighboring empty spaces
-fn neighbor_spaces(target: Coord, board: &Board) -> HashSet<Coord> {
+fn neighbor_spaces(target: Coord, board: &Board) -> Vec<Coord> {
     neighbors(target, board)
-        .iter()
-        .filter_map(|(coord, player)| {
-            if let Some(_) = player {
-                None
-            } else {
-                Some(coord)
-            }
-        })
-        .cloned()
+        .into_iter()
+        .filter(|(_, player)| player.is_none())
+        .map(|(coord, _)| coord)
         .collect()
 }
 
 /// Return neighbor pieces on all sides of the target
-fn neighbor_pieces(target: Coord, board: &Board) -> HashSet<(Coord, Player)> {
-    let mut h = HashSet::new();
-    for pair in neighbors(target, board)
-        .iter()
-        .filter_map(|(coord, player)| {
-            if let Some(p) = player {
-                Some((coord.clone(), p.clone()))
-            } else {
-                None
-            }
-        })
-    {
-        h.insert(pair);
-    }
-    h
+fn neighbor_pieces(target: Coord, board: &Board) -> Vec<(Coord, Player)> {
+    neighbors(target, board)
+        .into_iter()
+        .filter_map(|(coord, player)| player.map(|p| (coord, p)))
+        .collect()
 }
 
+/// True when the placement fills the target group's last liberty
 fn dead_from(target: Coord, placement: Coord, board: &Board) -> bool {
-    liberties(target, board) == [placement].iter().cloned().collect()
+    let mut seen = HashSet::new();
+    seen.insert(target);
+    let mut frontier = vec![target];
+    let player = board.get(target);
+    while let Some(c) = frontier.pop() {
+        for (n, p) in neighbors(c, board) {
+            if p.is_none() && n != placement {
+                return false;
+            }
+            if p == player && seen.insert(n) {
+                frontier.push(n)
+            }
+        }
+    }
+    true
 }
 
 /// Return all pieces of the same color, connected to the target.  Includes the target itself.
-fn connected(target: Coord, board: &Board) -> HashSet<Coord> {
-    fn same_color_pieces(player: Player, targs: &HashSet<Coord>, board: &Board) -> Vec<Coord> {
-        targs
-            .iter()
-            .filter_map(|c| {
-                let found = board.pieces.get(c);
-                found.map(|f| (c, f))
-            })
-            .filter(|(_, p)| **p == player)
-            .map(|(c, _)| *c)
-            .collect()
-    }
-    fn same_color_neighbors(player: Player, ps: &Vec<Coord>, board: &Board) -> HashSet<Coord> {
-        let mut r = HashSet::new();
-        for coord in ps {
-            for (npc, npp) in neighbor_pieces(*coord, board) {
-                if npp == player {
-                    r.insert(npc);
+pub fn connected(target: Coord, board: &Board) -> HashSet<Coord> {
+    let mut acc = HashSet::new();
+    if let Some(player) = board.get(target) {
+        acc.insert(target);
+        let mut frontier = vec![target];
+        while let Some(c) = frontier.pop() {
+            for (n, p) in neighbor_pieces(c, board) {
+                if p == player && acc.insert(n) {
+                    frontier.push(n)
                 }
             }
         }
-        r
-    }
-    if let Some(player) = board.pieces.get(&target) {
-        let mut acc: HashSet<Coord> = {
-            let mut hh = HashSet::new();
-            hh.insert(target);
-            hh
-        };
-        let mut targets: HashSet<Coord> = acc.clone();
-
-        loop {
-            let scns =
-                same_color_neighbors(*player, &same_color_pieces(*player, &targets, board), board);
-
-            if acc.is_superset(&scns) {
-                break;
-            } else {
-                let next_acc = acc.union(&scns);
-                targets = scns.clone();
-                acc = next_acc.cloned().collect();
-            }
-        }
-        acc
-    } else {
-        HashSet::new()
     }
+    acc
 }
 
 #[cfg(test)]
@@ -158,12 +134,11 @@ mod tests {
         .cloned()
         .collect();
 
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
 
-        let actual = neighbor_pieces(Coord { x: 1, y: 1 }, &board);
+        let actual: HashSet<(Coord, Player)> = neighbor_pieces(Coord { x: 1, y: 1 }, &board)
+            .into_iter()
+            .collect();
 
         let expected: HashSet<(Coord, Player)> = [
             (Coord { x: 1, y: 0 }, Player::BLACK),
@@ -197,12 +172,11 @@ mod tests {
         .cloned()
         .collect();
 
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
 
-        let actual = neighbor_pieces(Coord { x: 0, y: 1 }, &board);
+        let actual: HashSet<(Coord, Player)> = neighbor_pieces(Coord { x: 0, y: 1 }, &board)
+            .into_iter()
+            .collect();
 
         let expected: HashSet<(Coord, Player)> = [
             (Coord { x: 0, y: 0 }, Player::BLACK),
@@ -233,11 +207,10 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
-        let actual = neighbor_pieces(Coord::of(4, 4), &board);
+        let board: Board = pieces.into_iter().collect();
+        let actual: HashSet<(Coord, Player)> = neighbor_pieces(Coord::of(4, 4), &board)
+            .into_iter()
+            .collect();
         let mut expected = HashSet::new();
         expected.insert((Coord::of(4, 3), Player::BLACK));
         assert_eq!(expected, actual)
@@ -264,10 +237,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces: pieces.clone(),
-            ..Default::default()
-        };
+        let board: Board = pieces.clone().into_iter().collect();
         let actual = connected(Coord::of(1, 4), &board);
         let mut expected: HashSet<Coord> = HashSet::new();
         for (c, p) in pieces {
@@ -300,10 +270,7 @@ mod tests {
         .cloned()
         .collect();
 
-        let board = Board {
-            pieces: pieces.clone(),
-            ..Default::default()
-        };
+        let board: Board = pieces.clone().into_iter().collect();
         let actual = connected(Coord { x: 1, y: 0 }, &board);
         let e1: Vec<(Coord, Player)> = pieces
             .iter()
@@ -329,10 +296,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
         let actual = connected(Coord { x: 4, y: 4 }, &board);
         let expected: HashSet<Coord> = HashSet::new();
         assert_eq!(expected, actual)
@@ -360,10 +324,7 @@ mod tests {
         .cloned()
         .collect();
 
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
 
         let actual = liberties(Coord { x: 1, y: 3 }, &board);
         let expected: HashSet<Coord> = [
@@ -403,10 +364,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
         let actual = liberties(Coord { x: 0, y: 0 }, &board);
         let expected: HashSet<Coord> = [Coord { x: 3, y: 0 }, Coord { x: 3, y: 1 }]
             .iter()
@@ -434,10 +392,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
         let actual = captures_for(Player::WHITE, Coord { x: 3, y: 1 }, &board);
 
         let expected: HashSet<Coord> = [
@@ -470,10 +425,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
         let actual = captures_for(Player::WHITE, Coord::of(3, 1), &board);
         let expected: HashSet<Coord> = HashSet::new();
         assert_eq!(expected, actual);
@@ -506,10 +458,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces: pieces.clone(),
-            ..Default::default()
-        };
+        let board: Board = pieces.clone().into_iter().collect();
         let actual = captures_for(Player::BLACK, Coord::of(1, 6), &board);
         let mut expected = HashSet::new();
         for (coo, pla) in pieces {
@@ -529,10 +478,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
         let actual = captures_for(Player::WHITE, Coord::of(17, 18), &board);
         let mut expected = HashSet::new();
         expected.insert(Coord::of(18, 18));
@@ -548,10 +494,7 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
         let actual = liberties(Coord::of(18, 18), &board);
         let mut expected = HashSet::new();
         expected.insert(Coord::of(17, 18));
@@ -567,13 +510,72 @@ mod tests {
         .iter()
         .cloned()
         .collect();
-        let board = Board {
-            pieces,
-            ..Default::default()
-        };
+        let board: Board = pieces.into_iter().collect();
         let actual = connected(Coord::of(18, 18), &board);
         let mut expected = HashSet::new();
         expected.insert(Coord::of(18, 18));
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    fn single_stone_suicide() {
+        let pieces: HashMap<Coord, Player> = [
+            (Coord::of(1, 0), Player::BLACK),
+            (Coord::of(0, 1), Player::BLACK),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let board: Board = pieces.into_iter().collect();
+        let actual = suicide_for(Player::WHITE, Coord::of(0, 0), &board);
+        let mut expected = HashSet::new();
+        expected.insert(Coord::of(0, 0));
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn multi_stone_suicide() {
+        let pieces: HashMap<Coord, Player> = [
+            (Coord::of(0, 0), Player::WHITE),
+            (Coord::of(2, 0), Player::BLACK),
+            (Coord::of(0, 1), Player::BLACK),
+            (Coord::of(1, 1), Player::BLACK),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let board: Board = pieces.into_iter().collect();
+        let actual = removed_by(Player::WHITE, Coord::of(1, 0), &board);
+        let expected: HashSet<Coord> = [Coord::of(0, 0), Coord::of(1, 0)].iter().cloned().collect();
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn capturing_is_not_suicide() {
+        let pieces: HashMap<Coord, Player> = [
+            (Coord::of(18, 18), Player::BLACK),
+            (Coord::of(18, 17), Player::WHITE),
+            (Coord::of(16, 18), Player::BLACK),
+            (Coord::of(17, 17), Player::BLACK),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let board: Board = pieces.into_iter().collect();
+        assert!(suicide_for(Player::WHITE, Coord::of(17, 18), &board).is_empty());
+        let mut expected = HashSet::new();
+        expected.insert(Coord::of(18, 18));
+        assert_eq!(
+            expected,
+            removed_by(Player::WHITE, Coord::of(17, 18), &board)
+        )
+    }
+
+    #[test]
+    fn filling_a_liberty_is_not_suicide() {
+        let pieces: HashMap<Coord, Player> =
+            [(Coord::of(1, 0), Player::BLACK)].iter().cloned().collect();
+        let board: Board = pieces.into_iter().collect();
+        assert!(suicide_for(Player::WHITE, Coord::of(0, 0), &board).is_empty())
+    }
 }
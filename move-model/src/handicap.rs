@@ -0,0 +1,189 @@
+use super::*;
+use std::collections::HashSet;
+
+/// The largest handicap, placed or free
+pub const MAX_HANDICAP: u8 = 9;
+
+/// Black's extra stones, asked for either as a count to be
+/// placed on the star points, or as the points black chose.
+/// Clients send a number for the one and a list of points
+/// for the other.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Handicap {
+    Fixed(u8),
+    Free(Vec<Coord>),
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Handicap::Fixed(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InvalidHandicap {
+    TooMany,
+    /// A single free stone is no handicap at all
+    TooFew,
+    /// Only 9x9, 13x13 and 19x19 boards have star points
+    NoFixedPlacement,
+    OffBoard,
+    Repeated,
+}
+
+impl Handicap {
+    /// Where black's stones go.  Handicaps which cannot be
+    /// placed on this board are refused.
+    pub fn stones(&self, board_size: BoardSize) -> Result<Vec<Coord>, InvalidHandicap> {
+        match self {
+            Handicap::Fixed(n) if *n > MAX_HANDICAP => Err(InvalidHandicap::TooMany),
+            Handicap::Fixed(n) if *n < 2 => Ok(vec![]),
+            Handicap::Fixed(n) => match handicap_stones(board_size, *n) {
+                stones if stones.is_empty() => Err(InvalidHandicap::NoFixedPlacement),
+                stones => Ok(stones),
+            },
+            Handicap::Free(stones) => {
+                if stones.len() > MAX_HANDICAP as usize {
+                    return Err(InvalidHandicap::TooMany);
+                }
+                if stones.len() == 1 {
+                    return Err(InvalidHandicap::TooFew);
+                }
+                if !stones.iter().all(|c| board_size.contains(*c)) {
+                    return Err(InvalidHandicap::OffBoard);
+                }
+                if stones.iter().collect::<HashSet<_>>().len() < stones.len() {
+                    return Err(InvalidHandicap::Repeated);
+                }
+                Ok(stones.clone())
+            }
+        }
+    }
+}
+
+/// Fixed placement of black's handicap stones on the star
+/// points.  Only 9x9, 13x13 and 19x19 boards have standard
+/// placements, so other sizes receive no stones.  A handicap
+/// of one means no komi and needs no stones, either.
+pub fn handicap_stones(board_size: BoardSize, handicap: u8) -> Vec<Coord> {
+    let size = board_size.width;
+    let edge = match (size, board_size.height) {
+        (9, 9) => 2,
+        (13, 13) | (19, 19) => 3,
+        _ => return vec![],
+    };
+    if !(2..=MAX_HANDICAP).contains(&handicap) {
+        return vec![];
+    }
+
+    let (near, mid, far) = (edge, size / 2, size - 1 - edge);
+    let corners = [
+        Coord::of(far, near),
+        Coord::of(near, far),
+        Coord::of(far, far),
+        Coord::of(near, near),
+    ];
+    let sides = [Coord::of(near, mid), Coord::of(far, mid)];
+    let top_bottom = [Coord::of(mid, near), Coord::of(mid, far)];
+    let center = Coord::of(mid, mid);
+
+    let mut stones: Vec<Coord> = corners
+        .iter()
+        .take(handicap.min(4) as usize)
+        .cloned()
+        .collect();
+    if handicap >= 6 {
+        stones.extend(&sides)
+    }
+    if handicap >= 8 {
+        stones.extend(&top_bottom)
+    }
+    if handicap % 2 == 1 && handicap >= 5 {
+        stones.push(center)
+    }
+    stones
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_stones_on_opposite_corners() {
+        assert_eq!(
+            handicap_stones(BoardSize::square(19), 2),
+            vec![Coord::of(15, 3), Coord::of(3, 15)]
+        )
+    }
+
+    #[test]
+    fn odd_handicap_takes_center() {
+        let stones = handicap_stones(BoardSize::square(13), 5);
+        assert_eq!(stones.len(), 5);
+        assert!(stones.contains(&Coord::of(6, 6)));
+        assert!(!handicap_stones(BoardSize::square(13), 6).contains(&Coord::of(6, 6)))
+    }
+
+    #[test]
+    fn nine_stones_fill_star_points() {
+        let stones = handicap_stones(BoardSize::square(9), 9);
+        for x in &[2, 4, 6] {
+            for y in &[2, 4, 6] {
+                assert!(stones.contains(&Coord::of(*x, *y)))
+            }
+        }
+    }
+
+    #[test]
+    fn free_placement() {
+        let nine = BoardSize::square(9);
+        let stones = vec![Coord::of(0, 0), Coord::of(8, 8), Coord::of(4, 5)];
+        assert_eq!(Handicap::Free(stones.clone()).stones(nine), Ok(stones));
+        assert_eq!(Handicap::Free(vec![]).stones(nine), Ok(vec![]));
+        assert_eq!(
+            Handicap::Free(vec![Coord::of(1, 1)]).stones(nine),
+            Err(InvalidHandicap::TooFew)
+        );
+        assert_eq!(
+            Handicap::Free(vec![Coord::of(1, 1), Coord::of(9, 1)]).stones(nine),
+            Err(InvalidHandicap::OffBoard)
+        );
+        assert_eq!(
+            Handicap::Free(vec![Coord::of(1, 1), Coord::of(1, 1)]).stones(nine),
+            Err(InvalidHandicap::Repeated)
+        );
+        let too_many = (0..10).map(|x| Coord::of(x, 0)).collect();
+        assert_eq!(
+            Handicap::Free(too_many).stones(BoardSize::square(19)),
+            Err(InvalidHandicap::TooMany)
+        )
+    }
+
+    #[test]
+    fn fixed_placement_refused() {
+        assert_eq!(
+            Handicap::Fixed(4).stones(BoardSize::square(7)),
+            Err(InvalidHandicap::NoFixedPlacement)
+        );
+        assert_eq!(
+            Handicap::Fixed(10).stones(BoardSize::square(19)),
+            Err(InvalidHandicap::TooMany)
+        );
+        assert_eq!(Handicap::Fixed(1).stones(BoardSize::square(7)), Ok(vec![]));
+        assert_eq!(
+            Handicap::Fixed(2)
+                .stones(BoardSize::square(9))
+                .unwrap()
+                .len(),
+            2
+        )
+    }
+
+    #[test]
+    fn no_fixed_placement() {
+        assert!(handicap_stones(BoardSize::square(19), 1).is_empty());
+        assert!(handicap_stones(BoardSize::square(19), 10).is_empty());
+        assert!(handicap_stones(BoardSize::square(7), 4).is_empty())
+    }
+}
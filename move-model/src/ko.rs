@@ -0,0 +1,88 @@
+use super::*;
+
+/// Determines which point, if any, the next player is forbidden
+/// to play under the simple ko rule.  The board must already
+/// reflect the move: the stone is placed and its captures
+/// are removed.
+pub fn ko_point(board: &Board, move_made: &MoveMade) -> Option<Coord> {
+    let placed = move_made.coord?;
+    if move_made.captured.len() != 1 {
+        return None;
+    }
+    let captured = move_made.captured[0];
+    let mut lone_stone = true;
+    let mut liberties = 0;
+    for n in neighbors(placed, board.size) {
+        match board.get(n) {
+            Some(p) if p == move_made.player => lone_stone = false,
+            Some(_) => (),
+            None => liberties += 1,
+        }
+    }
+
+    if lone_stone && liberties == 1 && board.get(captured).is_none() {
+        Some(captured)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn move_made(player: Player, coord: Coord, captured: Vec<Coord>) -> MoveMade {
+        MoveMade {
+            game_id: GameId::new(),
+            reply_to: ReqId::new(),
+            event_id: EventId::new(),
+            player,
+            coord: Some(coord),
+            captured,
+        }
+    }
+
+    ///  . B W .
+    ///  B W . W
+    ///  . B W .
+    ///
+    /// Black plays (2,1) and captures the white stone at (1,1)
+    #[test]
+    fn detects_ko() {
+        let mut board = Board::default();
+        board.place(Coord::of(1, 0), Player::BLACK);
+        board.place(Coord::of(0, 1), Player::BLACK);
+        board.place(Coord::of(1, 2), Player::BLACK);
+        board.place(Coord::of(2, 0), Player::WHITE);
+        board.place(Coord::of(3, 1), Player::WHITE);
+        board.place(Coord::of(2, 2), Player::WHITE);
+        board.place(Coord::of(2, 1), Player::BLACK);
+
+        let mm = move_made(Player::BLACK, Coord::of(2, 1), vec![Coord::of(1, 1)]);
+        assert_eq!(ko_point(&board, &mm), Some(Coord::of(1, 1)))
+    }
+
+    #[test]
+    fn no_ko_when_capturing_stone_has_liberties() {
+        let mut board = Board::default();
+        board.place(Coord::of(1, 0), Player::BLACK);
+        board.place(Coord::of(0, 1), Player::BLACK);
+        board.place(Coord::of(1, 2), Player::BLACK);
+        board.place(Coord::of(2, 0), Player::WHITE);
+        board.place(Coord::of(2, 1), Player::BLACK);
+
+        let mm = move_made(Player::BLACK, Coord::of(2, 1), vec![Coord::of(1, 1)]);
+        assert_eq!(ko_point(&board, &mm), None)
+    }
+
+    #[test]
+    fn no_ko_when_capturing_two() {
+        let board = Board::default();
+        let mm = move_made(
+            Player::WHITE,
+            Coord::of(0, 0),
+            vec![Coord::of(1, 0), Coord::of(2, 0)],
+        );
+        assert_eq!(ko_point(&board, &mm), None)
+    }
+}
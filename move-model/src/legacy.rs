@@ -0,0 +1,106 @@
+//! Game states written before boards were packed stored
+//! their stones in a map.  These records are still found
+//! in redis until they expire, so we keep the old layout
+//! around to read them.
+use super::*;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct LegacyBoard {
+    pieces: HashMap<Coord, Player>,
+    size: u16,
+}
+
+impl From<LegacyBoard> for Board {
+    fn from(legacy: LegacyBoard) -> Self {
+        let mut board = Board::new(BoardSize::square(legacy.size));
+        for (c, p) in legacy.pieces {
+            board.place(c, p)
+        }
+        board
+    }
+}
+
+/// A game state as it was written before boards were
+/// packed.  Ko, rules, results and handicaps came later.
+#[derive(Deserialize)]
+pub struct LegacyGameState {
+    board: LegacyBoard,
+    captures: Captures,
+    turn: u16,
+    player_up: Player,
+    moves: Vec<MoveMade>,
+    game_id: GameId,
+}
+
+impl From<LegacyGameState> for GameState {
+    fn from(legacy: LegacyGameState) -> Self {
+        let size = BoardSize::square(legacy.board.size);
+        GameState {
+            board: legacy.board.into(),
+            captures: legacy.captures,
+            turn: legacy.turn,
+            player_up: legacy.player_up,
+            moves: legacy.moves,
+            ..GameState::starting(legacy.game_id, size, Rules::default(), vec![])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_derive::Serialize;
+
+    /// The layout of a board before it was packed
+    #[derive(Serialize)]
+    struct MapBoard {
+        pieces: HashMap<Coord, Player>,
+        size: u16,
+    }
+
+    /// The layout of a game state before boards were packed
+    #[derive(Serialize)]
+    struct MapGameState {
+        board: MapBoard,
+        captures: Captures,
+        turn: u16,
+        player_up: Player,
+        moves: Vec<MoveMade>,
+        game_id: GameId,
+    }
+
+    #[test]
+    fn reads_map_board() {
+        let game_id = GameId::new();
+        let played = MoveMade {
+            game_id: game_id.clone(),
+            reply_to: ReqId::new(),
+            event_id: EventId::new(),
+            player: Player::BLACK,
+            coord: Some(Coord::of(3, 3)),
+            captured: vec![],
+        };
+        let bytes = bincode::serialize(&MapGameState {
+            board: MapBoard {
+                pieces: vec![(Coord::of(3, 3), Player::BLACK)].into_iter().collect(),
+                size: 9,
+            },
+            captures: Captures::default(),
+            turn: 2,
+            player_up: Player::WHITE,
+            moves: vec![played.clone()],
+            game_id: game_id.clone(),
+        })
+        .unwrap();
+
+        let mut expected =
+            GameState::starting(game_id, BoardSize::square(9), Rules::default(), vec![]);
+        expected.board.place(Coord::of(3, 3), Player::BLACK);
+        expected.turn = 2;
+        expected.player_up = Player::WHITE;
+        expected.moves = vec![played];
+
+        assert_eq!(GameState::from(&bytes).unwrap(), expected)
+    }
+}
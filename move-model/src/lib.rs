@@ -1,8 +1,26 @@
 extern crate core_model;
 
+mod board;
+mod capturing;
+mod handicap;
+mod ko;
+mod legacy;
+mod marking;
+mod replay;
+mod scoring;
+mod variant;
+
+pub use board::{Board, BoardSize};
+pub use capturing::{captures_for, connected, removed_by, suicide_for};
+pub use handicap::{handicap_stones, Handicap, InvalidHandicap, MAX_HANDICAP};
+pub use ko::ko_point;
+pub use marking::*;
+pub use replay::ApplyErr;
+pub use scoring::{score, FinalScore, Score, DEFAULT_KOMI};
+pub use variant::Variant;
+
 use core_model::*;
 use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize, Eq, Hash)]
 pub enum Player {
@@ -10,19 +28,22 @@ pub enum Player {
     WHITE,
 }
 
-impl Player {
-    pub fn from_str(s: &str) -> Player {
+/// Anything starting with a "w" is white, and the rest is black
+impl From<&str> for Player {
+    fn from(s: &str) -> Player {
         let trimmed = s.trim().to_ascii_lowercase();
-        if trimmed.chars().next() == Some('w') {
+        if trimmed.starts_with('w') {
             Player::WHITE
         } else {
             Player::BLACK
         }
     }
-    pub fn to_string(&self) -> String {
+}
+impl std::fmt::Display for Player {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Player::BLACK => "BLACK".to_string(),
-            Player::WHITE => "WHITE".to_string(),
+            Player::BLACK => write!(f, "BLACK"),
+            Player::WHITE => write!(f, "WHITE"),
         }
     }
 }
@@ -45,42 +66,251 @@ pub struct GameState {
     pub player_up: Player,
     pub moves: Vec<MoveMade>,
     pub game_id: GameId,
+    /// The point which the player up may not play,
+    /// because it would immediately retake a ko
+    pub ko: Option<Coord>,
+    pub rules: Rules,
+    /// Set once the game has ended.  No further moves
+    /// will be accepted.
+    pub game_over: bool,
+    /// Set once the winner is decided, either by
+    /// resignation or by both players accepting the score
+    pub result: Option<GameResult>,
+    /// Black stones placed before the first move
+    pub handicap: Vec<Coord>,
+    /// Counts the changes made to this game.  A copy with
+    /// a lower revision than the one held is stale.
+    pub revision: u64,
 }
 
 impl GameState {
+    /// A game which no one has moved in yet.  When black
+    /// receives handicap stones, white moves first.
+    pub fn new(game_id: GameId, board_size: BoardSize, rules: Rules, handicap: u8) -> Self {
+        let handicap = handicap_stones(board_size, handicap);
+        GameState::starting(game_id, board_size, rules, handicap)
+    }
+
+    /// A game which no one has moved in yet, with black's
+    /// handicap stones already placed
+    pub fn starting(
+        game_id: GameId,
+        board_size: BoardSize,
+        rules: Rules,
+        handicap: Vec<Coord>,
+    ) -> Self {
+        let mut board = Board::new(board_size);
+        for c in &handicap {
+            board.place(*c, Player::BLACK)
+        }
+        GameState {
+            game_id,
+            board,
+            captures: Captures::default(),
+            turn: 1,
+            player_up: if handicap.is_empty() {
+                Player::BLACK
+            } else {
+                Player::WHITE
+            },
+            moves: vec![],
+            ko: None,
+            rules,
+            game_over: false,
+            result: None,
+            handicap,
+            revision: 0,
+        }
+    }
+
+    /// Reads a game state, falling back to the layout
+    /// used before boards were packed
     pub fn from(bytes: &[u8]) -> Result<GameState, std::boxed::Box<bincode::ErrorKind>> {
-        bincode::deserialize(bytes)
+        bincode::deserialize(bytes).or_else(|e| {
+            bincode::deserialize::<legacy::LegacyGameState>(bytes)
+                .map(|legacy| legacy.into())
+                .map_err(|_| e)
+        })
     }
     pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
-        Ok(bincode::serialize(&self)?)
+        bincode::serialize(&self)
     }
+
+    /// True when the two most recent moves were both passes
+    pub fn consecutive_passes(&self) -> bool {
+        self.moves.len() >= 2 && self.moves.iter().rev().take(2).all(|m| m.coord.is_none())
+    }
+}
+
+/// Rules which vary from game to game
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub struct Rules {
+    pub ko: KoRule,
+    pub suicide: SuicideRule,
+    pub scoring: ScoringRule,
+    pub komi: Komi,
+    pub variant: Variant,
+    pub undo: UndoPolicy,
+}
+
+impl Rules {
+    /// The rules of a named ruleset, with its customary
+    /// komi unless the players chose their own.  Games
+    /// without a ruleset keep the defaults.
+    pub fn of(ruleset: Option<Ruleset>, komi: Option<f32>) -> Result<Self, InvalidKomi> {
+        let rules = match ruleset {
+            None => Rules::default(),
+            Some(Ruleset::Japanese) => Rules {
+                ko: KoRule::Simple,
+                suicide: SuicideRule::Forbidden,
+                scoring: ScoringRule::Territory,
+                komi: Komi(6.5),
+                variant: Variant::Standard,
+                undo: UndoPolicy::Unlimited,
+            },
+            Some(Ruleset::Chinese) => Rules {
+                ko: KoRule::PositionalSuperko,
+                suicide: SuicideRule::Forbidden,
+                scoring: ScoringRule::Area,
+                komi: Komi(7.5),
+                variant: Variant::Standard,
+                undo: UndoPolicy::Unlimited,
+            },
+            Some(Ruleset::NewZealand) => Rules {
+                ko: KoRule::PositionalSuperko,
+                suicide: SuicideRule::MultiStoneAllowed,
+                scoring: ScoringRule::Area,
+                komi: Komi(7.0),
+                variant: Variant::Standard,
+                undo: UndoPolicy::Unlimited,
+            },
+        };
+        Ok(Rules {
+            komi: match komi {
+                Some(k) => Komi::checked(k).ok_or(InvalidKomi(k))?,
+                None => rules.komi,
+            },
+            ..rules
+        })
+    }
+}
+
+/// Named rulesets which players may choose when creating a game
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Ruleset {
+    Japanese,
+    Chinese,
+    NewZealand,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum KoRule {
+    /// Forbid immediately retaking a single stone ko
+    #[default]
+    Simple,
+    /// Forbid any move which recreates an earlier
+    /// board position
+    PositionalSuperko,
+}
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum SuicideRule {
+    /// Japanese and Chinese rules forbid suicide
+    #[default]
+    Forbidden,
+    /// New Zealand and Tromp-Taylor rules allow a move which
+    /// removes its own group of two or more stones.
+    /// Single stone suicide is never allowed.
+    MultiStoneAllowed,
+}
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ScoringRule {
+    /// Stones on the board plus surrounded points
+    #[default]
+    Area,
+    /// Surrounded points plus prisoners
+    Territory,
+}
+
+/// How often a player may take moves back.  The undo
+/// service counts each player's undos in every game.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum UndoPolicy {
+    #[default]
+    Unlimited,
+    /// Each player may undo this many times per game
+    PerGame(u8),
+    /// Rated games allow no takebacks at all
+    Disabled,
 }
 
-const DEFAULT_BOARD_SIZE: u16 = 19;
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-pub struct Board {
-    pub pieces: HashMap<Coord, Player>,
-    pub size: u16,
+impl UndoPolicy {
+    /// Whether a player who has already undone `used`
+    /// times in this game may do so again
+    pub fn allows(&self, used: u16) -> bool {
+        match self {
+            UndoPolicy::Unlimited => true,
+            UndoPolicy::PerGame(n) => used < u16::from(*n),
+            UndoPolicy::Disabled => false,
+        }
+    }
 }
-impl Default for Board {
+
+/// Points given to white to make up for moving second
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Komi(pub f32);
+impl Default for Komi {
     fn default() -> Self {
-        Board {
-            pieces: HashMap::new(),
-            size: DEFAULT_BOARD_SIZE,
+        Komi(DEFAULT_KOMI)
+    }
+}
+impl Komi {
+    /// Players may choose any whole or half point komi
+    /// up to this many points for either side
+    pub const MAX: f32 = 150.0;
+
+    pub fn checked(komi: f32) -> Option<Komi> {
+        if komi.is_finite() && komi.abs() <= Komi::MAX && (komi * 2.0).fract() == 0.0 {
+            Some(Komi(komi))
+        } else {
+            None
         }
     }
 }
+/// Komi is always a whole or half point, never NaN
+impl Eq for Komi {}
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Komi which is not a whole or half point, or is too big
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidKomi(pub f32);
+impl std::hash::Hash for Komi {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+/// Orthogonally adjacent points which lie on the board
+fn neighbors(coord: Coord, size: BoardSize) -> Vec<Coord> {
+    let mut out = Vec::with_capacity(4);
+    if coord.x > 0 {
+        out.push(Coord::of(coord.x - 1, coord.y))
+    }
+    if coord.x + 1 < size.width {
+        out.push(Coord::of(coord.x + 1, coord.y))
+    }
+    if coord.y > 0 {
+        out.push(Coord::of(coord.x, coord.y - 1))
+    }
+    if coord.y + 1 < size.height {
+        out.push(Coord::of(coord.x, coord.y + 1))
+    }
+    out
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Captures {
     pub black: u16,
     pub white: u16,
 }
-impl Default for Captures {
-    fn default() -> Self {
-        Captures { black: 0, white: 0 }
-    }
-}
 
 /// This command requests that a move be judged for
 /// for correctness and, if accepted, communicated to
@@ -108,11 +338,92 @@ pub struct MoveMade {
     pub captured: Vec<Coord>,
 }
 impl MoveMade {
+    /// A suicidal move lists its own stones, including
+    /// the one just placed, as captured
+    pub fn is_suicide(&self) -> bool {
+        self.coord
+            .map(|c| self.captured.contains(&c))
+            .unwrap_or(false)
+    }
+    pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
+        bincode::serialize(&self)
+    }
+}
+
+/// An event signalling that a move was judged illegal.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MoveRejected {
+    pub game_id: GameId,
+    pub reply_to: ReqId,
+    pub event_id: EventId,
+    pub player: Player,
+    pub coord: Option<Coord>,
+    pub reason: RejectReason,
+}
+impl MoveRejected {
     pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
-        Ok(bincode::serialize(&self)?)
+        bincode::serialize(&self)
     }
 }
 
+/// A player gives up, and their opponent wins the game.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Resign {
+    pub game_id: GameId,
+    pub player: Player,
+}
+
+/// A player leaves the game before it is over.  Nobody
+/// wins, and nothing more is played.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuitGame {
+    pub game_id: GameId,
+}
+
+/// An event signalling that the game has ended.
+/// No more moves will be accepted for this game.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameOver {
+    pub game_id: GameId,
+    pub event_id: EventId,
+    pub result: GameResult,
+    /// Absent unless the board was counted
+    pub score: Option<FinalScore>,
+}
+impl GameOver {
+    pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
+        bincode::serialize(&self)
+    }
+}
+
+/// The winner of a game and the reason it ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameResult {
+    /// None in the case of a draw
+    pub winner: Option<Player>,
+    pub reason: EndReason,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EndReason {
+    /// Both players passed, and the board was counted
+    Score,
+    Resignation,
+    /// A variant's own goal was reached, see [`Variant`]
+    FirstCapture,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RejectReason {
+    WrongPlayer,
+    OutOfBounds,
+    Occupied,
+    Ko,
+    Suicide,
+    GameOver,
+    UnknownGame,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +437,12 @@ mod tests {
             player_up: Player::BLACK,
             captures: Captures::default(),
             turn: 1,
+            ko: None,
+            rules: Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         }
     }
     #[test]
@@ -133,7 +450,38 @@ mod tests {
         let gs = new_game_state();
         let result = gs.serialize();
         assert!(result.is_ok());
-        assert!(result.unwrap().len() > 0)
+        assert!(!result.unwrap().is_empty())
+    }
+
+    #[test]
+    fn test_ruleset_komi() {
+        assert_eq!(Rules::of(None, None), Ok(Rules::default()));
+        assert_eq!(
+            Rules::of(Some(Ruleset::Chinese), None).unwrap().komi,
+            Komi(7.5)
+        );
+        let japanese = Rules::of(Some(Ruleset::Japanese), Some(0.5)).unwrap();
+        assert_eq!(japanese.scoring, ScoringRule::Territory);
+        assert_eq!(japanese.komi, Komi(0.5));
+        assert_eq!(Rules::of(None, Some(-3.0)).unwrap().komi, Komi(-3.0))
+    }
+
+    #[test]
+    fn test_invalid_komi() {
+        for k in &[6.3, f32::NAN, f32::INFINITY, 151.0, -200.5] {
+            assert!(Rules::of(None, Some(*k)).is_err(), "{}", k)
+        }
+    }
+
+    #[test]
+    fn test_handicap_game_white_first() {
+        let gs = GameState::new(GameId::new(), BoardSize::default(), Rules::default(), 4);
+        assert_eq!(gs.player_up, Player::WHITE);
+        assert_eq!(gs.board.pieces().count(), 4);
+        assert_eq!(gs.handicap.len(), 4);
+        let even = GameState::new(GameId::new(), BoardSize::default(), Rules::default(), 0);
+        assert_eq!(even.player_up, Player::BLACK);
+        assert!(even.board.is_empty())
     }
 
     #[test]
@@ -153,16 +501,68 @@ mod tests {
         assert_eq!(back, gs);
     }
 
+    #[test]
+    fn suicide_lists_its_own_stone() {
+        let mut mm = MoveMade {
+            player: Player::BLACK,
+            coord: Some(Coord::of(0, 0)),
+            captured: vec![Coord::of(1, 0)],
+            event_id: EventId::new(),
+            game_id: GameId::new(),
+            reply_to: ReqId::new(),
+        };
+        assert!(!mm.is_suicide());
+        mm.captured = vec![Coord::of(0, 0), Coord::of(0, 1)];
+        assert!(mm.is_suicide());
+        mm.coord = None;
+        mm.captured = vec![];
+        assert!(!mm.is_suicide())
+    }
+
+    #[test]
+    fn two_passes_in_a_row() {
+        let mut gs = new_game_state();
+        let mv = |player, coord| MoveMade {
+            player,
+            coord,
+            captured: vec![],
+            event_id: EventId::new(),
+            game_id: gs.game_id.clone(),
+            reply_to: ReqId::new(),
+        };
+        let (b_pass, w_play, w_pass) = (
+            mv(Player::BLACK, None),
+            mv(Player::WHITE, Some(Coord::of(3, 3))),
+            mv(Player::WHITE, None),
+        );
+        assert!(!gs.consecutive_passes());
+        gs.moves.push(b_pass.clone());
+        assert!(!gs.consecutive_passes());
+        gs.moves.push(w_play);
+        gs.moves.push(b_pass);
+        assert!(!gs.consecutive_passes());
+        gs.moves.push(w_pass);
+        assert!(gs.consecutive_passes())
+    }
+
     #[test]
     fn player_from_string() {
-        assert_eq!(Player::from_str("WHITE"), Player::WHITE);
-        assert_eq!(Player::from_str("BLACK"), Player::BLACK);
-        assert_eq!(Player::from_str("W"), Player::WHITE);
-        assert_eq!(Player::from_str("B"), Player::BLACK);
-        assert_eq!(Player::from_str("white"), Player::WHITE);
-        assert_eq!(Player::from_str("black"), Player::BLACK);
-        assert_eq!(Player::from_str("w"), Player::WHITE);
-        assert_eq!(Player::from_str("b"), Player::BLACK);
-        assert_eq!(Player::from_str(""), Player::BLACK);
+        assert_eq!(Player::from("WHITE"), Player::WHITE);
+        assert_eq!(Player::from("BLACK"), Player::BLACK);
+        assert_eq!(Player::from("W"), Player::WHITE);
+        assert_eq!(Player::from("B"), Player::BLACK);
+        assert_eq!(Player::from("white"), Player::WHITE);
+        assert_eq!(Player::from("black"), Player::BLACK);
+        assert_eq!(Player::from("w"), Player::WHITE);
+        assert_eq!(Player::from("b"), Player::BLACK);
+        assert_eq!(Player::from(""), Player::BLACK);
+    }
+
+    #[test]
+    fn undo_policy_allows() {
+        assert!(UndoPolicy::Unlimited.allows(100));
+        assert!(UndoPolicy::PerGame(2).allows(1));
+        assert!(!UndoPolicy::PerGame(2).allows(2));
+        assert!(!UndoPolicy::Disabled.allows(0));
     }
 }
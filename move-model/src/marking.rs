@@ -0,0 +1,100 @@
+use super::*;
+
+/// Once the game is over, a player marks a group of
+/// stones as dead, or marks a dead group alive again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToggleDeadStone {
+    pub game_id: GameId,
+    pub player: Player,
+    pub coord: Coord,
+}
+
+/// A player agrees to the score which results from
+/// the dead stones as they are currently marked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AcceptScore {
+    pub game_id: GameId,
+    pub player: Player,
+}
+
+/// A player disputes the marking and asks to continue
+/// the game from where it ended.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ResumePlay {
+    pub game_id: GameId,
+    pub player: Player,
+}
+
+/// An event announcing the dead stones as currently marked,
+/// the players who have accepted them, and the resulting score.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeadStonesMarked {
+    pub game_id: GameId,
+    pub event_id: EventId,
+    pub dead: Vec<Coord>,
+    pub accepted: Vec<Player>,
+    pub score: FinalScore,
+}
+impl DeadStonesMarked {
+    pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
+        bincode::serialize(&self)
+    }
+}
+
+/// An event signalling that both players accepted the score.
+/// The result of the game is final.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoreFinalized {
+    pub game_id: GameId,
+    pub event_id: EventId,
+    pub dead: Vec<Coord>,
+    pub score: FinalScore,
+    pub result: GameResult,
+}
+impl ScoreFinalized {
+    pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
+        bincode::serialize(&self)
+    }
+}
+
+/// An event signalling that a finished game has been
+/// resumed, and will accept moves again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlayResumed {
+    pub game_id: GameId,
+    pub event_id: EventId,
+    pub player: Player,
+}
+impl PlayResumed {
+    pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
+        bincode::serialize(&self)
+    }
+}
+
+/// The dead stones marked so far in a finished game, and the
+/// players who have accepted them.  Any change to the dead
+/// stones clears the acceptances.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Marking {
+    pub game_id: GameId,
+    pub dead: Vec<Coord>,
+    pub accepted: Vec<Player>,
+    /// Set once both players accept
+    pub finalized: bool,
+}
+impl Marking {
+    pub fn new(game_id: GameId) -> Self {
+        Marking {
+            game_id,
+            dead: vec![],
+            accepted: vec![],
+            finalized: false,
+        }
+    }
+    pub fn from(bytes: &[u8]) -> Result<Marking, std::boxed::Box<bincode::ErrorKind>> {
+        bincode::deserialize(bytes)
+    }
+    pub fn serialize(&self) -> Result<Vec<u8>, std::boxed::Box<bincode::ErrorKind>> {
+        bincode::serialize(&self)
+    }
+}
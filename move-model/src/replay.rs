@@ -0,0 +1,241 @@
+use super::*;
+
+/// Why a move could not be applied to a game state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplyErr {
+    WrongGame,
+    WrongPlayer,
+    OutOfBounds,
+    Occupied,
+    /// The stones listed as captured are not the
+    /// stones which the move removes from the board
+    WrongCaptures,
+}
+
+impl GameState {
+    /// This game as it stood before anyone moved,
+    /// with the same board size, rules and handicap
+    pub fn restart(&self) -> GameState {
+        GameState::starting(
+            self.game_id.clone(),
+            self.board.size,
+            self.rules,
+            self.handicap.clone(),
+        )
+    }
+
+    /// Plays an accepted move: places its stone, takes off
+    /// the stones it captures and passes the turn.  The move's
+    /// captured list must match what the board says it removes.
+    /// Ko and suicide rules are left to the judge.
+    pub fn apply(&mut self, move_made: &MoveMade) -> Result<(), ApplyErr> {
+        if move_made.game_id != self.game_id {
+            return Err(ApplyErr::WrongGame);
+        }
+        if move_made.player != self.player_up {
+            return Err(ApplyErr::WrongPlayer);
+        }
+        match move_made.coord {
+            Some(c) => {
+                if !self.board.size.contains(c) {
+                    return Err(ApplyErr::OutOfBounds);
+                }
+                if self.board.get(c).is_some() {
+                    return Err(ApplyErr::Occupied);
+                }
+                let removed = removed_by(move_made.player, c, &self.board);
+                if removed.len() != move_made.captured.len()
+                    || !move_made.captured.iter().all(|r| removed.contains(r))
+                {
+                    return Err(ApplyErr::WrongCaptures);
+                }
+                self.board.place(c, move_made.player);
+                for r in &move_made.captured {
+                    self.board.remove(*r);
+                }
+            }
+            None if !move_made.captured.is_empty() => return Err(ApplyErr::WrongCaptures),
+            None => (),
+        }
+
+        let opponent = match move_made.player {
+            Player::BLACK => Player::WHITE,
+            Player::WHITE => Player::BLACK,
+        };
+        let caps = move_made.captured.len() as u16;
+        // suicided stones are credited to the opponent
+        let capturer = if move_made.is_suicide() {
+            opponent
+        } else {
+            move_made.player
+        };
+        match capturer {
+            Player::BLACK => self.captures.black += caps,
+            Player::WHITE => self.captures.white += caps,
+        }
+
+        self.ko = ko_point(&self.board, move_made);
+        self.turn += 1;
+        self.player_up = opponent;
+        self.moves.push(move_made.clone());
+        self.revision += 1;
+        self.game_over = self.consecutive_passes();
+        if let Some(result) = self.rules.variant.decides(move_made) {
+            self.game_over = true;
+            self.result = Some(result);
+        }
+        Ok(())
+    }
+
+    /// Whether this move has been played already.  Retried
+    /// requests are judged again and arrive with a new event,
+    /// so a repeat of the last move by a player who is no
+    /// longer up counts as played, too.
+    pub fn has_played(&self, move_made: &MoveMade) -> bool {
+        let seen = self
+            .moves
+            .iter()
+            .any(|m| m.event_id == move_made.event_id || m.reply_to == move_made.reply_to);
+        let repeats_last = move_made.player != self.player_up
+            && self.moves.last().is_some_and(|last| {
+                last.player == move_made.player && last.coord == move_made.coord
+            });
+        seen || repeats_last
+    }
+
+    /// Plays the moves in order from the start of this game.
+    /// The result is the next revision of this game.
+    pub fn replay(&self, moves: &[MoveMade]) -> Result<GameState, ApplyErr> {
+        let mut out = self.restart();
+        for m in moves {
+            out.apply(m)?
+        }
+        out.revision = self.revision + 1;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(gs: &mut GameState, coord: Option<Coord>) -> MoveMade {
+        let captured = coord
+            .map(|c| removed_by(gs.player_up, c, &gs.board).into_iter().collect())
+            .unwrap_or_default();
+        let mm = MoveMade {
+            game_id: gs.game_id.clone(),
+            reply_to: ReqId::new(),
+            event_id: EventId::new(),
+            player: gs.player_up,
+            coord,
+            captured,
+        };
+        gs.apply(&mm).unwrap();
+        mm
+    }
+
+    #[test]
+    fn apply_captures_and_passes_turn() {
+        let mut gs = GameState::new(GameId::new(), BoardSize::square(9), Rules::default(), 0);
+        play(&mut gs, Some(Coord::of(1, 0)));
+        play(&mut gs, Some(Coord::of(0, 0)));
+        let capture = play(&mut gs, Some(Coord::of(0, 1)));
+        assert_eq!(capture.captured, vec![Coord::of(0, 0)]);
+        assert_eq!(gs.board.get(Coord::of(0, 0)), None);
+        assert_eq!(gs.captures, Captures { black: 1, white: 0 });
+        assert_eq!(gs.player_up, Player::WHITE);
+        assert_eq!(gs.turn, 4);
+        play(&mut gs, None);
+        play(&mut gs, None);
+        assert!(gs.game_over)
+    }
+
+    #[test]
+    fn replay_matches_incremental() {
+        let mut gs = GameState::new(GameId::new(), BoardSize::square(9), Rules::default(), 2);
+        for c in &[Coord::of(2, 2), Coord::of(2, 3), Coord::of(3, 2)] {
+            play(&mut gs, Some(*c));
+        }
+        play(&mut gs, None);
+        let replayed = gs.replay(&gs.moves).unwrap();
+        assert_eq!(replayed.revision, gs.revision + 1);
+        assert_eq!(
+            GameState {
+                revision: gs.revision,
+                ..replayed
+            },
+            gs
+        );
+        let restarted = gs.replay(&[]).unwrap();
+        assert_eq!(
+            GameState {
+                revision: 0,
+                ..restarted
+            },
+            gs.restart()
+        )
+    }
+
+    #[test]
+    fn replay_checks_captures() {
+        let mut gs = GameState::new(GameId::new(), BoardSize::square(9), Rules::default(), 0);
+        play(&mut gs, Some(Coord::of(4, 4)));
+        let mut moves = gs.moves.clone();
+        moves[0].captured = vec![Coord::of(4, 5)];
+        assert_eq!(gs.replay(&moves), Err(ApplyErr::WrongCaptures));
+        moves[0].captured = vec![];
+        moves[0].player = Player::WHITE;
+        assert_eq!(gs.replay(&moves), Err(ApplyErr::WrongPlayer))
+    }
+
+    #[test]
+    fn duplicates_already_played() {
+        let mut gs = GameState::new(GameId::new(), BoardSize::square(9), Rules::default(), 0);
+        let first = play(&mut gs, Some(Coord::of(4, 4)));
+        assert!(gs.has_played(&first));
+        let retried = MoveMade {
+            event_id: EventId::new(),
+            reply_to: ReqId::new(),
+            ..first.clone()
+        };
+        assert!(gs.has_played(&retried));
+        let reply = MoveMade {
+            player: Player::WHITE,
+            coord: Some(Coord::of(4, 5)),
+            ..retried.clone()
+        };
+        assert!(!gs.has_played(&reply));
+        play(&mut gs, Some(Coord::of(4, 5)));
+        // once black is up again, it is a new move
+        assert!(!gs.has_played(&retried))
+    }
+
+    #[test]
+    fn atari_go_ends_on_capture() {
+        let rules = Rules {
+            variant: Variant::AtariGo,
+            ..Rules::default()
+        };
+        let mut gs = GameState::new(GameId::new(), BoardSize::square(9), rules, 0);
+        play(&mut gs, Some(Coord::of(1, 0)));
+        play(&mut gs, Some(Coord::of(0, 0)));
+        assert!(!gs.game_over);
+        play(&mut gs, Some(Coord::of(0, 1)));
+        assert!(gs.game_over);
+        assert_eq!(gs.result.and_then(|r| r.winner), Some(Player::BLACK))
+    }
+
+    #[test]
+    fn suicide_credited_to_opponent() {
+        let mut gs = GameState::new(GameId::new(), BoardSize::square(9), Rules::default(), 0);
+        play(&mut gs, Some(Coord::of(1, 0)));
+        play(&mut gs, Some(Coord::of(0, 0)));
+        play(&mut gs, Some(Coord::of(1, 1)));
+        play(&mut gs, Some(Coord::of(5, 5)));
+        play(&mut gs, Some(Coord::of(0, 2)));
+        let suicide = play(&mut gs, Some(Coord::of(0, 1)));
+        assert!(suicide.is_suicide());
+        assert_eq!(gs.captures, Captures { black: 2, white: 0 })
+    }
+}
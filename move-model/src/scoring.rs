@@ -0,0 +1,207 @@
+use super::*;
+use std::collections::HashSet;
+
+/// Komi given to white when a game does not specify its own
+pub const DEFAULT_KOMI: f32 = 6.5;
+
+/// The count under a single scoring method,
+/// with komi already added to white's total
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Score {
+    pub black: f32,
+    pub white: f32,
+    /// None in the case of a draw
+    pub winner: Option<Player>,
+    pub margin: f32,
+}
+
+impl Score {
+    fn new(black: f32, white: f32) -> Self {
+        let winner = if black > white {
+            Some(Player::BLACK)
+        } else if white > black {
+            Some(Player::WHITE)
+        } else {
+            None
+        };
+        Score {
+            black,
+            white,
+            winner,
+            margin: (black - white).abs(),
+        }
+    }
+}
+
+/// The result of counting a finished game using both
+/// Chinese area scoring and Japanese territory scoring
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FinalScore {
+    pub area: Score,
+    pub territory: Score,
+    pub komi: f32,
+}
+
+impl FinalScore {
+    /// The count which decides the game under the given rule
+    pub fn counted(&self, scoring: ScoringRule) -> Score {
+        match scoring {
+            ScoringRule::Area => self.area,
+            ScoringRule::Territory => self.territory,
+        }
+    }
+}
+
+/// Counts a finished board.  Dead stones are taken off
+/// before counting, so that the points beneath them belong
+/// to whoever surrounds them.  Under territory scoring they
+/// are also added to the opponent's prisoners.
+pub fn score(board: &Board, captures: &Captures, dead: &HashSet<Coord>, komi: f32) -> FinalScore {
+    let mut alive = board.clone();
+    for c in dead {
+        alive.remove(*c);
+    }
+    let (black_territory, white_territory) = territory(&alive);
+    let stones = |player: Player| alive.pieces().filter(|(_, p)| *p == player).count() as f32;
+    let dead_stones = |player: Player| {
+        board
+            .pieces()
+            .filter(|(c, p)| *p == player && dead.contains(c))
+            .count() as f32
+    };
+
+    let area = Score::new(
+        stones(Player::BLACK) + black_territory,
+        stones(Player::WHITE) + white_territory + komi,
+    );
+    let territory = Score::new(
+        black_territory + captures.black as f32 + dead_stones(Player::WHITE),
+        white_territory + captures.white as f32 + dead_stones(Player::BLACK) + komi,
+    );
+
+    FinalScore {
+        area,
+        territory,
+        komi,
+    }
+}
+
+/// Counts the empty points enclosed only by black,
+/// and those enclosed only by white
+fn territory(board: &Board) -> (f32, f32) {
+    let size = board.size;
+    let mut seen: HashSet<Coord> = HashSet::new();
+    let (mut black, mut white) = (0, 0);
+    for x in 0..size.width {
+        for y in 0..size.height {
+            let start = Coord::of(x, y);
+            if board.get(start).is_some() || !seen.insert(start) {
+                continue;
+            }
+
+            let mut region = 0;
+            let mut borders: HashSet<Player> = HashSet::new();
+            let mut frontier = vec![start];
+            while let Some(c) = frontier.pop() {
+                region += 1;
+                for n in neighbors(c, size) {
+                    match board.get(n) {
+                        Some(p) => {
+                            borders.insert(p);
+                        }
+                        None => {
+                            if seen.insert(n) {
+                                frontier.push(n)
+                            }
+                        }
+                    }
+                }
+            }
+
+            if borders.len() == 1 {
+                match borders.iter().next() {
+                    Some(Player::BLACK) => black += region,
+                    Some(Player::WHITE) => white += region,
+                    None => (),
+                }
+            }
+        }
+    }
+    (black as f32, white as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 5x5 board split by a black wall on x=2
+    /// and a white wall on x=3
+    fn split_board() -> Board {
+        let mut board = Board::new(BoardSize::square(5));
+        for y in 0..5 {
+            board.place(Coord::of(2, y), Player::BLACK);
+            board.place(Coord::of(3, y), Player::WHITE);
+        }
+        board
+    }
+
+    #[test]
+    fn empty_board_goes_to_white_on_komi() {
+        let board = Board::new(BoardSize::square(9));
+        let result = score(&board, &Captures::default(), &HashSet::new(), DEFAULT_KOMI);
+        assert_eq!(result.area.winner, Some(Player::WHITE));
+        assert_eq!(result.area.margin, DEFAULT_KOMI);
+        assert_eq!(result.territory.black, 0.0);
+        assert_eq!(result.territory.white, DEFAULT_KOMI);
+    }
+
+    #[test]
+    fn area_and_territory() {
+        let result = score(
+            &split_board(),
+            &Captures::default(),
+            &HashSet::new(),
+            DEFAULT_KOMI,
+        );
+        assert_eq!(result.area.black, 15.0);
+        assert_eq!(result.area.white, 16.5);
+        assert_eq!(result.area.winner, Some(Player::WHITE));
+        assert_eq!(result.area.margin, 1.5);
+        assert_eq!(result.territory.black, 10.0);
+        assert_eq!(result.territory.white, 11.5);
+        assert_eq!(result.territory.margin, 1.5);
+    }
+
+    #[test]
+    fn captures_count_only_in_territory_scoring() {
+        let captures = Captures { black: 2, white: 0 };
+        let result = score(&split_board(), &captures, &HashSet::new(), DEFAULT_KOMI);
+        assert_eq!(result.area.winner, Some(Player::WHITE));
+        assert_eq!(result.territory.black, 12.0);
+        assert_eq!(result.territory.winner, Some(Player::BLACK));
+        assert_eq!(result.territory.margin, 0.5);
+    }
+
+    #[test]
+    fn dead_stones_are_removed() {
+        let mut board = split_board();
+        board.place(Coord::of(0, 0), Player::WHITE);
+        let alive = score(&board, &Captures::default(), &HashSet::new(), 0.0);
+        // the white stone spoils black's territory
+        assert_eq!(alive.territory.black, 0.0);
+
+        let dead: HashSet<Coord> = [Coord::of(0, 0)].iter().cloned().collect();
+        let result = score(&board, &Captures::default(), &dead, 0.0);
+        assert_eq!(result.area.black, 15.0);
+        assert_eq!(result.area.white, 10.0);
+        assert_eq!(result.territory.black, 11.0);
+        assert_eq!(result.territory.white, 5.0);
+    }
+
+    #[test]
+    fn draw_has_no_winner() {
+        let result = score(&split_board(), &Captures::default(), &HashSet::new(), 5.0);
+        assert_eq!(result.area.winner, None);
+        assert_eq!(result.area.margin, 0.0);
+    }
+}
@@ -0,0 +1,72 @@
+use super::*;
+
+/// Games which change what it takes to win
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum Variant {
+    /// Ordinary go, decided by counting the board
+    #[default]
+    Standard,
+    /// Capture go, used to teach beginners: whoever
+    /// captures first wins the game
+    AtariGo,
+}
+
+impl Variant {
+    /// The result of the game, if this move meets the
+    /// variant's own end condition.  Standard games only
+    /// end by passing or resigning.
+    pub fn decides(&self, move_made: &MoveMade) -> Option<GameResult> {
+        match self {
+            Variant::Standard => None,
+            Variant::AtariGo if move_made.captured.is_empty() => None,
+            Variant::AtariGo => Some(GameResult {
+                // a suicide captures the mover's own stones
+                winner: Some(if move_made.is_suicide() {
+                    match move_made.player {
+                        Player::BLACK => Player::WHITE,
+                        Player::WHITE => Player::BLACK,
+                    }
+                } else {
+                    move_made.player
+                }),
+                reason: EndReason::FirstCapture,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn move_made(coord: Coord, captured: Vec<Coord>) -> MoveMade {
+        MoveMade {
+            game_id: GameId::new(),
+            reply_to: ReqId::new(),
+            event_id: EventId::new(),
+            player: Player::WHITE,
+            coord: Some(coord),
+            captured,
+        }
+    }
+
+    #[test]
+    fn first_capture_wins() {
+        let quiet = move_made(Coord::of(3, 3), vec![]);
+        let capture = move_made(Coord::of(3, 3), vec![Coord::of(3, 4)]);
+        assert_eq!(Variant::AtariGo.decides(&quiet), None);
+        assert_eq!(Variant::Standard.decides(&capture), None);
+        assert_eq!(
+            Variant::AtariGo.decides(&capture),
+            Some(GameResult {
+                winner: Some(Player::WHITE),
+                reason: EndReason::FirstCapture
+            })
+        );
+        let suicide = move_made(Coord::of(0, 0), vec![Coord::of(0, 0), Coord::of(0, 1)]);
+        assert_eq!(
+            Variant::AtariGo.decides(&suicide).and_then(|r| r.winner),
+            Some(Player::BLACK)
+        )
+    }
+}
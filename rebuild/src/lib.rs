@@ -0,0 +1,90 @@
+/// it should replay the game ready, game states, make
+/// move and move made streams to rebuild every game they
+/// still hold, including undos and resignations
+///
+/// it should compare each rebuilt game with the
+/// changelog's snapshot and micro-sync's history,
+/// and report where they diverge
+///
+/// with --restore:
+///     it should write rebuilt games back to the
+///     game states changelog, unless the snapshot
+///     knows moves which the streams have forgotten
+pub mod replay;
+pub mod repo;
+pub mod stream;
+
+use log::{error, info, warn};
+use redis::Client;
+use replay::{diverges, rebuild};
+
+const REDIS_URL: &str = "redis://redis/";
+
+pub fn redis_client() -> Client {
+    Client::open(REDIS_URL).expect("redis client")
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub games: usize,
+    pub divergent: usize,
+    pub restored: usize,
+    pub orphans: usize,
+}
+
+pub fn run(client: &Client, restore: bool) -> Result<Report, redis::RedisError> {
+    let events = stream::read_all(client)?;
+    info!("read {} stream entries", events.len());
+    let (games, orphans) = rebuild(&events);
+    let mut report = Report {
+        games: games.len(),
+        orphans,
+        ..Report::default()
+    };
+    for game in games {
+        let game_id = &game.game_state.game_id;
+        for (move_made, e) in &game.unplayable {
+            warn!("{:?}: cannot play {:?}: {:?}", game_id, move_made, e)
+        }
+        if !game.unanswered.is_empty() {
+            info!(
+                "{:?}: {} moves requested but never accepted",
+                game_id,
+                game.unanswered.len()
+            )
+        }
+        let (snapshot, history) = match (
+            repo::snapshot(game_id, client),
+            repo::history(game_id, client),
+        ) {
+            (Ok(s), Ok(h)) => (s, h),
+            (s, h) => {
+                error!("{:?}: cannot read stored records {:?} {:?}", game_id, s, h);
+                continue;
+            }
+        };
+        let divergence = diverges(&game.game_state, snapshot.as_ref(), history.as_deref());
+        if divergence.is_empty() {
+            continue;
+        }
+        report.divergent += 1;
+        warn!("{:?}: {:?}", game_id, divergence);
+        if restore && divergence.iter().all(|d| d.restorable()) {
+            // consumers ignore a revision they already hold
+            let revision = snapshot
+                .map(|s| s.revision)
+                .unwrap_or(0)
+                .max(game.game_state.revision)
+                + 1;
+            let restored = move_model::GameState {
+                revision,
+                ..game.game_state.clone()
+            };
+            match stream::xadd_game_state(&restored, client) {
+                Ok(()) => report.restored += 1,
+                Err(e) => error!("{:?}: could not restore {:?}", game_id, e),
+            }
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,17 @@
+use log::info;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Run with --restore to write rebuilt games back.
+/// Exits with 1 when something diverged and was not
+/// restored.
+fn main() {
+    env_logger::init();
+    info!("🔢 {}", VERSION);
+    let restore = std::env::args().any(|a| a == "--restore");
+    let report = rebuild::run(&rebuild::redis_client(), restore).expect("rebuild");
+    info!("{:?}", report);
+    if report.divergent > report.restored {
+        std::process::exit(1)
+    }
+}
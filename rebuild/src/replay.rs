@@ -0,0 +1,393 @@
+use core_model::{GameId, ReqId};
+use lobby_model::api::GameReady;
+use move_model::{ApplyErr, GameState, MakeMove, MoveMade, Rules};
+use redis_streams::XReadEntryId;
+use std::collections::HashMap;
+use sync_model::Move;
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Ready(GameReady),
+    /// A game state written to the changelog.  The lobby
+    /// writes the first one, which carries the rules and the
+    /// handicap that game ready events lack.  Undos and
+    /// resignations are only ever written here.
+    Logged(GameState),
+    Command(MakeMove),
+    Made(MoveMade),
+}
+
+/// A game as the streams tell it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rebuilt {
+    pub game_state: GameState,
+    /// Moves requested which were never accepted, either
+    /// because the judge refused them or because they are
+    /// still in flight
+    pub unanswered: Vec<ReqId>,
+    /// Accepted moves which could not be played
+    pub unplayable: Vec<(MoveMade, ApplyErr)>,
+}
+
+impl Rebuilt {
+    fn new(game_state: GameState) -> Self {
+        Rebuilt {
+            game_state,
+            unanswered: vec![],
+            unplayable: vec![],
+        }
+    }
+}
+
+/// Replays the events in the order they were written.
+/// Games whose start has been trimmed from the streams
+/// cannot be rebuilt, and their moves are counted as
+/// orphans.
+pub fn rebuild(events: &[(XReadEntryId, Event)]) -> (Vec<Rebuilt>, usize) {
+    let mut order: Vec<GameId> = vec![];
+    let mut games: HashMap<GameId, Rebuilt> = HashMap::new();
+    let mut orphans = 0;
+    for (_, event) in events {
+        match event {
+            Event::Ready(ready) => {
+                if !games.contains_key(&ready.game_id) {
+                    order.push(ready.game_id.clone());
+                    games.insert(
+                        ready.game_id.clone(),
+                        Rebuilt::new(GameState::new(
+                            ready.game_id.clone(),
+                            ready.board_size,
+                            Rules::default(),
+                            0,
+                        )),
+                    );
+                }
+            }
+            // the changelog's record replaces ours, and later
+            // moves are played on top of it
+            Event::Logged(game_state) => match games.get_mut(&game_state.game_id) {
+                Some(game) => game.game_state = game_state.clone(),
+                None => {
+                    order.push(game_state.game_id.clone());
+                    games.insert(game_state.game_id.clone(), Rebuilt::new(game_state.clone()));
+                }
+            },
+            Event::Command(make_move) => match games.get_mut(&make_move.game_id) {
+                Some(game) => game.unanswered.push(make_move.req_id.clone()),
+                None => orphans += 1,
+            },
+            Event::Made(move_made) => match games.get_mut(&move_made.game_id) {
+                Some(game) => {
+                    game.unanswered.retain(|r| r != &move_made.reply_to);
+                    if game.game_state.has_played(move_made) {
+                        continue;
+                    }
+                    if let Err(e) = game.game_state.apply(move_made) {
+                        game.unplayable.push((move_made.clone(), e))
+                    }
+                }
+                None => orphans += 1,
+            },
+        }
+    }
+    let rebuilt = order
+        .iter()
+        .filter_map(|game_id| games.remove(game_id))
+        .collect();
+    (rebuilt, orphans)
+}
+
+/// Ways in which a stored record disagrees with the streams
+#[derive(Clone, Debug, PartialEq)]
+pub enum Divergence {
+    MissingSnapshot,
+    /// The snapshot has moves which the streams have
+    /// since forgotten.  It is left alone.
+    SnapshotAhead {
+        stored: usize,
+        rebuilt: usize,
+    },
+    SnapshotBehind {
+        stored: usize,
+        rebuilt: usize,
+    },
+    SnapshotDiffers,
+    MissingHistory,
+    HistoryDiffers,
+}
+
+impl Divergence {
+    /// Whether writing the rebuilt game back would lose
+    /// nothing that the stored records know
+    pub fn restorable(&self) -> bool {
+        !matches!(self, Divergence::SnapshotAhead { .. })
+    }
+}
+
+/// Compares the rebuilt game with the changelog's snapshot
+/// and micro-sync's history of it.  Finished games which
+/// redis has expired are not missing: the archive has them.
+pub fn diverges(
+    rebuilt: &GameState,
+    snapshot: Option<&GameState>,
+    history: Option<&[Move]>,
+) -> Vec<Divergence> {
+    let mut out = vec![];
+    if rebuilt.game_over && snapshot.is_none() && history.is_none() {
+        return out;
+    }
+    match snapshot {
+        None => out.push(Divergence::MissingSnapshot),
+        Some(stored) if same_game(stored, rebuilt) => (),
+        Some(stored) => {
+            let (s, r) = (stored.moves.len(), rebuilt.moves.len());
+            out.push(if s > r && stored.moves.starts_with(&rebuilt.moves) {
+                Divergence::SnapshotAhead {
+                    stored: s,
+                    rebuilt: r,
+                }
+            } else if s < r && rebuilt.moves.starts_with(&stored.moves) {
+                Divergence::SnapshotBehind {
+                    stored: s,
+                    rebuilt: r,
+                }
+            } else {
+                Divergence::SnapshotDiffers
+            })
+        }
+    }
+    match history {
+        // micro-sync only keeps a history once someone moves
+        None if rebuilt.moves.is_empty() => (),
+        None => out.push(Divergence::MissingHistory),
+        Some(moves) if moves == &to_history(rebuilt)[..] => (),
+        Some(_) => out.push(Divergence::HistoryDiffers),
+    }
+    out
+}
+
+/// Whether the two agree on everything but their revision
+fn same_game(stored: &GameState, rebuilt: &GameState) -> bool {
+    stored
+        == &GameState {
+            revision: stored.revision,
+            ..rebuilt.clone()
+        }
+}
+
+/// The moves as micro-sync records them, numbered from one
+pub fn to_history(game_state: &GameState) -> Vec<Move> {
+    game_state
+        .moves
+        .iter()
+        .enumerate()
+        .map(|(i, mm)| Move {
+            turn: (i + 1) as u32,
+            player: mm.player,
+            coord: mm.coord,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_model::{EventId, SessionId};
+    use move_model::{BoardSize, Coord, EndReason, GameResult, Komi, Player};
+
+    fn xid(n: u64) -> XReadEntryId {
+        XReadEntryId {
+            millis_time: n,
+            seq_no: 0,
+        }
+    }
+
+    fn made(game_id: &GameId, req_id: &ReqId, player: Player, coord: Coord) -> MoveMade {
+        MoveMade {
+            game_id: game_id.clone(),
+            reply_to: req_id.clone(),
+            event_id: EventId::new(),
+            player,
+            coord: Some(coord),
+            captured: vec![],
+        }
+    }
+
+    #[test]
+    fn rebuilds_from_events() {
+        let game_id = GameId::new();
+        let rules = Rules {
+            komi: Komi(0.5),
+            ..Rules::default()
+        };
+        let started = GameState::new(game_id.clone(), BoardSize::square(9), rules, 0);
+        let (r1, r2, r3) = (ReqId::new(), ReqId::new(), ReqId::new());
+        let first = made(&game_id, &r1, Player::BLACK, Coord::of(2, 2));
+        let second = made(&game_id, &r2, Player::WHITE, Coord::of(6, 6));
+        let events = vec![
+            (
+                xid(1),
+                Event::Ready(GameReady {
+                    game_id: game_id.clone(),
+                    sessions: (SessionId::new(), SessionId::new()),
+                    event_id: EventId::new(),
+                    board_size: BoardSize::square(9),
+                }),
+            ),
+            (xid(2), Event::Logged(started.clone())),
+            (
+                xid(3),
+                Event::Command(MakeMove {
+                    game_id: game_id.clone(),
+                    req_id: r1,
+                    player: Player::BLACK,
+                    coord: Some(Coord::of(2, 2)),
+                }),
+            ),
+            (xid(4), Event::Made(first.clone())),
+            // retried, and accepted a second time
+            (
+                xid(5),
+                Event::Made(MoveMade {
+                    event_id: EventId::new(),
+                    ..first.clone()
+                }),
+            ),
+            (xid(6), Event::Made(second.clone())),
+            (
+                xid(7),
+                Event::Command(MakeMove {
+                    game_id: game_id.clone(),
+                    req_id: r3.clone(),
+                    player: Player::BLACK,
+                    coord: Some(Coord::of(6, 6)),
+                }),
+            ),
+            (
+                xid(8),
+                Event::Made(made(
+                    &GameId::new(),
+                    &ReqId::new(),
+                    Player::BLACK,
+                    Coord::of(0, 0),
+                )),
+            ),
+        ];
+
+        let (rebuilt, orphans) = rebuild(&events);
+        assert_eq!(orphans, 1);
+        assert_eq!(rebuilt.len(), 1);
+        let mut expected = started.clone();
+        expected.apply(&first).unwrap();
+        expected.apply(&second).unwrap();
+        assert_eq!(rebuilt[0].game_state, expected);
+        assert_eq!(rebuilt[0].game_state.rules.komi, Komi(0.5));
+        assert_eq!(rebuilt[0].unanswered, vec![r3]);
+        assert!(rebuilt[0].unplayable.is_empty());
+    }
+
+    /// The changelog's record after each move, as its own
+    /// game state stream holds it
+    fn logged(game_state: &mut GameState, move_made: &MoveMade) -> Event {
+        game_state.apply(move_made).unwrap();
+        Event::Logged(game_state.clone())
+    }
+
+    #[test]
+    fn replays_undo() {
+        let game_id = GameId::new();
+        let started = GameState::new(game_id.clone(), BoardSize::square(9), Rules::default(), 0);
+        let first = made(&game_id, &ReqId::new(), Player::BLACK, Coord::of(2, 2));
+        let second = made(&game_id, &ReqId::new(), Player::WHITE, Coord::of(6, 6));
+        let again = made(&game_id, &ReqId::new(), Player::WHITE, Coord::of(4, 4));
+        let mut changelog = started.clone();
+        let mut events = vec![(xid(1), Event::Logged(started.clone()))];
+        events.push((xid(2), logged(&mut changelog, &first)));
+        events.push((xid(3), Event::Made(first.clone())));
+        events.push((xid(4), logged(&mut changelog, &second)));
+        events.push((xid(5), Event::Made(second)));
+        // white takes back their move, and plays elsewhere
+        let undone = changelog.replay(&[first]).unwrap();
+        events.push((xid(6), Event::Logged(undone.clone())));
+        let mut changelog = undone;
+        events.push((xid(7), logged(&mut changelog, &again)));
+        events.push((xid(8), Event::Made(again)));
+
+        let (rebuilt, orphans) = rebuild(&events);
+        assert_eq!(orphans, 0);
+        assert_eq!(rebuilt.len(), 1);
+        assert_eq!(rebuilt[0].game_state, changelog);
+        assert!(rebuilt[0].unplayable.is_empty());
+        assert_eq!(rebuilt[0].game_state.board.get(Coord::of(6, 6)), None)
+    }
+
+    #[test]
+    fn keeps_resignation() {
+        let game_id = GameId::new();
+        let started = GameState::new(game_id.clone(), BoardSize::square(9), Rules::default(), 0);
+        let first = made(&game_id, &ReqId::new(), Player::BLACK, Coord::of(2, 2));
+        let mut changelog = started.clone();
+        let mut events = vec![(xid(1), Event::Logged(started))];
+        events.push((xid(2), logged(&mut changelog, &first)));
+        events.push((xid(3), Event::Made(first)));
+        let resigned = GameState {
+            game_over: true,
+            result: Some(GameResult {
+                winner: Some(Player::BLACK),
+                reason: EndReason::Resignation,
+            }),
+            revision: changelog.revision + 1,
+            ..changelog
+        };
+        events.push((xid(4), Event::Logged(resigned.clone())));
+
+        let (rebuilt, _) = rebuild(&events);
+        assert_eq!(rebuilt[0].game_state, resigned);
+        let history = to_history(&resigned);
+        assert!(diverges(&rebuilt[0].game_state, Some(&resigned), Some(&history)).is_empty())
+    }
+
+    #[test]
+    fn reports_divergence() {
+        let game_id = GameId::new();
+        let start = GameState::new(game_id.clone(), BoardSize::square(9), Rules::default(), 0);
+        let mut rebuilt = start.clone();
+        rebuilt
+            .apply(&made(
+                &game_id,
+                &ReqId::new(),
+                Player::BLACK,
+                Coord::of(2, 2),
+            ))
+            .unwrap();
+        let history = to_history(&rebuilt);
+
+        assert!(diverges(&rebuilt, Some(&rebuilt), Some(&history)).is_empty());
+        assert_eq!(
+            diverges(&rebuilt, None, None),
+            vec![Divergence::MissingSnapshot, Divergence::MissingHistory]
+        );
+        assert_eq!(
+            diverges(&rebuilt, Some(&start), Some(&[])),
+            vec![
+                Divergence::SnapshotBehind {
+                    stored: 0,
+                    rebuilt: 1
+                },
+                Divergence::HistoryDiffers
+            ]
+        );
+        let ahead = diverges(&start, Some(&rebuilt), Some(&history));
+        assert_eq!(
+            ahead[0],
+            Divergence::SnapshotAhead {
+                stored: 1,
+                rebuilt: 0
+            }
+        );
+        assert!(!ahead[0].restorable());
+
+        rebuilt.game_over = true;
+        assert!(diverges(&rebuilt, None, None).is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+use core_model::GameId;
+use move_model::GameState;
+use redis::{Client, Commands};
+use sync_model::Move;
+
+#[derive(Debug)]
+pub enum FetchErr {
+    Redis(redis::RedisError),
+    Deser(Box<bincode::ErrorKind>),
+}
+
+/// The changelog's snapshot of the game.  Reading it
+/// leaves the record's TTL alone.
+pub fn snapshot(game_id: &GameId, client: &Client) -> Result<Option<GameState>, FetchErr> {
+    let mut conn = client.get_connection()?;
+    let bytes: Option<Vec<u8>> =
+        conn.get(format!("/BUGOUT/micro_changelog/game_states/{}", game_id.0))?;
+    Ok(match bytes {
+        Some(b) => Some(GameState::from(&b)?),
+        None => None,
+    })
+}
+
+/// micro-sync's history of the game
+pub fn history(game_id: &GameId, client: &Client) -> Result<Option<Vec<Move>>, FetchErr> {
+    let mut conn = client.get_connection()?;
+    let bytes: Option<Vec<u8>> = conn.get(format!("/BUGOUT/micro_sync/history/{}", game_id.0))?;
+    Ok(match bytes {
+        Some(b) => Some(bincode::deserialize(&b)?),
+        None => None,
+    })
+}
+
+impl From<redis::RedisError> for FetchErr {
+    fn from(e: redis::RedisError) -> Self {
+        FetchErr::Redis(e)
+    }
+}
+impl From<Box<bincode::ErrorKind>> for FetchErr {
+    fn from(e: Box<bincode::ErrorKind>) -> Self {
+        FetchErr::Deser(e)
+    }
+}
@@ -0,0 +1,6 @@
+mod read;
+pub mod topics;
+mod xadd;
+
+pub use read::*;
+pub use xadd::*;
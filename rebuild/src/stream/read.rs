@@ -0,0 +1,59 @@
+use super::topics::*;
+use crate::replay::Event;
+use core_model::{GameId, ReqId};
+use log::warn;
+use move_model::{Coord, GameState, MakeMove, Player};
+use redis::streams::{StreamId, StreamRangeReply};
+use redis::{Client, Commands};
+use redis_streams::XReadEntryId;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Reads everything the streams still hold, oldest first.
+/// Entries which cannot be read are skipped with a warning.
+pub fn read_all(client: &Client) -> Result<Vec<(XReadEntryId, Event)>, redis::RedisError> {
+    let mut conn = client.get_connection()?;
+    let mut out = vec![];
+    for topic in &[GAME_READY, GAME_STATES_CHANGELOG, MAKE_MOVE, MOVE_MADE] {
+        let reply: StreamRangeReply = conn.xrange_all(*topic)?;
+        for entry in reply.ids {
+            match (XReadEntryId::from_str(&entry.id), deser(topic, &entry)) {
+                (Ok(xid), Some(event)) => out.push((xid, event)),
+                _ => warn!("skipping {} entry {}", topic, entry.id),
+            }
+        }
+    }
+    out.sort_by_key(|(xid, _)| *xid);
+    Ok(out)
+}
+
+fn deser(topic: &str, entry: &StreamId) -> Option<Event> {
+    let data: Option<Vec<u8>> = entry.get("data");
+    match (topic, data) {
+        (GAME_READY, Some(data)) => bincode::deserialize(&data).map(Event::Ready).ok(),
+        (GAME_STATES_CHANGELOG, Some(data)) => GameState::from(&data).map(Event::Logged).ok(),
+        (MAKE_MOVE, Some(data)) => bincode::deserialize(&data).map(Event::Command).ok(),
+        (MAKE_MOVE, None) => make_move_fields(entry).map(Event::Command),
+        (MOVE_MADE, Some(data)) => bincode::deserialize(&data).map(Event::Made).ok(),
+        _ => None,
+    }
+}
+
+/// The gateway and micro-sync still write make move
+/// commands as separate fields
+fn make_move_fields(entry: &StreamId) -> Option<MakeMove> {
+    let game_id: String = entry.get("game_id")?;
+    let req_id: String = entry.get("req_id")?;
+    let player: String = entry.get("player")?;
+    let x: Option<u16> = entry.get("coord_x");
+    let y: Option<u16> = entry.get("coord_y");
+    Some(MakeMove {
+        game_id: GameId(Uuid::from_str(&game_id).ok()?),
+        req_id: ReqId(Uuid::from_str(&req_id).ok()?),
+        player: Player::from(player.as_str()),
+        coord: match (x, y) {
+            (Some(x), Some(y)) => Some(Coord { x, y }),
+            _ => None,
+        },
+    })
+}
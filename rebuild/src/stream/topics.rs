@@ -0,0 +1,4 @@
+pub const GAME_READY: &str = "bugout-game-ready-ev";
+pub const GAME_STATES_CHANGELOG: &str = "bugout-game-states";
+pub const MAKE_MOVE: &str = "bugout-make-move-cmd";
+pub const MOVE_MADE: &str = "bugout-move-made-ev";
@@ -0,0 +1,37 @@
+use super::topics::GAME_STATES_CHANGELOG;
+use move_model::GameState;
+use redis::Client;
+
+#[derive(Debug)]
+pub enum StreamAddErr {
+    Redis(redis::RedisError),
+    Ser(Box<bincode::ErrorKind>),
+}
+
+/// Puts the rebuilt game back on the changelog.  The
+/// changelog, judge and micro-sync each store it from
+/// there, just as they do for the lobby's first record.
+pub fn xadd_game_state(game_state: &GameState, client: &Client) -> Result<(), StreamAddErr> {
+    let mut conn = client.get_connection()?;
+    redis::cmd("XADD")
+        .arg(GAME_STATES_CHANGELOG)
+        .arg("MAXLEN")
+        .arg("~")
+        .arg("1000")
+        .arg("*")
+        .arg("data")
+        .arg(game_state.serialize()?)
+        .query::<String>(&mut conn)?;
+    Ok(())
+}
+
+impl From<redis::RedisError> for StreamAddErr {
+    fn from(e: redis::RedisError) -> Self {
+        StreamAddErr::Redis(e)
+    }
+}
+impl From<Box<bincode::ErrorKind>> for StreamAddErr {
+    fn from(e: Box<bincode::ErrorKind>) -> Self {
+        StreamAddErr::Ser(e)
+    }
+}
@@ -35,21 +35,25 @@ fn main() {
                 let game_id = GameId(Uuid::new_v4());
                 websocket
                     .write_message(Message::Binary(
-                        bincode::serialize(&ComputeMove {
+                        bincode::serialize(&BotCommand::Compute(ComputeMove {
                             game_id: game_id.clone(),
+                            req_id: ReqId::new(),
                             game_state: GameState {
-                                board: Board {
-                                    size: 9,
-                                    ..Board::default()
-                                },
+                                board: Board::new(BoardSize::square(9)),
                                 captures: Captures::default(),
                                 moves: vec![],
                                 player_up: Player::BLACK,
                                 turn: 1,
                                 game_id: game_id,
+                                ko: None,
+                                rules: Rules::default(),
+                                game_over: false,
+                                result: None,
+                                handicap: vec![],
+                                revision: 0,
                             },
                             max_visits: None,
-                        })
+                        }))
                         .expect("ser"),
                     ))
                     .unwrap();
@@ -13,6 +13,8 @@ pub enum KataGoParseErr {
     UuidErr(uuid::Error),
     WrongFormat,
     Coord,
+    /// Terminated queries may come back without moves
+    NoMoves,
 }
 impl From<uuid::Error> for KataGoParseErr {
     fn from(u: uuid::Error) -> Self {
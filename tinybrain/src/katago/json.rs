@@ -1,6 +1,6 @@
 use crate::err::*;
 
-use bot_model::api::ComputeMove;
+use bot_model::api::{CancelCompute, ComputeMove};
 use core_model::*;
 use move_model::*;
 use serde_derive::{Deserialize, Serialize};
@@ -10,8 +10,11 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Serialize, PartialEq, PartialOrd)]
 pub struct KataGoQuery {
     pub id: Id,
-    #[serde(rename = "initialStone")]
+    #[serde(rename = "initialStones")]
     pub initial_stones: Vec<Move>,
+    /// Who moves first, when it isn't black
+    #[serde(rename = "initialPlayer", skip_serializing_if = "Option::is_none")]
+    pub initial_player: Option<String>,
     pub moves: Vec<Move>,
     pub rules: Rules,
     pub komi: Komi,
@@ -32,11 +35,49 @@ pub struct KataGoResponse {
     pub turn_number: u32,
 }
 
+/// KataGo's answer to a query which takes some action,
+/// rather than asking for analysis
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct KataGoAck {
+    pub id: Id,
+    pub action: String,
+}
+
+/// Everything KataGo writes to stdout
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum KataGoOutput {
+    Analysis(KataGoResponse),
+    Ack(KataGoAck),
+}
+
+/// Stops the analysis of an earlier query.  See
+/// https://github.com/lightvector/KataGo/blob/master/docs/Analysis_Engine.md#special-action-queries
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct KataGoTerminate {
+    pub id: Id,
+    pub action: String,
+    #[serde(rename = "terminateId")]
+    pub terminate_id: Id,
+}
+
 /// In the form of
-/// `GAMEID_BUGOUTTURN_WHOMOVED`
+/// `GAMEID_BUGOUTTURN_WHOMOVED_REQID`
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd)]
 pub struct Id(pub String);
 
+impl Id {
+    pub fn of(game_id: &GameId, turn: u16, player: Player, req_id: &ReqId) -> Self {
+        Id(format!(
+            "{}_{}_{}_{}",
+            game_id.0,
+            turn,
+            player.to_string(),
+            req_id.0
+        ))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Eq, PartialOrd)]
 pub struct Move(pub String, pub KataCoordOrPass);
 
@@ -50,6 +91,8 @@ pub struct Komi(pub f32);
 pub struct MoveInfo {
     pub order: u32,
     pub r#move: String,
+    /// Reported from black's perspective, see analysis.cfg
+    pub winrate: f32,
 }
 
 pub const PASS: &str = "pass";
@@ -88,6 +131,7 @@ impl KataCoordOrPass {
 impl KataGoQuery {
     pub fn from(compute_move: ComputeMove) -> Result<Self, CoordOutOfRange> {
         let game_id = compute_move.game_id;
+        let req_id = compute_move.req_id;
         let game_state = compute_move.game_state;
         let moves_with_errors: Vec<Result<Move, CoordOutOfRange>> = game_state
             .moves
@@ -105,15 +149,22 @@ impl KataGoQuery {
                 .collect();
 
             Ok(KataGoQuery {
-                id: Id(format!(
-                    "{}_{}_{}",
-                    game_id.0,
-                    game_state.turn,
-                    game_state.player_up.to_string()
-                )),
+                id: Id::of(&game_id, game_state.turn, game_state.player_up, &req_id),
+                initial_stones: game_state
+                    .handicap
+                    .iter()
+                    .filter_map(|c| Move::from(Player::BLACK, Some(*c)).ok())
+                    .collect(),
+                initial_player: if game_state.handicap.is_empty() {
+                    None
+                } else {
+                    Some("W".to_string())
+                },
                 moves,
-                board_x_size: game_state.board.size,
-                board_y_size: game_state.board.size,
+                board_x_size: game_state.board.size.width,
+                board_y_size: game_state.board.size.height,
+                rules: Rules::from(&game_state.rules),
+                komi: Komi(game_state.rules.komi.0),
                 max_visits: compute_move.max_visits,
                 ..Default::default()
             })
@@ -121,12 +172,34 @@ impl KataGoQuery {
     }
 
     pub fn to_json(&self) -> Result<Vec<u8>, serde_json::Error> {
-        Ok(format!("{}\n", &serde_json::to_string(self)?)
-            .as_bytes()
-            .to_vec())
+        to_json_line(self)
     }
 }
 
+const TERMINATE: &str = "terminate";
+
+impl KataGoTerminate {
+    pub fn from(cancel: &CancelCompute) -> Self {
+        let terminate_id = Id::of(&cancel.game_id, cancel.turn, cancel.player, &cancel.req_id);
+        KataGoTerminate {
+            id: Id(format!("{}_{}", TERMINATE, terminate_id.0)),
+            action: TERMINATE.to_string(),
+            terminate_id,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, serde_json::Error> {
+        to_json_line(self)
+    }
+}
+
+/// KataGo reads one query per line
+fn to_json_line<T: serde::Serialize>(query: &T) -> Result<Vec<u8>, serde_json::Error> {
+    Ok(format!("{}\n", &serde_json::to_string(query)?)
+        .as_bytes()
+        .to_vec())
+}
+
 impl KataGoResponse {
     pub fn game_id(&self) -> Result<GameId, KataGoParseErr> {
         let parts: Vec<&str> = self.id.0.split('_').collect();
@@ -137,12 +210,30 @@ impl KataGoResponse {
         }
     }
 
+    pub fn turn(&self) -> Result<u16, KataGoParseErr> {
+        let parts: Vec<&str> = self.id.0.split('_').collect();
+        if parts.len() < 3 {
+            Err(KataGoParseErr::WrongFormat)
+        } else {
+            parts[1].parse().map_err(|_| KataGoParseErr::WrongFormat)
+        }
+    }
+
     pub fn player(&self) -> Result<Player, KataGoParseErr> {
         let parts: Vec<&str> = self.id.0.split('_').collect();
         if parts.len() < 2 {
             Err(KataGoParseErr::WrongFormat)
         } else {
-            Ok(Player::from_str(parts[2]))
+            Ok(Player::from(parts[2]))
+        }
+    }
+
+    pub fn req_id(&self) -> Result<ReqId, KataGoParseErr> {
+        let parts: Vec<&str> = self.id.0.split('_').collect();
+        if parts.len() < 4 {
+            Err(KataGoParseErr::WrongFormat)
+        } else {
+            Ok(ReqId(Uuid::from_str(parts[3])?))
         }
     }
 }
@@ -153,6 +244,7 @@ impl Default for KataGoQuery {
         KataGoQuery {
             id: Id(uuid::Uuid::new_v4().to_string()),
             initial_stones: vec![],
+            initial_player: None,
             moves: vec![],
             komi: Komi::default(),
             rules: Rules::default(),
@@ -163,15 +255,36 @@ impl Default for KataGoQuery {
     }
 }
 
+impl Rules {
+    /// KataGo accepts a compact rules string such as
+    /// `koSIMPLEscoreAREAtaxNONEsui0`, which lets us
+    /// match the ko, suicide and scoring rules that micro-judge enforces.
+    pub fn from(rules: &move_model::Rules) -> Self {
+        let ko = match rules.ko {
+            KoRule::Simple => "SIMPLE",
+            KoRule::PositionalSuperko => "POSITIONAL",
+        };
+        let score = match rules.scoring {
+            ScoringRule::Area => "AREA",
+            ScoringRule::Territory => "TERRITORY",
+        };
+        let sui = match rules.suicide {
+            SuicideRule::Forbidden => 0,
+            SuicideRule::MultiStoneAllowed => 1,
+        };
+        Rules(format!("ko{}score{}taxNONEsui{}", ko, score, sui))
+    }
+}
+
 impl Default for Rules {
     fn default() -> Self {
-        Rules("tromp-taylor".to_string())
+        Rules::from(&move_model::Rules::default())
     }
 }
 
 impl Default for Komi {
     fn default() -> Self {
-        Komi(6.5)
+        Komi(DEFAULT_KOMI)
     }
 }
 
@@ -189,9 +302,16 @@ mod tests {
             captures: Captures::default(),
             board: Board::default(),
             game_id: game_id.clone(),
+            ko: None,
+            rules: move_model::Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         };
         ComputeMove {
             game_id,
+            req_id: ReqId(Uuid::nil()),
             game_state,
             max_visits: None,
         }
@@ -232,15 +352,25 @@ mod tests {
             captures: Captures::default(),
             board: Board::default(),
             game_id: game_id.clone(),
+            ko: None,
+            rules: move_model::Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         };
         let compute_move = ComputeMove {
             game_id,
+            req_id: ReqId(Uuid::nil()),
             game_state,
             max_visits: None,
         };
 
         let expected = KataGoQuery {
-            id: Id("00000000-0000-0000-0000-000000000000_4_WHITE".to_string()),
+            id: Id(
+                "00000000-0000-0000-0000-000000000000_4_WHITE_00000000-0000-0000-0000-000000000000"
+                    .to_string(),
+            ),
             moves: vec![
                 Move("B".to_string(), KataCoordOrPass("(0,0)".to_string())),
                 Move("W".to_string(), KataCoordOrPass("(1,1)".to_string())),
@@ -270,9 +400,16 @@ mod tests {
             board: Board::default(),
             player_up: Player::WHITE,
             game_id: game_id.clone(),
+            ko: None,
+            rules: move_model::Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         };
         let compute_move = ComputeMove {
             game_id,
+            req_id: ReqId(Uuid::nil()),
             game_state,
             max_visits: None,
         };
@@ -287,24 +424,34 @@ mod tests {
             moves: vec![],
             turn: 1,
             player_up: Player::WHITE,
-            board: Board {
-                pieces: std::collections::HashMap::new(),
-                size: 9,
-            },
+            board: Board::new(BoardSize {
+                width: 9,
+                height: 7,
+            }),
             game_id: game_id.clone(),
             captures: Captures::default(),
+            ko: None,
+            rules: move_model::Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         };
         let compute_move = ComputeMove {
             game_id,
+            req_id: ReqId(Uuid::nil()),
             game_state,
             max_visits: None,
         };
 
         let expected = KataGoQuery {
-            id: Id("00000000-0000-0000-0000-000000000000_1_WHITE".to_string()),
+            id: Id(
+                "00000000-0000-0000-0000-000000000000_1_WHITE_00000000-0000-0000-0000-000000000000"
+                    .to_string(),
+            ),
             moves: vec![],
             board_x_size: 9,
-            board_y_size: 9,
+            board_y_size: 7,
             ..KataGoQuery::default()
         };
 
@@ -322,15 +469,25 @@ mod tests {
             captures: Captures::default(),
             board: Board::default(),
             game_id: game_id.clone(),
+            ko: None,
+            rules: move_model::Rules::default(),
+            game_over: false,
+            result: None,
+            handicap: vec![],
+            revision: 0,
         };
         let compute_move = ComputeMove {
             game_id,
+            req_id: ReqId(Uuid::nil()),
             game_state,
             max_visits: Some(25),
         };
 
         let expected = KataGoQuery {
-            id: Id("00000000-0000-0000-0000-000000000000_1_BLACK".to_string()),
+            id: Id(
+                "00000000-0000-0000-0000-000000000000_1_BLACK_00000000-0000-0000-0000-000000000000"
+                    .to_string(),
+            ),
             moves: vec![],
             max_visits: Some(25),
             ..KataGoQuery::default()
@@ -368,4 +525,101 @@ mod tests {
 
         assert!(json.contains("\"komi\":6.5"))
     }
+
+    #[test]
+    fn ko_rule_reaches_katago() {
+        let mut compute_move = basic_move();
+        compute_move.game_state.rules.ko = KoRule::PositionalSuperko;
+
+        let query = KataGoQuery::from(compute_move).expect("query formed");
+
+        assert_eq!(
+            query.rules,
+            Rules("koPOSITIONALscoreAREAtaxNONEsui0".to_string())
+        )
+    }
+
+    #[test]
+    fn suicide_rule_reaches_katago() {
+        let mut compute_move = basic_move();
+        compute_move.game_state.rules.suicide = SuicideRule::MultiStoneAllowed;
+
+        let query = KataGoQuery::from(compute_move).expect("query formed");
+
+        assert_eq!(
+            query.rules,
+            Rules("koSIMPLEscoreAREAtaxNONEsui1".to_string())
+        )
+    }
+
+    #[test]
+    fn game_komi_reaches_katago() {
+        let mut compute_move = basic_move();
+        compute_move.game_state.rules =
+            move_model::Rules::of(Some(Ruleset::Japanese), Some(0.5)).unwrap();
+
+        let query = KataGoQuery::from(compute_move).expect("query formed");
+
+        assert_eq!(query.komi, Komi(0.5));
+        assert_eq!(
+            query.rules,
+            Rules("koSIMPLEscoreTERRITORYtaxNONEsui0".to_string())
+        )
+    }
+
+    #[test]
+    fn handicap_stones_reach_katago() {
+        let mut compute_move = basic_move();
+        compute_move.game_state = GameState::new(
+            compute_move.game_id.clone(),
+            BoardSize::square(9),
+            move_model::Rules::default(),
+            2,
+        );
+
+        let query = KataGoQuery::from(compute_move).expect("query formed");
+
+        assert_eq!(
+            query.initial_stones,
+            vec![
+                Move("B".to_string(), KataCoordOrPass("(6,2)".to_string())),
+                Move("B".to_string(), KataCoordOrPass("(2,6)".to_string()))
+            ]
+        );
+        assert_eq!(query.initial_player, Some("W".to_string()));
+        let json = serde_json::to_string(&query).expect("json");
+        assert!(json.contains("\"initialStones\""))
+    }
+
+    #[test]
+    fn terminate_query_names_the_cancelled_one() {
+        let terminate = KataGoTerminate::from(&CancelCompute {
+            game_id: GameId(Uuid::nil()),
+            req_id: ReqId(Uuid::nil()),
+            turn: 4,
+            player: Player::WHITE,
+        });
+        let json = String::from_utf8(terminate.to_json().expect("json")).expect("utf8");
+
+        assert!(json.ends_with('\n'));
+        assert!(json.contains("\"action\":\"terminate\""));
+        assert!(json.contains("\"terminateId\":\"00000000-0000-0000-0000-000000000000_4_WHITE_00000000-0000-0000-0000-000000000000\""))
+    }
+
+    #[test]
+    fn output_tells_acks_from_analysis() {
+        let ack: KataGoOutput = serde_json::from_str(
+            "{\"action\":\"terminate\",\"id\":\"terminate_x\",\"terminateId\":\"x\"}",
+        )
+        .expect("ack");
+        assert!(matches!(ack, KataGoOutput::Ack(_)));
+
+        let analysis: KataGoOutput =
+            serde_json::from_str("{\"id\":\"x_3_BLACK\",\"moveInfos\":[],\"turnNumber\":3}")
+                .expect("analysis");
+        match analysis {
+            KataGoOutput::Analysis(response) => assert_eq!(response.turn().expect("turn"), 3),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
 }
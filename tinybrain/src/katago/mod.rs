@@ -2,6 +2,7 @@ use crate::*;
 use crossbeam_channel::{select, Receiver, Sender};
 use json::*;
 use log::{error, info};
+use move_model::Player;
 use std::convert::TryFrom;
 use std::io::{BufRead, BufReader, Write};
 use std::process::{Child, Command, Stdio};
@@ -23,15 +24,15 @@ lazy_static! {
     ];
 }
 
-pub fn start(move_computed_in: Sender<MoveComputed>, compute_move_out: Receiver<ComputeMove>) {
+pub fn start(move_computed_in: Sender<MoveComputed>, bot_command_out: Receiver<BotCommand>) {
     let mut process = launch_child().expect("failed to start katago");
 
     let mut child_in = process.stdin.take().expect("no handle to stdin");
     thread::spawn(move || loop {
         select! {
-                recv(compute_move_out) -> request =>
+                recv(bot_command_out) -> request =>
                     match request {
-                        Ok(r) =>{
+                        Ok(BotCommand::Compute(r)) =>{
                             if let Ok(query) = KataGoQuery::from(r) {
                                 match query.to_json() {
                                     Ok(qj) => match child_in.write(&qj) {
@@ -44,6 +45,16 @@ pub fn start(move_computed_in: Sender<MoveComputed>, compute_move_out: Receiver<
                                 error!("ERR Bad coord in game state")
                             }
                         }
+                        Ok(BotCommand::Cancel(cancel)) => {
+                            let terminate = KataGoTerminate::from(&cancel);
+                            match terminate.to_json() {
+                                Ok(tj) => match child_in.write(&tj) {
+                                    Err(why) => panic!("couldn't write to stdin: {:?}", why),
+                                    Ok(_) => info!("> terminate {:?}", terminate.terminate_id),
+                                },
+                                Err(e) => error!("failed terminate ser {:?}", e)
+                            }
+                        }
                         Err(_) => error!("Error receiving compute move in katago select")
                     },
         }
@@ -58,16 +69,18 @@ pub fn start(move_computed_in: Sender<MoveComputed>, compute_move_out: Receiver<
             Err(why) => panic!("couldn't read stdout: {:?}", why),
             Ok(_) => {
                 info!("< katago respond:\n{}", s);
-                let deser: Result<KataGoResponse, _> = serde_json::from_str(&s.trim());
+                let deser: Result<KataGoOutput, _> = serde_json::from_str(&s.trim());
                 match deser {
                     Err(e) => error!("Deser error in katago response: {:?}\nraw: {}", e, s),
-                    Ok(kgr) => {
-                        if let Err(e) = move_computed_in
-                            .send(MoveComputed::try_from(kgr).expect("couldnt make a movecomputed"))
-                        {
-                            error!("failed to send move_computed {:?}", e)
+                    Ok(KataGoOutput::Ack(ack)) => info!("katago {} {:?}", ack.action, ack.id),
+                    Ok(KataGoOutput::Analysis(kgr)) => match MoveComputed::try_from(kgr) {
+                        Ok(mc) => {
+                            if let Err(e) = move_computed_in.send(mc) {
+                                error!("failed to send move_computed {:?}", e)
+                            }
                         }
-                    }
+                        Err(e) => error!("couldnt make a movecomputed {:?}", e),
+                    },
                 }
             }
         }
@@ -75,11 +88,29 @@ pub fn start(move_computed_in: Sender<MoveComputed>, compute_move_out: Receiver<
 }
 
 const PASS: &str = "PASS";
+/// Don't give up during the opening, however grim it looks
+const RESIGN_MIN_TURN: u32 = 50;
+const RESIGN_WINRATE: f32 = 0.02;
+
+fn hopeless(response: &KataGoResponse, player: Player) -> bool {
+    let black_winrate = response.move_infos[0].winrate;
+    let winrate = match player {
+        Player::BLACK => black_winrate,
+        Player::WHITE => 1.0 - black_winrate,
+    };
+    response.turn_number >= RESIGN_MIN_TURN && winrate < RESIGN_WINRATE
+}
+
 impl TryFrom<KataGoResponse> for MoveComputed {
     type Error = crate::err::KataGoParseErr;
     fn try_from(response: KataGoResponse) -> Result<Self, Self::Error> {
         let game_id = response.game_id()?;
+        let req_id = response.req_id()?;
         let player = response.player()?;
+        let turn = response.turn()?;
+        if response.move_infos.is_empty() {
+            return Err(crate::err::KataGoParseErr::NoMoves);
+        }
         let alpha_num_or_pass = &response.move_infos[0].r#move;
 
         let alphanum_coord = if alpha_num_or_pass.to_ascii_uppercase().trim() == PASS {
@@ -95,8 +126,11 @@ impl TryFrom<KataGoResponse> for MoveComputed {
 
         Ok(MoveComputed {
             game_id,
+            req_id,
             player,
+            turn,
             alphanum_coord,
+            resign: hopeless(&response, player),
         })
     }
 }
@@ -125,18 +159,22 @@ mod tests {
     #[test]
     fn move_computed_from_play() {
         let actual = MoveComputed::try_from(KataGoResponse {
-            id: Id(format!("{}_1_WHITE", Uuid::nil().to_string())),
+            id: Id(format!("{0}_1_WHITE_{0}", Uuid::nil().to_string())),
             turn_number: 1,
             move_infos: vec![MoveInfo {
                 r#move: "B3".to_string(),
                 order: 0,
+                winrate: 0.5,
             }],
         })
         .expect("fail");
         let expected = MoveComputed {
             game_id: GameId(Uuid::nil()),
+            req_id: ReqId(Uuid::nil()),
             alphanum_coord: Some(AlphaNumCoord('B', 3)),
             player: Player::WHITE,
+            turn: 1,
+            resign: false,
         };
         assert_eq!(actual, expected)
     }
@@ -144,18 +182,22 @@ mod tests {
     #[test]
     fn y_coord_not_truncated() {
         let actual = MoveComputed::try_from(KataGoResponse {
-            id: Id(format!("{}_1_WHITE", Uuid::nil().to_string())),
+            id: Id(format!("{0}_1_WHITE_{0}", Uuid::nil().to_string())),
             turn_number: 1,
             move_infos: vec![MoveInfo {
                 r#move: "D10".to_string(),
                 order: 0,
+                winrate: 0.5,
             }],
         })
         .expect("fail");
         let expected = MoveComputed {
             game_id: GameId(Uuid::nil()),
+            req_id: ReqId(Uuid::nil()),
             alphanum_coord: Some(AlphaNumCoord('D', 10)),
             player: Player::WHITE,
+            turn: 1,
+            resign: false,
         };
         assert_eq!(actual, expected)
     }
@@ -163,19 +205,53 @@ mod tests {
     #[test]
     fn move_computed_from_pass() {
         let actual = MoveComputed::try_from(KataGoResponse {
-            id: Id(format!("{}_1_BLACK", Uuid::nil().to_string())),
+            id: Id(format!("{0}_1_BLACK_{0}", Uuid::nil().to_string())),
             turn_number: 1,
             move_infos: vec![MoveInfo {
                 r#move: "pass".to_string(),
                 order: 0,
+                winrate: 0.5,
             }],
         })
         .expect("fail");
         let expected = MoveComputed {
             game_id: GameId(Uuid::nil()),
+            req_id: ReqId(Uuid::nil()),
             alphanum_coord: None,
             player: Player::BLACK,
+            turn: 1,
+            resign: false,
         };
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn resigns_when_hopeless() {
+        let actual = MoveComputed::try_from(KataGoResponse {
+            id: Id(format!("{0}_80_WHITE_{0}", Uuid::nil().to_string())),
+            turn_number: 80,
+            move_infos: vec![MoveInfo {
+                r#move: "C3".to_string(),
+                order: 0,
+                winrate: 0.99,
+            }],
+        })
+        .expect("fail");
+        assert!(actual.resign)
+    }
+
+    #[test]
+    fn no_resignation_in_opening() {
+        let actual = MoveComputed::try_from(KataGoResponse {
+            id: Id(format!("{0}_4_WHITE_{0}", Uuid::nil().to_string())),
+            turn_number: 4,
+            move_infos: vec![MoveInfo {
+                r#move: "C3".to_string(),
+                order: 0,
+                winrate: 0.99,
+            }],
+        })
+        .expect("fail");
+        assert!(!actual.resign)
+    }
 }
@@ -1,6 +1,6 @@
 extern crate tinybrain;
 
-use bot_model::api::{ComputeMove, MoveComputed};
+use bot_model::api::{BotCommand, MoveComputed};
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use log::info;
 use std::thread;
@@ -14,11 +14,10 @@ async fn main() {
     info!("🔢 {}", VERSION);
     env::init();
 
-    let (compute_move_in, compute_move_out): (Sender<ComputeMove>, Receiver<ComputeMove>) =
-        unbounded();
+    let (bot_command_in, bot_command_out): (Sender<BotCommand>, Receiver<BotCommand>) = unbounded();
     let (move_computed_in, move_computed_out): (Sender<MoveComputed>, Receiver<MoveComputed>) =
         unbounded();
 
-    thread::spawn(|| katago::start(move_computed_in, compute_move_out));
-    websocket::start(compute_move_in, move_computed_out).await;
+    thread::spawn(|| katago::start(move_computed_in, bot_command_out));
+    websocket::start(bot_command_in, move_computed_out).await;
 }
@@ -18,13 +18,10 @@ use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 const RETRY_MAX_EXP: u32 = 5;
 const RETRY_BASE_SECS: u64 = 2;
 
-pub async fn start(
-    compute_move_in: Sender<ComputeMove>,
-    move_computed_out: Receiver<MoveComputed>,
-) {
+pub async fn start(bot_command_in: Sender<BotCommand>, move_computed_out: Receiver<MoveComputed>) {
     let mut retry_exp: u32 = 0;
     loop {
-        retry_exp = match connect_loop(compute_move_in.clone(), move_computed_out.clone()).await {
+        retry_exp = match connect_loop(bot_command_in.clone(), move_computed_out.clone()).await {
             InitialConnection::Succeeded => 0,
             InitialConnection::Failed => cmp::min(retry_exp + 1, RETRY_MAX_EXP),
         };
@@ -57,7 +54,7 @@ enum InitialConnection {
 /// some moves, and then being drops counts as a successful
 /// initial connection.
 async fn connect_loop(
-    compute_move_in: Sender<ComputeMove>,
+    bot_command_in: Sender<BotCommand>,
     move_computed_out: Receiver<MoveComputed>,
 ) -> InitialConnection {
     if let Ok((socket, response)) = connect_async(create_http_request()).await {
@@ -78,9 +75,7 @@ async fn connect_loop(
                     Some(msg) => {
                         if let Ok(msg) = msg {
                             match msg {
-                                Message::Binary(data) => {
-                                    handle_compute_move(data, &compute_move_in)
-                                }
+                                Message::Binary(data) => handle_bot_command(data, &bot_command_in),
                                 Message::Text(_) => warn!("Unexpected text data"),
                                 Message::Close(_) => break InitialConnection::Succeeded,
                                 _ => (), // PingPong
@@ -132,15 +127,16 @@ async fn respond_all_moves_computed(
     }
 }
 
-/// Deserialize the compute move request received from botlink,
-/// then send it over crossbeam to be handled by the katago thread.
-fn handle_compute_move(data: Vec<u8>, compute_move_in: &Sender<ComputeMove>) {
-    let cm: Result<ComputeMove, _> = bincode::deserialize(&data);
-    match cm {
-        Err(e) => error!("failed to deser compute move {:?}", e),
-        Ok(compute_move) => {
-            if let Err(e) = compute_move_in.send(compute_move) {
-                error!("failed to send compute move {:?}", e)
+/// Deserialize the request received from botlink, either to
+/// compute a move or to cancel one, then send it over crossbeam
+/// to be handled by the katago thread.
+fn handle_bot_command(data: Vec<u8>, bot_command_in: &Sender<BotCommand>) {
+    let command: Result<BotCommand, _> = bincode::deserialize(&data);
+    match command {
+        Err(e) => error!("failed to deser bot command {:?}", e),
+        Ok(command) => {
+            if let Err(e) = bot_command_in.send(command) {
+                error!("failed to send bot command {:?}", e)
             }
         }
     }
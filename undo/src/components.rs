@@ -1,4 +1,4 @@
-use crate::repo::{BotnessRepo, GameStateRepo};
+use crate::repo::{BotnessRepo, GameStateRepo, PendingUndoRepo, UndoCountRepo};
 use crate::stream::{XAck, XAdd, XRead};
 
 use std::rc::Rc;
@@ -9,6 +9,8 @@ pub struct Components {
     pub xread: Box<dyn XRead>,
     pub botness_repo: Box<dyn BotnessRepo>,
     pub game_state_repo: Box<dyn GameStateRepo>,
+    pub pending_undo_repo: Box<dyn PendingUndoRepo>,
+    pub undo_count_repo: Box<dyn UndoCountRepo>,
 }
 
 const REDIS_URL: &str = "redis://redis/";
@@ -22,6 +24,8 @@ impl Components {
         Components {
             botness_repo: Box::new(client.clone()),
             game_state_repo: Box::new(client.clone()),
+            pending_undo_repo: Box::new(client.clone()),
+            undo_count_repo: Box::new(client.clone()),
             xadd: Box::new(client.clone()),
             xack: Box::new(client.clone()),
             xread: Box::new(client),
@@ -3,9 +3,14 @@
 /// on UndoMove:
 ///     it should emit a new game state
 ///     it should emit MoveUndone
+///     unless both players are human, in which case it
+///     should emit UndoRequested and wait for AcceptUndo
+///     unless the game's undo policy forbids it
+///     it should audit every undo, carried out or refused
 /// see also https://github.com/Terkwood/BUGOUT/issues/479
 mod components;
 mod repo;
 pub mod stream;
+mod time;
 
 pub use components::*;
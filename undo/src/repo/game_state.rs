@@ -15,7 +15,7 @@ impl GameStateRepo for Rc<Client> {
         let mut conn = self.get_connection()?;
         let data: Option<Vec<u8>> = conn.get(key(game_id))?;
         Ok(if let Some(bytes) = data {
-            Some(bincode::deserialize(&bytes)?)
+            Some(GameState::from(&bytes)?)
         } else {
             None
         })
@@ -1,10 +1,14 @@
 mod botness;
 mod expire;
 mod game_state;
+mod pending;
+mod undo_count;
 
 pub use botness::*;
 use expire::*;
 pub use game_state::*;
+pub use pending::*;
+pub use undo_count::*;
 
 #[derive(Debug)]
 pub enum RepoErr {
@@ -0,0 +1,87 @@
+use super::{expire, RepoErr};
+use core_model::GameId;
+use redis::{Client, Commands};
+use serde_derive::{Deserialize, Serialize};
+use std::rc::Rc;
+use undo_model::api::UndoMove;
+
+/// An undo which waits on the opponent's answer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingUndo {
+    pub undo_move: UndoMove,
+    /// The turn at the time of the request.  If anyone
+    /// moves in the meantime, the request no longer applies.
+    pub turn: u16,
+    /// The turn which the game goes back to
+    pub to_turn: u16,
+    pub expires_at_millis: u64,
+}
+
+/// Undo requests which are waiting on the opponent, at
+/// most one per game.  A sorted set of their deadlines
+/// lets us find the ones that nobody answered.
+pub trait PendingUndoRepo {
+    fn get(&self, game_id: &GameId) -> Result<Option<PendingUndo>, RepoErr>;
+    fn put(&self, pending: &PendingUndo) -> Result<(), RepoErr>;
+    fn del(&self, game_id: &GameId) -> Result<(), RepoErr>;
+    /// Removes and returns the requests whose deadline has passed
+    fn take_expired(&self, now_millis: u64) -> Result<Vec<PendingUndo>, RepoErr>;
+}
+
+const DEADLINES_KEY: &str = "/BUGOUT/undo/pending_deadlines";
+
+impl PendingUndoRepo for Rc<Client> {
+    fn get(&self, game_id: &GameId) -> Result<Option<PendingUndo>, RepoErr> {
+        let mut conn = self.get_connection()?;
+        let data: Option<Vec<u8>> = conn.get(pending_id(game_id))?;
+        Ok(if let Some(bytes) = data {
+            Some(bincode::deserialize(&bytes)?)
+        } else {
+            None
+        })
+    }
+
+    fn put(&self, pending: &PendingUndo) -> Result<(), RepoErr> {
+        let game_id = &pending.undo_move.game_id;
+        let key = pending_id(game_id);
+        let mut conn = self.get_connection()?;
+        let bytes = bincode::serialize(pending)?;
+        conn.set(&key, bytes)?;
+        expire(&key, &mut conn)?;
+        conn.zadd(
+            DEADLINES_KEY,
+            game_id.0.to_string(),
+            pending.expires_at_millis,
+        )?;
+        Ok(())
+    }
+
+    fn del(&self, game_id: &GameId) -> Result<(), RepoErr> {
+        let mut conn = self.get_connection()?;
+        conn.del(pending_id(game_id))?;
+        conn.zrem(DEADLINES_KEY, game_id.0.to_string())?;
+        Ok(())
+    }
+
+    fn take_expired(&self, now_millis: u64) -> Result<Vec<PendingUndo>, RepoErr> {
+        let mut conn = self.get_connection()?;
+        let game_ids: Vec<String> = conn.zrangebyscore(DEADLINES_KEY, 0, now_millis)?;
+        let mut out = vec![];
+        for id in game_ids {
+            let key = format!("{}{}", PENDING_PREFIX, id);
+            let data: Option<Vec<u8>> = conn.get(&key)?;
+            if let Some(bytes) = data {
+                out.push(bincode::deserialize(&bytes)?)
+            }
+            conn.del(&key)?;
+            conn.zrem(DEADLINES_KEY, &id)?;
+        }
+        Ok(out)
+    }
+}
+
+const PENDING_PREFIX: &str = "/BUGOUT/undo/pending/";
+
+fn pending_id(game_id: &GameId) -> String {
+    format!("{}{}", PENDING_PREFIX, game_id.0.to_string())
+}
@@ -0,0 +1,32 @@
+use super::{expire, RepoErr};
+use core_model::GameId;
+use move_model::Player;
+use redis::{Client, Commands};
+use std::rc::Rc;
+
+/// How many times each player has taken moves back in a
+/// game, so that the game's undo policy can be enforced
+pub trait UndoCountRepo {
+    fn get(&self, game_id: &GameId, player: Player) -> Result<u16, RepoErr>;
+    fn incr(&self, game_id: &GameId, player: Player) -> Result<u16, RepoErr>;
+}
+
+impl UndoCountRepo for Rc<Client> {
+    fn get(&self, game_id: &GameId, player: Player) -> Result<u16, RepoErr> {
+        let mut conn = self.get_connection()?;
+        let count: Option<u16> = conn.get(count_id(game_id, player))?;
+        Ok(count.unwrap_or(0))
+    }
+
+    fn incr(&self, game_id: &GameId, player: Player) -> Result<u16, RepoErr> {
+        let key = count_id(game_id, player);
+        let mut conn = self.get_connection()?;
+        let count: u16 = conn.incr(&key, 1)?;
+        expire(&key, &mut conn)?;
+        Ok(count)
+    }
+}
+
+fn count_id(game_id: &GameId, player: Player) -> String {
+    format!("/BUGOUT/undo/count/{}_{}", game_id.0, player.to_string())
+}
@@ -9,6 +9,9 @@ pub fn create_consumer_group(client: &redis::Client) {
         topics::GAME_STATES_CHANGELOG,
         topics::BOT_ATTACHED,
         topics::UNDO_MOVE,
+        topics::UNDO_TO_TURN,
+        topics::ACCEPT_UNDO,
+        topics::DECLINE_UNDO,
     ];
     for topic in to_create {
         let created: Result<(), _> = conn.xgroup_create_mkstream(topic, GROUP_NAME, "$");
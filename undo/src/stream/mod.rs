@@ -23,11 +23,593 @@ pub enum StreamOutput {
     MU(MoveUndone),
     LOG(GameState),
     REJECT(UndoMove),
+    REQ(UndoRequested),
+    AUDIT(UndoAudited),
 }
 
 #[derive(Clone, Debug)]
 pub enum StreamInput {
     UM(UndoMove),
+    UT(UndoToTurn),
     LOG(GameState),
     BA(BotAttached),
+    AU(AcceptUndo),
+    DU(DeclineUndo),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::repo::*;
+    use crate::Components;
+    use core_model::{EventId, GameId, ReqId};
+    use move_model::{BoardSize, Coord, MoveMade, Player, Rules, UndoPolicy};
+    use redis_streams::XReadEntryId;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::mpsc::{channel, Receiver, Sender};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    struct FakeBotnessRepo {
+        contents: Arc<Mutex<HashMap<Player, Botness>>>,
+    }
+    impl BotnessRepo for FakeBotnessRepo {
+        fn get(&self, _game_id: &GameId, player: Player) -> Result<Botness, RepoErr> {
+            let data = self.contents.lock().expect("mutex");
+            Ok(data.get(&player).copied().unwrap_or(Botness::IsHuman))
+        }
+
+        fn put(&self, _game_id: &GameId, player: Player, botness: Botness) -> Result<(), RepoErr> {
+            self.contents.lock().expect("mutex").insert(player, botness);
+            Ok(())
+        }
+    }
+
+    struct FakeGameStateRepo {
+        contents: Arc<Mutex<Option<GameState>>>,
+    }
+    impl GameStateRepo for FakeGameStateRepo {
+        fn get(&self, _game_id: &GameId) -> Result<Option<GameState>, RepoErr> {
+            Ok(self.contents.lock().expect("mutex").clone())
+        }
+
+        fn put(&self, game_state: &GameState) -> Result<(), RepoErr> {
+            *self.contents.lock().expect("mutex") = Some(game_state.clone());
+            Ok(())
+        }
+    }
+
+    struct FakePendingUndoRepo {
+        contents: Arc<Mutex<Option<PendingUndo>>>,
+    }
+    impl PendingUndoRepo for FakePendingUndoRepo {
+        fn get(&self, _game_id: &GameId) -> Result<Option<PendingUndo>, RepoErr> {
+            Ok(self.contents.lock().expect("mutex").clone())
+        }
+
+        fn put(&self, pending: &PendingUndo) -> Result<(), RepoErr> {
+            *self.contents.lock().expect("mutex") = Some(pending.clone());
+            Ok(())
+        }
+
+        fn del(&self, _game_id: &GameId) -> Result<(), RepoErr> {
+            *self.contents.lock().expect("mutex") = None;
+            Ok(())
+        }
+
+        fn take_expired(&self, now_millis: u64) -> Result<Vec<PendingUndo>, RepoErr> {
+            let mut data = self.contents.lock().expect("mutex");
+            match &*data {
+                Some(pending) if pending.expires_at_millis <= now_millis => {
+                    Ok(data.take().into_iter().collect())
+                }
+                _ => Ok(vec![]),
+            }
+        }
+    }
+
+    struct FakeUndoCountRepo {
+        contents: Arc<Mutex<HashMap<Player, u16>>>,
+    }
+    impl UndoCountRepo for FakeUndoCountRepo {
+        fn get(&self, _game_id: &GameId, player: Player) -> Result<u16, RepoErr> {
+            let data = self.contents.lock().expect("mutex");
+            Ok(data.get(&player).copied().unwrap_or(0))
+        }
+
+        fn incr(&self, _game_id: &GameId, player: Player) -> Result<u16, RepoErr> {
+            let mut data = self.contents.lock().expect("mutex");
+            let count = data.entry(player).or_insert(0);
+            *count += 1;
+            Ok(*count)
+        }
+    }
+
+    struct FakeXAdd(Sender<StreamOutput>);
+    impl XAdd for FakeXAdd {
+        fn xadd(&self, output: &StreamOutput) -> Result<(), StreamAddErr> {
+            self.0.send(output.clone()).map_err(|_| StreamAddErr::Conn)
+        }
+    }
+
+    struct FakeXAck;
+    impl XAck for FakeXAck {
+        fn ack_changelog(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_bot_attached(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_undo_move(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_undo_to_turn(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_accept_undo(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+        fn ack_decline_undo(&self, _: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+            Ok(())
+        }
+    }
+
+    struct FakeXRead {
+        sorted_data: Arc<Mutex<Vec<(XReadEntryId, StreamInput)>>>,
+        max_read_xid_ms: AtomicU64,
+    }
+    impl XRead for FakeXRead {
+        fn xread_sorted(&self) -> Result<Vec<(XReadEntryId, StreamInput)>, XReadErr> {
+            let max_xid_ms = self.max_read_xid_ms.load(Ordering::Relaxed);
+            let data: Vec<_> = self
+                .sorted_data
+                .lock()
+                .expect("lock")
+                .iter()
+                .filter(|(xid, _)| max_xid_ms < xid.millis_time)
+                .cloned()
+                .collect();
+            match data.last() {
+                // standing in for "xreadgroup >" semantics
+                Some((xid, _)) => self
+                    .max_read_xid_ms
+                    .store(xid.millis_time, Ordering::Relaxed),
+                // stop the test thread from spinning like crazy
+                None => thread::sleep(Duration::from_millis(10)),
+            }
+            Ok(data)
+        }
+    }
+
+    struct TestFakes {
+        sorted_stream: Arc<Mutex<Vec<(XReadEntryId, StreamInput)>>>,
+        game_state: Arc<Mutex<Option<GameState>>>,
+        pending: Arc<Mutex<Option<PendingUndo>>>,
+        botness: Arc<Mutex<HashMap<Player, Botness>>>,
+        undo_counts: Arc<Mutex<HashMap<Player, u16>>>,
+        output: Receiver<StreamOutput>,
+        time_ms: u64,
+    }
+
+    impl TestFakes {
+        fn emit(&mut self, input: StreamInput) {
+            self.time_ms += 1;
+            let xid = XReadEntryId {
+                millis_time: self.time_ms,
+                seq_no: 0,
+            };
+            self.sorted_stream.lock().expect("lock").push((xid, input));
+        }
+
+        fn recv(&self) -> StreamOutput {
+            self.output
+                .recv_timeout(Duration::from_secs(1))
+                .expect("stream output")
+        }
+
+        /// Nothing more is written
+        fn quiet(&self) -> bool {
+            self.output
+                .recv_timeout(Duration::from_millis(100))
+                .is_err()
+        }
+    }
+
+    fn spawn_process_thread() -> TestFakes {
+        let sorted_stream = Arc::new(Mutex::new(vec![]));
+        let game_state = Arc::new(Mutex::new(None));
+        let pending = Arc::new(Mutex::new(None));
+        let botness = Arc::new(Mutex::new(HashMap::new()));
+        let undo_counts = Arc::new(Mutex::new(HashMap::new()));
+        let (output_in, output) = channel();
+
+        let (ss, gs, pu, bn, uc) = (
+            sorted_stream.clone(),
+            game_state.clone(),
+            pending.clone(),
+            botness.clone(),
+            undo_counts.clone(),
+        );
+        thread::spawn(move || {
+            let components = Components {
+                xadd: Box::new(FakeXAdd(output_in)),
+                xack: Box::new(FakeXAck),
+                xread: Box::new(FakeXRead {
+                    sorted_data: ss,
+                    max_read_xid_ms: AtomicU64::new(0),
+                }),
+                botness_repo: Box::new(FakeBotnessRepo { contents: bn }),
+                game_state_repo: Box::new(FakeGameStateRepo { contents: gs }),
+                pending_undo_repo: Box::new(FakePendingUndoRepo { contents: pu }),
+                undo_count_repo: Box::new(FakeUndoCountRepo { contents: uc }),
+            };
+            process(&components)
+        });
+
+        TestFakes {
+            sorted_stream,
+            game_state,
+            pending,
+            botness,
+            undo_counts,
+            output,
+            time_ms: 0,
+        }
+    }
+
+    /// Black, white and black have each moved once
+    fn three_moves(rules: Rules) -> GameState {
+        let game_id = GameId::new();
+        let mut game_state = GameState::new(game_id.clone(), BoardSize::square(9), rules, 0);
+        for (i, player) in [Player::BLACK, Player::WHITE, Player::BLACK]
+            .iter()
+            .enumerate()
+        {
+            game_state
+                .apply(&MoveMade {
+                    game_id: game_id.clone(),
+                    reply_to: ReqId::new(),
+                    event_id: EventId::new(),
+                    player: *player,
+                    coord: Some(Coord::of(i as u16, 0)),
+                    captured: vec![],
+                })
+                .unwrap();
+        }
+        game_state
+    }
+
+    fn audited(output: StreamOutput) -> UndoAudited {
+        match output {
+            StreamOutput::AUDIT(audit) => audit,
+            other => panic!("expected an audit, got {:?}", other),
+        }
+    }
+
+    /// White asks to take back their move.  Black is human,
+    /// so black is asked first.
+    fn request_white_undo(fakes: &mut TestFakes) -> GameState {
+        let game_state = three_moves(Rules::default());
+        *fakes.game_state.lock().expect("lock") = Some(game_state.clone());
+        fakes.emit(StreamInput::UM(UndoMove {
+            game_id: game_state.game_id.clone(),
+            player: Player::WHITE,
+        }));
+        assert_eq!(
+            fakes.recv(),
+            StreamOutput::REQ(UndoRequested {
+                game_id: game_state.game_id.clone(),
+                player: Player::WHITE,
+                turn: 2,
+            })
+        );
+        assert!(fakes.pending.lock().expect("lock").is_some());
+        game_state
+    }
+
+    #[test]
+    fn test_undo_consent_accepted() {
+        let mut fakes = spawn_process_thread();
+        let game_state = request_white_undo(&mut fakes);
+        let game_id = game_state.game_id.clone();
+
+        // white cannot answer their own request
+        fakes.emit(StreamInput::AU(AcceptUndo {
+            game_id: game_id.clone(),
+            player: Player::WHITE,
+        }));
+        assert!(fakes.quiet());
+
+        fakes.emit(StreamInput::AU(AcceptUndo {
+            game_id: game_id.clone(),
+            player: Player::BLACK,
+        }));
+        let rolled_back = game_state.replay(&game_state.moves[..1]).unwrap();
+        assert_eq!(fakes.recv(), StreamOutput::LOG(rolled_back.clone()));
+        assert_eq!(
+            fakes.recv(),
+            StreamOutput::MU(MoveUndone {
+                game_id: game_id.clone(),
+                player: Player::WHITE,
+                game_state: rolled_back,
+            })
+        );
+        let audit = audited(fakes.recv());
+        assert_eq!(audit.outcome, UndoOutcome::Accepted);
+        assert_eq!((audit.turn, audit.to_turn), (Some(4), Some(2)));
+        assert!(fakes.pending.lock().expect("lock").is_none());
+        assert_eq!(
+            fakes.undo_counts.lock().expect("lock").get(&Player::WHITE),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn test_undo_consent_declined() {
+        let mut fakes = spawn_process_thread();
+        let game_state = request_white_undo(&mut fakes);
+        let undo_move = UndoMove {
+            game_id: game_state.game_id.clone(),
+            player: Player::WHITE,
+        };
+
+        fakes.emit(StreamInput::DU(DeclineUndo {
+            game_id: game_state.game_id.clone(),
+            player: Player::BLACK,
+        }));
+        assert_eq!(fakes.recv(), StreamOutput::REJECT(undo_move));
+        assert_eq!(audited(fakes.recv()).outcome, UndoOutcome::Declined);
+        assert!(fakes.pending.lock().expect("lock").is_none());
+        assert!(fakes.undo_counts.lock().expect("lock").is_empty());
+
+        // nothing is left to accept
+        fakes.emit(StreamInput::AU(AcceptUndo {
+            game_id: game_state.game_id.clone(),
+            player: Player::BLACK,
+        }));
+        assert!(fakes.quiet())
+    }
+
+    #[test]
+    fn test_undo_consent_timed_out() {
+        let mut fakes = spawn_process_thread();
+        let game_state = request_white_undo(&mut fakes);
+        let undo_move = UndoMove {
+            game_id: game_state.game_id.clone(),
+            player: Player::WHITE,
+        };
+
+        // the deadline passes without an answer
+        if let Some(pending) = fakes.pending.lock().expect("lock").as_mut() {
+            pending.expires_at_millis = 0
+        }
+        assert_eq!(fakes.recv(), StreamOutput::REJECT(undo_move));
+        let audit = audited(fakes.recv());
+        assert_eq!(audit.outcome, UndoOutcome::TimedOut);
+        assert_eq!((audit.turn, audit.to_turn), (Some(4), Some(2)));
+        assert!(fakes.pending.lock().expect("lock").is_none());
+
+        // a late answer changes nothing
+        fakes.emit(StreamInput::AU(AcceptUndo {
+            game_id: game_state.game_id.clone(),
+            player: Player::BLACK,
+        }));
+        assert!(fakes.quiet());
+        assert!(fakes.undo_counts.lock().expect("lock").is_empty())
+    }
+
+    #[test]
+    fn test_undo_against_bot_needs_no_consent() {
+        let mut fakes = spawn_process_thread();
+        let game_state = three_moves(Rules::default());
+        *fakes.game_state.lock().expect("lock") = Some(game_state.clone());
+        fakes
+            .botness
+            .lock()
+            .expect("lock")
+            .insert(Player::BLACK, Botness::IsBot);
+
+        fakes.emit(StreamInput::UM(UndoMove {
+            game_id: game_state.game_id.clone(),
+            player: Player::WHITE,
+        }));
+        match fakes.recv() {
+            StreamOutput::LOG(rolled_back) => assert_eq!(rolled_back.moves.len(), 1),
+            other => panic!("expected a game state, got {:?}", other),
+        }
+        assert!(matches!(fakes.recv(), StreamOutput::MU(_)));
+        assert_eq!(audited(fakes.recv()).outcome, UndoOutcome::Accepted);
+        assert!(fakes.pending.lock().expect("lock").is_none())
+    }
+
+    /// Asks to go back to `turn` in a game against a bot,
+    /// which never has to consent
+    fn undo_to_turn(game_state: &GameState, player: Player, turn: u16) -> UndoAudited {
+        let mut fakes = spawn_process_thread();
+        *fakes.game_state.lock().expect("lock") = Some(game_state.clone());
+        fakes
+            .botness
+            .lock()
+            .expect("lock")
+            .insert(other_player(player), Botness::IsBot);
+        fakes.emit(StreamInput::UT(UndoToTurn {
+            game_id: game_state.game_id.clone(),
+            player,
+            turn,
+        }));
+        loop {
+            if let StreamOutput::AUDIT(audit) = fakes.recv() {
+                return audit;
+            }
+        }
+    }
+
+    fn other_player(player: Player) -> Player {
+        match player {
+            Player::BLACK => Player::WHITE,
+            Player::WHITE => Player::BLACK,
+        }
+    }
+
+    #[test]
+    fn test_undo_to_earlier_turns_of_requester() {
+        let game_state = three_moves(Rules::default());
+        assert_eq!(game_state.turn, 4);
+
+        let audit = undo_to_turn(&game_state, Player::WHITE, 2);
+        assert_eq!(audit.outcome, UndoOutcome::Accepted);
+        assert_eq!((audit.turn, audit.to_turn), (Some(4), Some(2)));
+
+        let audit = undo_to_turn(&game_state, Player::BLACK, 1);
+        assert_eq!(audit.outcome, UndoOutcome::Accepted);
+        let audit = undo_to_turn(&game_state, Player::BLACK, 3);
+        assert_eq!(audit.outcome, UndoOutcome::Accepted)
+    }
+
+    #[test]
+    fn test_undo_to_turn_zero() {
+        let game_state = three_moves(Rules::default());
+        for player in [Player::BLACK, Player::WHITE] {
+            let audit = undo_to_turn(&game_state, player, 0);
+            assert_eq!(audit.outcome, UndoOutcome::NotAllowed)
+        }
+    }
+
+    #[test]
+    fn test_undo_to_current_turn() {
+        let game_state = three_moves(Rules::default());
+        let audit = undo_to_turn(&game_state, Player::WHITE, game_state.turn);
+        assert_eq!(audit.outcome, UndoOutcome::NotAllowed)
+    }
+
+    #[test]
+    fn test_undo_beyond_current_turn() {
+        let game_state = three_moves(Rules::default());
+        for turn in [game_state.turn + 1, u16::MAX] {
+            let audit = undo_to_turn(&game_state, Player::BLACK, turn);
+            assert_eq!(audit.outcome, UndoOutcome::NotAllowed)
+        }
+    }
+
+    #[test]
+    fn test_undo_to_opponents_turn() {
+        let game_state = three_moves(Rules::default());
+        let audit = undo_to_turn(&game_state, Player::WHITE, 1);
+        assert_eq!(audit.outcome, UndoOutcome::NotAllowed);
+        let audit = undo_to_turn(&game_state, Player::BLACK, 2);
+        assert_eq!(audit.outcome, UndoOutcome::NotAllowed)
+    }
+
+    #[test]
+    fn test_undo_after_game_over() {
+        let mut game_state = three_moves(Rules::default());
+        game_state.game_over = true;
+        let audit = undo_to_turn(&game_state, Player::WHITE, 2);
+        assert_eq!(audit.outcome, UndoOutcome::NotAllowed);
+
+        let mut fakes = spawn_process_thread();
+        *fakes.game_state.lock().expect("lock") = Some(game_state.clone());
+        fakes.emit(StreamInput::UM(UndoMove {
+            game_id: game_state.game_id.clone(),
+            player: Player::WHITE,
+        }));
+        assert!(matches!(fakes.recv(), StreamOutput::REJECT(_)));
+        assert_eq!(audited(fakes.recv()).outcome, UndoOutcome::NotAllowed)
+    }
+
+    #[test]
+    fn test_undo_policy_per_game() {
+        let mut fakes = spawn_process_thread();
+        let game_state = three_moves(Rules {
+            undo: UndoPolicy::PerGame(1),
+            ..Rules::default()
+        });
+        let game_id = game_state.game_id.clone();
+        *fakes.game_state.lock().expect("lock") = Some(game_state.clone());
+        fakes
+            .botness
+            .lock()
+            .expect("lock")
+            .insert(Player::BLACK, Botness::IsBot);
+        let undo_move = UndoMove {
+            game_id: game_id.clone(),
+            player: Player::WHITE,
+        };
+
+        fakes.emit(StreamInput::UM(undo_move.clone()));
+        assert!(matches!(fakes.recv(), StreamOutput::LOG(_)));
+        assert!(matches!(fakes.recv(), StreamOutput::MU(_)));
+        let audit = audited(fakes.recv());
+        assert_eq!(audit.game_id, game_id);
+        assert_eq!(audit.player, Player::WHITE);
+        assert_eq!(audit.outcome, UndoOutcome::Accepted);
+        assert_eq!(
+            fakes.undo_counts.lock().expect("lock").get(&Player::WHITE),
+            Some(&1)
+        );
+
+        // the one undo allowed has been used
+        fakes.emit(StreamInput::UM(undo_move.clone()));
+        assert_eq!(fakes.recv(), StreamOutput::REJECT(undo_move));
+        let audit = audited(fakes.recv());
+        assert_eq!(audit.outcome, UndoOutcome::PolicyForbids);
+        assert_eq!((audit.turn, audit.to_turn), (Some(4), Some(2)));
+        assert_eq!(
+            fakes.undo_counts.lock().expect("lock").get(&Player::WHITE),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn test_undo_policy_disabled() {
+        let mut fakes = spawn_process_thread();
+        let game_state = three_moves(Rules {
+            undo: UndoPolicy::Disabled,
+            ..Rules::default()
+        });
+        *fakes.game_state.lock().expect("lock") = Some(game_state.clone());
+        let undo_move = UndoMove {
+            game_id: game_state.game_id.clone(),
+            player: Player::WHITE,
+        };
+
+        fakes.emit(StreamInput::UM(undo_move.clone()));
+        assert_eq!(fakes.recv(), StreamOutput::REJECT(undo_move));
+        assert_eq!(audited(fakes.recv()).outcome, UndoOutcome::PolicyForbids);
+        // nobody is asked to consent
+        assert!(fakes.pending.lock().expect("lock").is_none());
+        assert!(fakes.undo_counts.lock().expect("lock").is_empty());
+        assert!(fakes.quiet())
+    }
+
+    #[test]
+    fn test_undo_counted_per_player() {
+        let mut fakes = spawn_process_thread();
+        let game_state = three_moves(Rules::default());
+        *fakes.game_state.lock().expect("lock") = Some(game_state.clone());
+        fakes
+            .undo_counts
+            .lock()
+            .expect("lock")
+            .insert(Player::BLACK, 7);
+        fakes
+            .botness
+            .lock()
+            .expect("lock")
+            .insert(Player::BLACK, Botness::IsBot);
+
+        fakes.emit(StreamInput::UM(UndoMove {
+            game_id: game_state.game_id.clone(),
+            player: Player::WHITE,
+        }));
+        assert!(matches!(fakes.recv(), StreamOutput::LOG(_)));
+        assert!(matches!(fakes.recv(), StreamOutput::MU(_)));
+        assert_eq!(audited(fakes.recv()).outcome, UndoOutcome::Accepted);
+
+        let counts = fakes.undo_counts.lock().expect("lock");
+        assert_eq!(counts.get(&Player::WHITE), Some(&1));
+        assert_eq!(counts.get(&Player::BLACK), Some(&7))
+    }
 }
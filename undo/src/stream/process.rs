@@ -1,4 +1,4 @@
-use super::undo::consume_undo;
+use super::undo::*;
 use super::*;
 use crate::repo::Botness;
 use crate::Components;
@@ -18,6 +18,10 @@ pub fn process(reg: &Components) {
             Err(e) => error!("Stream err {:?}", e),
         }
 
+        if let Err(e) = expire_undo_requests(reg) {
+            error!("could not expire undo requests {:?}", e)
+        }
+
         unacked.ack_all(&reg)
     }
 }
@@ -31,6 +35,21 @@ fn consume(_xid: XReadEntryId, event: &StreamInput, reg: &Components) {
                 error!("could not process undo move event {:?}", e)
             }
         }
+        StreamInput::UT(undo_to_turn) => {
+            if let Err(e) = consume_undo_to_turn(undo_to_turn, reg) {
+                error!("could not process undo to turn event {:?}", e)
+            }
+        }
+        StreamInput::AU(accept_undo) => {
+            if let Err(e) = consume_accept_undo(accept_undo, reg) {
+                error!("could not process accept undo event {:?}", e)
+            }
+        }
+        StreamInput::DU(decline_undo) => {
+            if let Err(e) = consume_decline_undo(decline_undo, reg) {
+                error!("could not process decline undo event {:?}", e)
+            }
+        }
     }
 }
 
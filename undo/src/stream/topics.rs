@@ -1,5 +1,12 @@
 pub const UNDO_MOVE: &str = "bugout-undo-move-cmd";
+pub const UNDO_TO_TURN: &str = "bugout-undo-to-turn-cmd";
 pub const MOVE_UNDONE: &str = "bugout-move-undone-ev";
 pub const UNDO_REJECTED: &str = "bugout-undo-rejected-ev";
+pub const UNDO_REQUESTED: &str = "bugout-undo-requested-ev";
+/// Every undo carried out or refused, for the archive
+/// and the stats tooling
+pub const UNDO_AUDITED: &str = "bugout-undo-audited-ev";
+pub const ACCEPT_UNDO: &str = "bugout-accept-undo-cmd";
+pub const DECLINE_UNDO: &str = "bugout-decline-undo-cmd";
 pub const GAME_STATES_CHANGELOG: &str = "bugout-game-states";
 pub const BOT_ATTACHED: &str = "bugout-bot-attached-ev";
@@ -1,85 +1,215 @@
 use super::*;
-use crate::repo::Botness;
+use crate::repo::{Botness, PendingUndo};
+use crate::time::now_millis;
 use crate::Components;
-use move_model::{Board, Captures, MoveMade, Player};
+use move_model::{ApplyErr, Player};
 
+/// How long the opponent has to answer an undo request
+const UNDO_REQUEST_TIMEOUT_MS: u64 = 30_000;
+
+/// Takes back the requester's last move and the reply to it
 pub fn consume_undo(um: &UndoMove, reg: &Components) -> Result<(), UndoProcessingErr> {
+    takeback(um, |game_state| game_state.turn.saturating_sub(2), reg)
+}
+
+pub fn consume_undo_to_turn(ut: &UndoToTurn, reg: &Components) -> Result<(), UndoProcessingErr> {
+    let um = UndoMove {
+        game_id: ut.game_id.clone(),
+        player: ut.player,
+    };
+    takeback(&um, |_| ut.turn, reg)
+}
+
+fn takeback(
+    um: &UndoMove,
+    to_turn: impl Fn(&GameState) -> u16,
+    reg: &Components,
+) -> Result<(), UndoProcessingErr> {
     let botness = reg.botness_repo.get(&um.game_id, um.player)?;
     let requester_is_human = botness == Botness::IsHuman;
 
     if let Some(game_state) = reg.game_state_repo.get(&um.game_id)? {
-        let player_up_is_human: bool = requester_is_human && game_state.player_up == um.player;
-        let at_least_two_moves_made: bool = game_state.moves.len() > 1;
+        let to_turn = to_turn(&game_state);
+        let turns = Some((game_state.turn, to_turn));
+        if !requester_is_human || !can_undo(&game_state, to_turn, um.player) {
+            reject(um, turns, UndoOutcome::NotAllowed, reg)?
+        } else if !policy_allows(um, &game_state, reg)? {
+            reject(um, turns, UndoOutcome::PolicyForbids, reg)?
+        } else {
+            let opponent = reg.botness_repo.get(&um.game_id, other_player(um.player))?;
+            if opponent == Botness::IsBot {
+                undo(um, &game_state, to_turn, reg)?
+            } else {
+                request_consent(um, &game_state, to_turn, reg)?
+            }
+        }
+    } else {
+        reject(um, None, UndoOutcome::NotAllowed, reg)?
+    }
+
+    Ok(())
+}
+
+/// The requester's opponent agrees to take the moves back
+pub fn consume_accept_undo(au: &AcceptUndo, reg: &Components) -> Result<(), UndoProcessingErr> {
+    match reg.pending_undo_repo.get(&au.game_id)? {
+        Some(pending) if pending.undo_move.player != au.player => {
+            reg.pending_undo_repo.del(&au.game_id)?;
+            let um = &pending.undo_move;
+            let turns = Some((pending.turn, pending.to_turn));
+            match reg.game_state_repo.get(&au.game_id)? {
+                // nobody may have moved since the request
+                Some(game_state)
+                    if game_state.turn == pending.turn
+                        && can_undo(&game_state, pending.to_turn, um.player) =>
+                {
+                    undo(um, &game_state, pending.to_turn, reg)?
+                }
+                _ => reject(um, turns, UndoOutcome::NotAllowed, reg)?,
+            }
+        }
+        _ => log::warn!("No undo request to accept in {:?}", au.game_id),
+    }
+
+    Ok(())
+}
+
+pub fn consume_decline_undo(du: &DeclineUndo, reg: &Components) -> Result<(), UndoProcessingErr> {
+    match reg.pending_undo_repo.get(&du.game_id)? {
+        Some(pending) if pending.undo_move.player != du.player => {
+            reg.pending_undo_repo.del(&du.game_id)?;
+            let turns = Some((pending.turn, pending.to_turn));
+            reject(&pending.undo_move, turns, UndoOutcome::Declined, reg)?
+        }
+        _ => log::warn!("No undo request to decline in {:?}", du.game_id),
+    }
+
+    Ok(())
+}
+
+/// Rejects the undo requests which nobody answered in time
+pub fn expire_undo_requests(reg: &Components) -> Result<(), UndoProcessingErr> {
+    for pending in reg.pending_undo_repo.take_expired(now_millis())? {
+        log::info!("Undo request timed out in {:?}", pending.undo_move.game_id);
+        let turns = Some((pending.turn, pending.to_turn));
+        reject(&pending.undo_move, turns, UndoOutcome::TimedOut, reg)?
+    }
+
+    Ok(())
+}
 
-        if player_up_is_human && at_least_two_moves_made {
-            let rolled_back = rollback(&game_state);
+/// The game may go back to any earlier turn on which the
+/// requester moved, so that it is their turn once again
+fn can_undo(game_state: &GameState, to_turn: u16, requester: Player) -> bool {
+    let earlier_turn = to_turn >= 1 && to_turn < game_state.turn;
+    earlier_turn
+        && !game_state.game_over
+        && game_state
+            .moves
+            .get(to_turn as usize - 1)
+            .map(|m| m.player == requester)
+            .unwrap_or(false)
+}
+
+fn policy_allows(
+    um: &UndoMove,
+    game_state: &GameState,
+    reg: &Components,
+) -> Result<bool, UndoProcessingErr> {
+    let used = reg.undo_count_repo.get(&um.game_id, um.player)?;
+    Ok(game_state.rules.undo.allows(used))
+}
+
+fn undo(
+    um: &UndoMove,
+    game_state: &GameState,
+    to_turn: u16,
+    reg: &Components,
+) -> Result<(), UndoProcessingErr> {
+    let turns = Some((game_state.turn, to_turn));
+    match rollback(game_state, to_turn) {
+        Ok(rolled_back) => {
             reg.xadd.xadd(&StreamOutput::LOG(rolled_back.clone()))?;
             reg.xadd.xadd(&StreamOutput::MU(MoveUndone {
                 game_id: um.game_id.clone(),
                 player: um.player,
                 game_state: rolled_back,
             }))?;
-        } else {
-            reject(um, reg)?
+            reg.undo_count_repo.incr(&um.game_id, um.player)?;
+            Ok(audit(um, turns, UndoOutcome::Accepted, reg)?)
         }
-    } else {
-        reject(um, reg)?
+        Err(e) => {
+            log::error!("Cannot replay history of {:?}: {:?}", um.game_id, e);
+            Ok(reject(um, turns, UndoOutcome::NotAllowed, reg)?)
+        }
+    }
+}
+
+/// A human opponent has to agree before anything is
+/// taken back.  Only one request may wait at a time.
+fn request_consent(
+    um: &UndoMove,
+    game_state: &GameState,
+    to_turn: u16,
+    reg: &Components,
+) -> Result<(), UndoProcessingErr> {
+    if reg.pending_undo_repo.get(&um.game_id)?.is_some() {
+        let turns = Some((game_state.turn, to_turn));
+        return Ok(reject(um, turns, UndoOutcome::NotAllowed, reg)?);
     }
 
+    reg.pending_undo_repo.put(&PendingUndo {
+        undo_move: um.clone(),
+        turn: game_state.turn,
+        to_turn,
+        expires_at_millis: now_millis() + UNDO_REQUEST_TIMEOUT_MS,
+    })?;
+    reg.xadd.xadd(&StreamOutput::REQ(UndoRequested {
+        game_id: um.game_id.clone(),
+        player: um.player,
+        turn: to_turn,
+    }))?;
     Ok(())
 }
 
-fn reject(undo_move: &UndoMove, reg: &Components) -> Result<(), StreamAddErr> {
-    reg.xadd.xadd(&StreamOutput::REJECT(undo_move.clone()))
+/// Turns are the one at which the undo was asked for, and
+/// the one it would go back to, if the game is known
+fn reject(
+    undo_move: &UndoMove,
+    turns: Option<(u16, u16)>,
+    outcome: UndoOutcome,
+    reg: &Components,
+) -> Result<(), StreamAddErr> {
+    reg.xadd.xadd(&StreamOutput::REJECT(undo_move.clone()))?;
+    audit(undo_move, turns, outcome, reg)
 }
 
-fn rollback(game_state: &GameState) -> GameState {
-    let moves = {
-        let mut ms = game_state.moves.clone();
-        ms.pop();
-        ms.pop();
-        ms
-    };
-    let board = compute_board(&moves, game_state.board.size);
-    let captures = compute_captures(&moves);
-
-    GameState {
-        game_id: game_state.game_id.clone(),
-        turn: game_state.turn - 2,
-        player_up: game_state.player_up,
-        moves,
-        board,
-        captures,
-    }
+fn audit(
+    undo_move: &UndoMove,
+    turns: Option<(u16, u16)>,
+    outcome: UndoOutcome,
+    reg: &Components,
+) -> Result<(), StreamAddErr> {
+    reg.xadd.xadd(&StreamOutput::AUDIT(UndoAudited {
+        game_id: undo_move.game_id.clone(),
+        player: undo_move.player,
+        turn: turns.map(|(turn, _)| turn),
+        to_turn: turns.map(|(_, to_turn)| to_turn),
+        outcome,
+        epoch_millis: now_millis(),
+    }))
 }
 
-fn compute_captures(moves: &[MoveMade]) -> Captures {
-    let mut out = Captures::default();
-    for m in moves.iter() {
-        let c = m.captured.len() as u16;
-        if m.player == Player::BLACK {
-            out.black += c
-        } else {
-            out.white += c
-        }
-    }
-    out
+fn rollback(game_state: &GameState, to_turn: u16) -> Result<GameState, ApplyErr> {
+    let moves = &game_state.moves[..to_turn as usize - 1];
+    game_state.replay(moves)
 }
-fn compute_board(moves: &[MoveMade], size: u16) -> Board {
-    let mut out = Board {
-        size,
-        ..Default::default()
-    };
-    for m in moves.iter() {
-        for c in &m.captured {
-            out.pieces.remove(c);
-        }
 
-        if let Some(coord) = m.coord {
-            out.pieces.insert(coord, m.player);
-        }
+fn other_player(player: Player) -> Player {
+    match player {
+        Player::BLACK => Player::WHITE,
+        Player::WHITE => Player::BLACK,
     }
-    out
 }
 
 use crate::repo::RepoErr;
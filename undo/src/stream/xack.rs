@@ -9,6 +9,9 @@ pub trait XAck {
     fn ack_changelog(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_bot_attached(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
     fn ack_undo_move(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_undo_to_turn(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_accept_undo(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
+    fn ack_decline_undo(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr>;
 }
 
 pub struct StreamAckErr;
@@ -16,7 +19,10 @@ pub struct StreamAckErr;
 pub struct Unacknowledged {
     changelog: Vec<XReadEntryId>,
     undo_move: Vec<XReadEntryId>,
+    undo_to_turn: Vec<XReadEntryId>,
     bot_attached: Vec<XReadEntryId>,
+    accept_undo: Vec<XReadEntryId>,
+    decline_undo: Vec<XReadEntryId>,
 }
 
 impl XAck for std::rc::Rc<Client> {
@@ -29,6 +35,15 @@ impl XAck for std::rc::Rc<Client> {
     fn ack_undo_move(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
         ack(self, topics::UNDO_MOVE, xids)
     }
+    fn ack_undo_to_turn(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::UNDO_TO_TURN, xids)
+    }
+    fn ack_accept_undo(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::ACCEPT_UNDO, xids)
+    }
+    fn ack_decline_undo(&self, xids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
+        ack(self, topics::DECLINE_UNDO, xids)
+    }
 }
 
 fn ack(client: &Client, key: &str, ids: &[XReadEntryId]) -> Result<(), StreamAckErr> {
@@ -65,6 +80,13 @@ impl Unacknowledged {
                 self.undo_move.clear();
             }
         }
+        if !self.undo_to_turn.is_empty() {
+            if let Err(_e) = reg.xack.ack_undo_to_turn(&self.undo_to_turn) {
+                error!("ack for undo to turn failed")
+            } else {
+                self.undo_to_turn.clear();
+            }
+        }
         if !self.bot_attached.is_empty() {
             if let Err(_e) = reg.xack.ack_bot_attached(&self.bot_attached) {
                 error!("ack for bot attached failed")
@@ -72,13 +94,30 @@ impl Unacknowledged {
                 self.bot_attached.clear();
             }
         }
+        if !self.accept_undo.is_empty() {
+            if let Err(_e) = reg.xack.ack_accept_undo(&self.accept_undo) {
+                error!("ack for accept undo failed")
+            } else {
+                self.accept_undo.clear();
+            }
+        }
+        if !self.decline_undo.is_empty() {
+            if let Err(_e) = reg.xack.ack_decline_undo(&self.decline_undo) {
+                error!("ack for decline undo failed")
+            } else {
+                self.decline_undo.clear();
+            }
+        }
     }
 
     pub fn push(&mut self, xid: XReadEntryId, event: StreamInput) {
         match event {
             StreamInput::UM(_) => self.undo_move.push(xid),
+            StreamInput::UT(_) => self.undo_to_turn.push(xid),
             StreamInput::BA(_) => self.bot_attached.push(xid),
             StreamInput::LOG(_) => self.changelog.push(xid),
+            StreamInput::AU(_) => self.accept_undo.push(xid),
+            StreamInput::DU(_) => self.decline_undo.push(xid),
         }
     }
 }
@@ -89,7 +128,10 @@ impl Default for Unacknowledged {
         Self {
             changelog: Vec::with_capacity(INIT_ACK_CAPACITY),
             undo_move: Vec::with_capacity(INIT_ACK_CAPACITY),
+            undo_to_turn: Vec::with_capacity(INIT_ACK_CAPACITY),
             bot_attached: Vec::with_capacity(INIT_ACK_CAPACITY),
+            accept_undo: Vec::with_capacity(INIT_ACK_CAPACITY),
+            decline_undo: Vec::with_capacity(INIT_ACK_CAPACITY),
         }
     }
 }
@@ -18,6 +18,8 @@ pub enum StreamAddErr {
 const AUTO_ID: &str = "*";
 const DATA_KEY: &str = "data";
 const MAX_LEN: usize = 1000;
+/// The audit stream is read over many games
+const AUDIT_MAX_LEN: usize = 100_000;
 impl XAdd for Rc<Client> {
     fn xadd(&self, output: &StreamOutput) -> Result<(), StreamAddErr> {
         let (key, bytes_result) = match &output {
@@ -26,22 +28,34 @@ impl XAdd for Rc<Client> {
             StreamOutput::REJECT(original_undo) => {
                 (UNDO_REJECTED, bincode::serialize(&original_undo))
             }
+            StreamOutput::REQ(requested) => (UNDO_REQUESTED, bincode::serialize(&requested)),
+            StreamOutput::AUDIT(audited) => (UNDO_AUDITED, bincode::serialize(&audited)),
         };
         if let Ok(bytes) = bytes_result {
             let mut m: BTreeMap<&str, &[u8]> = BTreeMap::new();
 
             m.insert(DATA_KEY, &bytes);
 
-            xadd_io(&self, key, m)
+            let max_len = if key == UNDO_AUDITED {
+                AUDIT_MAX_LEN
+            } else {
+                MAX_LEN
+            };
+            xadd_io(self, key, m, max_len)
         } else {
             Err(StreamAddErr::Ser)
         }
     }
 }
 
-fn xadd_io(client: &Client, key: &str, m: BTreeMap<&str, &[u8]>) -> Result<(), StreamAddErr> {
+fn xadd_io(
+    client: &Client,
+    key: &str,
+    m: BTreeMap<&str, &[u8]>,
+    max_len: usize,
+) -> Result<(), StreamAddErr> {
     if let Ok(mut conn) = client.get_connection() {
-        conn.xadd_maxlen_map(key, StreamMaxlen::Approx(MAX_LEN), AUTO_ID, m)
+        conn.xadd_maxlen_map(key, StreamMaxlen::Approx(max_len), AUTO_ID, m)
             .map_err(|e| StreamAddErr::Redis(e))
     } else {
         Err(StreamAddErr::Conn)
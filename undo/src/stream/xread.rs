@@ -1,6 +1,7 @@
 use super::topics::*;
 use super::{StreamInput, GROUP_NAME};
 use log::{error, warn};
+use move_model::GameState;
 use redis::streams::{StreamReadOptions, StreamReadReply};
 use redis::{Client, Commands};
 use redis_streams::XReadEntryId;
@@ -40,8 +41,15 @@ impl XRead for Rc<Client> {
             .block(BLOCK_MS)
             .group(GROUP_NAME, CONSUMER_NAME);
         let xrr = conn.xread_options(
-            &[UNDO_MOVE, BOT_ATTACHED, GAME_STATES_CHANGELOG],
-            &[READ_OP, READ_OP, READ_OP],
+            &[
+                UNDO_MOVE,
+                UNDO_TO_TURN,
+                BOT_ATTACHED,
+                GAME_STATES_CHANGELOG,
+                ACCEPT_UNDO,
+                DECLINE_UNDO,
+            ],
+            &[READ_OP, READ_OP, READ_OP, READ_OP, READ_OP, READ_OP],
             opts,
         )?;
         let unsorted = deser(xrr)?;
@@ -66,9 +74,7 @@ fn deser(srr: StreamReadReply) -> Result<HashMap<XReadEntryId, StreamInput>, XRe
                 let maybe_data: Option<Vec<u8>> = e.get("data");
                 if let Some(data) = maybe_data {
                     let sd: Option<StreamInput> = if key == GAME_STATES_CHANGELOG {
-                        bincode::deserialize(&data)
-                            .map(|gs| StreamInput::LOG(gs))
-                            .ok()
+                        GameState::from(&data).map(|gs| StreamInput::LOG(gs)).ok()
                     } else if key == BOT_ATTACHED {
                         bincode::deserialize(&data)
                             .map(|ba| StreamInput::BA(ba))
@@ -77,6 +83,12 @@ fn deser(srr: StreamReadReply) -> Result<HashMap<XReadEntryId, StreamInput>, XRe
                         bincode::deserialize(&data)
                             .map(|um| StreamInput::UM(um))
                             .ok()
+                    } else if key == UNDO_TO_TURN {
+                        bincode::deserialize(&data).map(StreamInput::UT).ok()
+                    } else if key == ACCEPT_UNDO {
+                        bincode::deserialize(&data).map(StreamInput::AU).ok()
+                    } else if key == DECLINE_UNDO {
+                        bincode::deserialize(&data).map(StreamInput::DU).ok()
                     } else {
                         warn!("Unknown key {}", key);
                         None
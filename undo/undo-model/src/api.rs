@@ -9,6 +9,16 @@ pub struct UndoMove {
     pub player: Player,
 }
 
+/// Takes back every move from `turn` onwards, so that
+/// `turn` is the next one to be played
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoToTurn {
+    pub game_id: GameId,
+    pub player: Player,
+    pub turn: u16,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct MoveUndone {
@@ -16,3 +26,63 @@ pub struct MoveUndone {
     pub player: Player,
     pub game_state: GameState,
 }
+
+/// Asks the requester's opponent to agree to an undo.
+/// Only sent in games between two humans.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoRequested {
+    pub game_id: GameId,
+    /// The player who asked for the undo
+    pub player: Player,
+    /// The turn which the game would go back to
+    pub turn: u16,
+}
+
+/// The opponent agrees to an undo requested of them
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptUndo {
+    pub game_id: GameId,
+    /// The player who was asked
+    pub player: Player,
+}
+
+/// The opponent refuses an undo requested of them
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclineUndo {
+    pub game_id: GameId,
+    /// The player who was asked
+    pub player: Player,
+}
+
+/// Why an undo was or was not carried out
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoOutcome {
+    Accepted,
+    /// Nothing to take back, not the requester's turn,
+    /// or the game is over
+    NotAllowed,
+    /// The player has used up the undos which the
+    /// game's policy allows
+    PolicyForbids,
+    Declined,
+    TimedOut,
+}
+
+/// Written to the audit stream for every undo which is
+/// carried out or refused
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoAudited {
+    pub game_id: GameId,
+    /// The player who asked for the undo
+    pub player: Player,
+    /// The turn when the undo was asked for, and the one
+    /// it would go back to.  Unknown if the game was.
+    pub turn: Option<u16>,
+    pub to_turn: Option<u16>,
+    pub outcome: UndoOutcome,
+    pub epoch_millis: u64,
+}